@@ -0,0 +1,259 @@
+//! Account identifiers - the names deals files are stored under
+
+use crate::shared::error::AppError;
+use crate::shared::utils::cache_dir;
+use std::fmt;
+use std::path::PathBuf;
+
+/// Longest accepted account id (keeps `{id}.parquet` within file name limits)
+const MAX_ACCOUNT_ID_LENGTH: usize = 128;
+
+/// Error code of `AppError`s raised for invalid account ids
+pub const INVALID_ACCOUNT_CODE: &str = "invalid_account";
+
+/// Prefix of the accounts backtests write their synthetic deals to
+pub const BACKTEST_ACCOUNT_PREFIX: &str = "backtest_";
+
+/// Characters Windows does not allow in file names
+const RESERVED_CHARACTERS: [char; 9] = ['/', '\\', ':', '*', '?', '"', '<', '>', '|'];
+
+/// Device names Windows reserves whatever the extension
+const RESERVED_NAMES: [&str; 22] = [
+    "CON", "PRN", "AUX", "NUL", "COM1", "COM2", "COM3", "COM4", "COM5", "COM6", "COM7", "COM8", "COM9",
+    "LPT1", "LPT2", "LPT3", "LPT4", "LPT5", "LPT6", "LPT7", "LPT8", "LPT9",
+];
+
+/// Why a name was rejected as an account id
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AccountIdError {
+    Empty,
+    TooLong { length: usize },
+    /// Path separators, drive letters and characters not allowed in file names
+    ReservedCharacter { name: String, character: char },
+    ControlCharacter { name: String },
+    /// `.`, `..`, hidden names and names Windows silently trims
+    EdgeDot { name: String },
+    /// Names Windows maps to devices
+    ReservedName { name: String },
+    /// Ids that still end with `.parquet` once the extension is removed (`foo.parquet.parquet`);
+    /// parsing them again would give another account
+    ParquetExtension { name: String },
+}
+
+impl fmt::Display for AccountIdError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Empty => write!(f, "Account cannot be empty"),
+            Self::TooLong { length } => write!(
+                f,
+                "Account is {} characters long (at most {} allowed)",
+                length, MAX_ACCOUNT_ID_LENGTH
+            ),
+            Self::ReservedCharacter { name, character } => {
+                write!(f, "Invalid account '{}': '{}' is not allowed", name, character)
+            }
+            Self::ControlCharacter { name } => {
+                write!(f, "Invalid account '{}': control characters are not allowed", name.escape_debug())
+            }
+            Self::EdgeDot { name } => write!(f, "Invalid account '{}': it cannot start or end with '.'", name),
+            Self::ReservedName { name } => write!(f, "Invalid account '{}': the name is reserved", name),
+            Self::ParquetExtension { name } => {
+                write!(f, "Invalid account '{}': it cannot end with '.parquet'", name)
+            }
+        }
+    }
+}
+
+impl std::error::Error for AccountIdError {}
+
+impl From<AccountIdError> for AppError {
+    fn from(error: AccountIdError) -> Self {
+        AppError {
+            message: error.to_string(),
+            code: Some(INVALID_ACCOUNT_CODE.to_string()),
+        }
+    }
+}
+
+/// Validated account id, safe to use as a file name in the deals cache
+/// Every name received from the frontend or another tool (uploaded file names, insight
+/// `account_number`s, registry keys) goes through `parse` before touching the file system
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct AccountId(String);
+
+impl AccountId {
+    /// Parses an account id or deals file name (with or without .parquet)
+    /// Surrounding whitespace is trimmed; the id must be a single plain file name
+    pub fn parse(value: &str) -> Result<Self, AccountIdError> {
+        let value = value.trim();
+        let id = value.strip_suffix(".parquet").unwrap_or(value).trim_end();
+
+        if id.is_empty() {
+            return Err(AccountIdError::Empty);
+        }
+        let length = id.chars().count();
+        if length > MAX_ACCOUNT_ID_LENGTH {
+            return Err(AccountIdError::TooLong { length });
+        }
+        if let Some(character) = id.chars().find(|c| RESERVED_CHARACTERS.contains(c)) {
+            return Err(AccountIdError::ReservedCharacter {
+                name: id.to_string(),
+                character,
+            });
+        }
+        if id.chars().any(char::is_control) {
+            return Err(AccountIdError::ControlCharacter { name: id.to_string() });
+        }
+        if id.ends_with(".parquet") {
+            return Err(AccountIdError::ParquetExtension { name: id.to_string() });
+        }
+        if id.starts_with('.') || id.ends_with('.') {
+            return Err(AccountIdError::EdgeDot { name: id.to_string() });
+        }
+        let stem = id.split('.').next().unwrap_or(id).trim_end();
+        if RESERVED_NAMES.iter().any(|reserved| reserved.eq_ignore_ascii_case(stem)) {
+            return Err(AccountIdError::ReservedName { name: id.to_string() });
+        }
+
+        Ok(Self(id.to_string()))
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    pub fn into_string(self) -> String {
+        self.0
+    }
+
+    /// Name of the account's deals file
+    pub fn file_name(&self) -> String {
+        format!("{}.parquet", self.0)
+    }
+
+    /// Path of the account's deals file in the deals cache directory
+    pub fn deals_path(&self) -> Result<PathBuf, AppError> {
+        Ok(cache_dir::get_deals_cache_dir()?.join(self.file_name()))
+    }
+}
+
+impl fmt::Display for AccountId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(value: &str) -> Result<String, AccountIdError> {
+        AccountId::parse(value).map(AccountId::into_string)
+    }
+
+    #[test]
+    fn accepts_plain_names() {
+        assert_eq!(parse("5043757397"), Ok("5043757397".to_string()));
+        assert_eq!(parse("backtest_EURUSD_1H"), Ok("backtest_EURUSD_1H".to_string()));
+        assert_eq!(parse("FTMO 100k"), Ok("FTMO 100k".to_string()));
+        assert_eq!(parse("export.2024"), Ok("export.2024".to_string()));
+    }
+
+    #[test]
+    fn strips_extension_and_whitespace() {
+        assert_eq!(parse("foo.parquet"), Ok("foo".to_string()));
+        assert_eq!(parse(" foo "), Ok("foo".to_string()));
+        assert_eq!(parse(" foo.parquet "), Ok("foo".to_string()));
+    }
+
+    #[test]
+    fn rejects_double_extension() {
+        assert!(matches!(parse("foo.parquet.parquet"), Err(AccountIdError::ParquetExtension { .. })));
+    }
+
+    #[test]
+    fn rejects_empty() {
+        assert_eq!(parse(""), Err(AccountIdError::Empty));
+        assert_eq!(parse("   "), Err(AccountIdError::Empty));
+        assert_eq!(parse("\t\n"), Err(AccountIdError::Empty));
+        assert_eq!(parse(".parquet"), Err(AccountIdError::Empty));
+    }
+
+    #[test]
+    fn rejects_dot_names() {
+        for value in [".", "..", ".hidden", "foo."] {
+            assert!(matches!(parse(value), Err(AccountIdError::EdgeDot { .. })), "{:?}", value);
+        }
+    }
+
+    #[test]
+    fn rejects_paths() {
+        let cases = [
+            ("./x", '/'),
+            ("../x", '/'),
+            ("a/b", '/'),
+            ("a\\b", '\\'),
+            ("/etc/passwd", '/'),
+            ("C:\\x", ':'),
+            ("C:x", ':'),
+            ("..\\..\\x", '\\'),
+        ];
+        for (value, expected) in cases {
+            match parse(value) {
+                Err(AccountIdError::ReservedCharacter { character, .. }) => assert_eq!(character, expected, "{:?}", value),
+                other => panic!("{:?} parsed as {:?}", value, other),
+            }
+        }
+    }
+
+    #[test]
+    fn rejects_reserved_characters() {
+        for value in ["a*b", "a?b", "a\"b", "a<b", "a>b", "a|b"] {
+            assert!(matches!(parse(value), Err(AccountIdError::ReservedCharacter { .. })), "{:?}", value);
+        }
+    }
+
+    #[test]
+    fn rejects_control_characters() {
+        for value in ["a\0b", "a\nb", "a\u{7f}b"] {
+            assert!(matches!(parse(value), Err(AccountIdError::ControlCharacter { .. })), "{:?}", value);
+        }
+    }
+
+    #[test]
+    fn rejects_device_names() {
+        for value in ["CON", "con", "con.txt", "NUL.parquet", "com1", "LPT9.log", "AUX "] {
+            assert!(matches!(parse(value), Err(AccountIdError::ReservedName { .. })), "{:?}", value);
+        }
+        assert!(parse("CONSOLE").is_ok());
+        assert!(parse("COM10").is_ok());
+    }
+
+    #[test]
+    fn limits_length() {
+        assert!(parse(&"a".repeat(MAX_ACCOUNT_ID_LENGTH)).is_ok());
+        assert_eq!(
+            parse(&"a".repeat(MAX_ACCOUNT_ID_LENGTH + 1)),
+            Err(AccountIdError::TooLong {
+                length: MAX_ACCOUNT_ID_LENGTH + 1
+            })
+        );
+        // The extension does not count
+        assert!(parse(&format!("{}.parquet", "a".repeat(MAX_ACCOUNT_ID_LENGTH))).is_ok());
+    }
+
+    #[test]
+    fn parsing_is_idempotent() {
+        for value in ["foo.parquet", " 123 ", "export.2024"] {
+            let id = AccountId::parse(value).unwrap();
+            assert_eq!(AccountId::parse(id.as_str()), Ok(id.clone()));
+        }
+    }
+
+    #[test]
+    fn invalid_ids_carry_the_error_code() {
+        let error: AppError = AccountId::parse("../x").unwrap_err().into();
+        assert_eq!(error.code.as_deref(), Some(INVALID_ACCOUNT_CODE));
+        assert_eq!(error.into_command_error(), "invalid_account: Invalid account '../x': '/' is not allowed");
+    }
+}
@@ -0,0 +1,86 @@
+//! Accounts command handlers
+
+use crate::features::accounts::model::{Account, AccountMetadata};
+use crate::features::accounts::service::AccountService;
+use crate::shared::error::AppError;
+
+/// Tauri command: list_accounts
+/// Lists the accounts with stored deals or registry metadata
+/// 
+/// Returns the accounts sorted by id
+#[tauri::command]
+pub fn list_accounts() -> Result<Vec<Account>, String> {
+    AccountService::list_accounts()
+        .map_err(AppError::into_command_error)
+}
+
+/// Tauri command: get_account
+/// Gets an account with its registry metadata
+/// 
+/// # Arguments
+/// * `account` - The account id (deals file name, with or without .parquet)
+/// 
+/// Returns the account
+#[tauri::command]
+pub fn get_account(account: String) -> Result<Account, String> {
+    AccountService::get_account(&account)
+        .map_err(AppError::into_command_error)
+}
+
+/// Tauri command: update_account
+/// Replaces the metadata of an account (name, broker, server, currency, leverage,
+/// timezone offset, tags), registering the account if needed
+/// 
+/// # Arguments
+/// * `account` - The account id (deals file name, with or without .parquet)
+/// * `metadata` - The new metadata
+/// 
+/// Returns the updated account
+#[tauri::command]
+pub fn update_account(account: String, metadata: AccountMetadata) -> Result<Account, String> {
+    AccountService::update_account(&account, metadata)
+        .map_err(AppError::into_command_error)
+}
+
+/// Tauri command: delete_account
+/// Removes an account from the registry
+/// 
+/// # Arguments
+/// * `account` - The account id (deals file name, with or without .parquet)
+/// * `delete_deals` - Optional flag to delete the stored deals too (default: false)
+#[tauri::command]
+pub fn delete_account(account: String, delete_deals: Option<bool>) -> Result<(), String> {
+    AccountService::delete_account(&account, delete_deals.unwrap_or(false))
+        .map_err(AppError::into_command_error)
+}
+
+/// Tauri command: set_account_timezone_offset
+/// Sets the broker server timezone offset of an account
+/// 
+/// # Arguments
+/// * `account` - The account (deals file name, with or without .parquet)
+/// * `offset_minutes` - Offset of the server time from UTC in minutes (e.g. 120 for UTC+2)
+/// 
+/// Returns the updated account
+#[tauri::command]
+pub fn set_account_timezone_offset(
+    account: String,
+    offset_minutes: i64,
+) -> Result<Account, String> {
+    AccountService::set_timezone_offset(&account, offset_minutes)
+        .map_err(AppError::into_command_error)
+}
+
+/// Tauri command: set_account_currency
+/// Sets the currency the profits of an account are denominated in
+/// 
+/// # Arguments
+/// * `account` - The account (deals file name, with or without .parquet)
+/// * `currency` - Three-letter ISO currency code (e.g. "USD")
+/// 
+/// Returns the updated account
+#[tauri::command]
+pub fn set_account_currency(account: String, currency: String) -> Result<Account, String> {
+    AccountService::set_currency(&account, &currency)
+        .map_err(AppError::into_command_error)
+}
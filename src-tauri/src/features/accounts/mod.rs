@@ -0,0 +1,15 @@
+/// Accounts feature module
+pub mod account_id;
+pub mod command;
+pub mod service;
+pub mod model;
+pub mod registry;
+
+pub use command::{
+    list_accounts,
+    get_account,
+    update_account,
+    delete_account,
+    set_account_timezone_offset,
+    set_account_currency,
+};
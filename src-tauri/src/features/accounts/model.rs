@@ -0,0 +1,53 @@
+//! Account-related data models
+
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+
+/// Metadata of an account kept in the account registry
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct AccountMetadata {
+    /// Display name (e.g. "FTMO challenge"); insights also accept it in place of the account id
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub broker: Option<String>,
+    /// Trade server (e.g. "ICMarketsSC-Live07")
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub server: Option<String>,
+    /// ISO currency code the account's profits are denominated in (e.g. "USD")
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub currency: Option<String>,
+    /// Leverage as 1:N (e.g. 500)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub leverage: Option<u32>,
+    /// Offset of the broker server time from UTC in minutes (e.g. 120 for UTC+2)
+    /// Deal times are stored in server time; subtracting the offset gives UTC
+    #[serde(default)]
+    pub timezone_offset_minutes: i64,
+    #[serde(default)]
+    pub tags: Vec<String>,
+}
+
+/// An account: its deals file and registry metadata
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Account {
+    /// Account id (deals file name without .parquet)
+    pub id: String,
+    #[serde(flatten)]
+    pub metadata: AccountMetadata,
+    /// Whether deals are stored for the account
+    #[serde(default)]
+    pub has_deals: bool,
+    /// Whether the account holds the synthetic deals of a backtest (ids starting with `backtest_`)
+    /// Such accounts are left out when insights read every account
+    #[serde(default)]
+    pub is_backtest: bool,
+}
+
+/// An account with stored deals, as resolved for reading them
+#[derive(Debug, Clone)]
+pub struct ResolvedAccount {
+    pub id: String,
+    pub deals_path: PathBuf,
+    pub metadata: AccountMetadata,
+}
@@ -0,0 +1,68 @@
+//! Account registry persisted next to the deals files
+
+use crate::features::accounts::model::AccountMetadata;
+use crate::shared::error::AppError;
+use crate::shared::utils::cache_dir;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::sync::Mutex;
+
+/// Serializes read-modify-write cycles on the registry file
+static REGISTRY_LOCK: Mutex<()> = Mutex::new(());
+
+/// Metadata of all registered accounts stored as JSON in the deals cache directory
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct AccountRegistry {
+    /// Metadata keyed by account id (deals file name without extension)
+    #[serde(default)]
+    pub accounts: BTreeMap<String, AccountMetadata>,
+}
+
+impl AccountRegistry {
+    /// Loads the registry, returning an empty registry if the file does not exist yet
+    pub fn load() -> Result<Self, AppError> {
+        let path = cache_dir::get_account_registry_path()?;
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+
+        let content = fs::read_to_string(&path)
+            .map_err(|e| AppError::new(format!("Failed to read account registry: {}", e)))?;
+        serde_json::from_str(&content)
+            .map_err(|e| AppError::new(format!("Failed to parse account registry: {}", e)))
+    }
+
+    /// Writes the registry to disk
+    pub fn save(&self) -> Result<(), AppError> {
+        let path = cache_dir::get_account_registry_path()?;
+        if let Some(parent) = path.parent() {
+            cache_dir::ensure_cache_dir(parent)?;
+        }
+
+        let content = serde_json::to_string_pretty(self)
+            .map_err(|e| AppError::new(format!("Failed to serialize account registry: {}", e)))?;
+        let temp_path = path.with_extension("json.tmp");
+        fs::write(&temp_path, content)
+            .map_err(|e| AppError::new(format!("Failed to write account registry: {}", e)))?;
+        fs::rename(&temp_path, &path)
+            .map_err(|e| AppError::new(format!("Failed to write account registry: {}", e)))
+    }
+
+    /// Loads the registry, applies `update` and saves it, holding the registry lock throughout
+    pub fn update<T>(update: impl FnOnce(&mut AccountRegistry) -> Result<T, AppError>) -> Result<T, AppError> {
+        let _guard = REGISTRY_LOCK
+            .lock()
+            .map_err(|_| AppError::new("Account registry lock is poisoned"))?;
+
+        let mut registry = Self::load()?;
+        let result = update(&mut registry)?;
+        registry.save()?;
+        Ok(result)
+    }
+
+    /// Metadata of an account (defaults when it is not registered)
+    pub fn get(&self, account: &str) -> AccountMetadata {
+        self.accounts.get(account).cloned().unwrap_or_default()
+    }
+}
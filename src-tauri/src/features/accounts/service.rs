@@ -0,0 +1,275 @@
+//! Accounts service - account registry and resolution of accounts to deals files
+
+use crate::features::accounts::account_id::{AccountId, BACKTEST_ACCOUNT_PREFIX};
+use crate::features::accounts::model::{Account, AccountMetadata, ResolvedAccount};
+use crate::features::accounts::registry::AccountRegistry;
+use crate::features::deals::service::DealService;
+use crate::features::fx::service::CurrencyConverter;
+use crate::shared::error::AppError;
+use crate::shared::utils::cache_dir;
+use std::collections::BTreeSet;
+use std::fs;
+use std::path::PathBuf;
+
+/// Largest supported UTC offset (UTC-14:00 to UTC+14:00)
+const MAX_TIMEZONE_OFFSET_MINUTES: i64 = 14 * 60;
+
+/// Service for managing accounts
+pub struct AccountService;
+
+impl AccountService {
+    /// Lists every account with stored deals or registry metadata, sorted by id
+    pub fn list_accounts() -> Result<Vec<Account>, AppError> {
+        let registry = AccountRegistry::load()?;
+        let stored: BTreeSet<String> = Self::stored_account_ids()?.into_iter().collect();
+
+        let ids: BTreeSet<&String> = stored.iter().chain(registry.accounts.keys()).collect();
+        Ok(ids
+            .into_iter()
+            .map(|id| Account {
+                id: id.clone(),
+                metadata: registry.get(id),
+                has_deals: stored.contains(id),
+                is_backtest: Self::is_backtest(id),
+            })
+            .collect())
+    }
+
+    /// Gets an account by id
+    pub fn get_account(account: &str) -> Result<Account, AppError> {
+        let id = Self::account_id(account)?;
+        let registry = AccountRegistry::load()?;
+        let has_deals = Self::deals_path(&id)?.exists();
+        if !has_deals && !registry.accounts.contains_key(&id) {
+            return Err(AppError::new(format!("Unknown account: {}", id)));
+        }
+
+        Ok(Account {
+            metadata: registry.get(&id),
+            is_backtest: Self::is_backtest(&id),
+            id,
+            has_deals,
+        })
+    }
+
+    /// Replaces the metadata of an account, registering it if needed
+    /// Accounts can be registered before their deals are imported
+    pub fn update_account(account: &str, metadata: AccountMetadata) -> Result<Account, AppError> {
+        let id = Self::account_id(account)?;
+        let metadata = Self::normalize_metadata(metadata)?;
+
+        AccountRegistry::update(|registry| {
+            if let Some(ref name) = metadata.name {
+                // Names resolve accounts in insights, so they must stay unambiguous
+                let taken = registry.accounts.iter().any(|(other, other_metadata)| {
+                    *other != id
+                        && (other.eq_ignore_ascii_case(name)
+                            || other_metadata.name.as_deref().is_some_and(|other_name| other_name.eq_ignore_ascii_case(name)))
+                });
+                if taken {
+                    return Err(AppError::new(format!("Account name '{}' is already used", name)));
+                }
+            }
+            registry.accounts.insert(id.clone(), metadata);
+            Ok(())
+        })?;
+
+        Self::get_account(&id)
+    }
+
+    /// Removes an account from the registry; with `delete_deals`, its deals file is deleted too
+    pub fn delete_account(account: &str, delete_deals: bool) -> Result<(), AppError> {
+        let account_id = AccountId::parse(account)?;
+        let id = account_id.to_string();
+        let deals_path = account_id.deals_path()?;
+
+        // Held so an import into the account cannot recreate the file around the delete
+        DealService::with_account_lock(&account_id, || {
+            let registered = AccountRegistry::update(|registry| Ok(registry.accounts.remove(&id).is_some()))?;
+            let has_deals = deals_path.exists();
+            if !registered && !has_deals {
+                return Err(AppError::new(format!("Unknown account: {}", id)));
+            }
+
+            if delete_deals && has_deals {
+                fs::remove_file(&deals_path)
+                    .map_err(|e| AppError::new(format!("Failed to delete deals of account {}: {}", id, e)))?;
+            }
+            Ok(())
+        })
+    }
+
+    /// Sets the offset of the account's server time from UTC in minutes (e.g. 120 for UTC+2)
+    /// Used to align deal times (server time) with the UTC market data
+    pub fn set_timezone_offset(account: &str, offset_minutes: i64) -> Result<Account, AppError> {
+        Self::validate_timezone_offset(offset_minutes)?;
+        Self::modify(account, |metadata| metadata.timezone_offset_minutes = offset_minutes)
+    }
+
+    /// Sets the currency the profits of an account are denominated in (ISO code, e.g. "USD")
+    /// Needed to convert the account's deals to a reporting currency
+    pub fn set_currency(account: &str, currency: &str) -> Result<Account, AppError> {
+        let currency = CurrencyConverter::normalize_currency(currency)?;
+        Self::modify(account, |metadata| metadata.currency = Some(currency))
+    }
+
+    /// Resolves the accounts an insight reads
+    /// An account is given by id (with or without .parquet) or registered name (case-insensitive);
+    /// without one, every account with stored deals is returned except backtest accounts,
+    /// which are only read when asked for
+    pub fn resolve(account: Option<&str>) -> Result<Vec<ResolvedAccount>, AppError> {
+        let registry = AccountRegistry::load()?;
+
+        let ids = match account {
+            Some(account) => vec![Self::resolve_id(&registry, account)?],
+            None => Self::stored_account_ids()?
+                .into_iter()
+                .filter(|id| !Self::is_backtest(id))
+                .collect(),
+        };
+
+        ids.into_iter()
+            .map(|id| {
+                let deals_path = Self::deals_path(&id)?;
+                if !deals_path.exists() {
+                    return Err(AppError::new(format!(
+                        "Deal file not found: {}",
+                        deals_path.display()
+                    )));
+                }
+                Ok(ResolvedAccount {
+                    metadata: registry.get(&id),
+                    id,
+                    deals_path,
+                })
+            })
+            .collect()
+    }
+
+    /// Resolves a single account (see `resolve`)
+    pub fn resolve_one(account: &str) -> Result<ResolvedAccount, AppError> {
+        Self::resolve(Some(account))?
+            .pop()
+            .ok_or_else(|| AppError::new(format!("Unknown account: {}", account)))
+    }
+
+    /// Ids of the accounts with a deals file in the deals cache directory, sorted
+    pub fn stored_account_ids() -> Result<Vec<String>, AppError> {
+        let deals_dir = cache_dir::get_deals_cache_dir()?;
+        if !deals_dir.exists() {
+            return Ok(Vec::new());
+        }
+
+        let entries = fs::read_dir(&deals_dir)
+            .map_err(|e| AppError::new(format!("Failed to read deals directory: {}", e)))?;
+
+        let mut accounts = Vec::new();
+        for entry in entries {
+            let entry = entry
+                .map_err(|e| AppError::new(format!("Failed to read directory entry: {}", e)))?;
+            let path = entry.path();
+            if path.is_file() && path.extension().and_then(|s| s.to_str()) == Some("parquet") {
+                // Files placed by hand under names that are not valid ids are skipped
+                if let Some(id) = path.file_stem().and_then(|stem| AccountId::parse(&stem.to_string_lossy()).ok()) {
+                    accounts.push(id.into_string());
+                }
+            }
+        }
+
+        accounts.sort();
+        Ok(accounts)
+    }
+
+    /// Id of a registered or stored account given by id or name
+    fn resolve_id(registry: &AccountRegistry, account: &str) -> Result<String, AppError> {
+        // Display names may hold characters ids cannot, so an invalid id can still be a name
+        let id = AccountId::parse(account);
+        if let Ok(ref id) = id {
+            if registry.accounts.contains_key(id.as_str()) || id.deals_path()?.exists() {
+                return Ok(id.to_string());
+            }
+        }
+
+        let named: Vec<&String> = registry
+            .accounts
+            .iter()
+            .filter(|(_, metadata)| metadata.name.as_deref().is_some_and(|name| name.eq_ignore_ascii_case(account.trim())))
+            .map(|(id, _)| id)
+            .collect();
+        match named.as_slice() {
+            [id] => Ok((*id).clone()),
+            [] => match id {
+                Ok(_) => Err(AppError::new(format!("Unknown account: {}", account))),
+                Err(e) => Err(e.into()),
+            },
+            _ => Err(AppError::new(format!("Account name '{}' matches several accounts", account))),
+        }
+    }
+
+    /// Applies a change to the metadata of an account, registering it if needed
+    fn modify(account: &str, change: impl FnOnce(&mut AccountMetadata)) -> Result<Account, AppError> {
+        let id = Self::account_id(account)?;
+        AccountRegistry::update(|registry| {
+            change(registry.accounts.entry(id.clone()).or_default());
+            Ok(())
+        })?;
+        Self::get_account(&id)
+    }
+
+    /// Account id of a deals file name (with or without .parquet)
+    fn account_id(account: &str) -> Result<String, AppError> {
+        Ok(AccountId::parse(account)?.into_string())
+    }
+
+    /// Whether an account holds the synthetic deals written by a backtest
+    fn is_backtest(id: &str) -> bool {
+        id.starts_with(BACKTEST_ACCOUNT_PREFIX)
+    }
+
+    /// Ids from the registry file are validated too, as it may be edited by other tools
+    fn deals_path(id: &str) -> Result<PathBuf, AppError> {
+        AccountId::parse(id)?.deals_path()
+    }
+
+    fn validate_timezone_offset(offset_minutes: i64) -> Result<(), AppError> {
+        if offset_minutes.abs() > MAX_TIMEZONE_OFFSET_MINUTES {
+            return Err(AppError::new(format!(
+                "Timezone offset must be between -{0} and {0} minutes",
+                MAX_TIMEZONE_OFFSET_MINUTES
+            )));
+        }
+        Ok(())
+    }
+
+    /// Trims text fields (empty ones are cleared), validates the currency, leverage and
+    /// timezone offset, and removes empty and repeated tags
+    fn normalize_metadata(metadata: AccountMetadata) -> Result<AccountMetadata, AppError> {
+        let text = |value: Option<String>| value.map(|value| value.trim().to_string()).filter(|value| !value.is_empty());
+
+        let currency = text(metadata.currency)
+            .map(|currency| CurrencyConverter::normalize_currency(&currency))
+            .transpose()?;
+        if metadata.leverage == Some(0) {
+            return Err(AppError::new("Leverage must be at least 1"));
+        }
+        Self::validate_timezone_offset(metadata.timezone_offset_minutes)?;
+
+        let mut tags: Vec<String> = Vec::new();
+        for tag in metadata.tags {
+            let tag = tag.trim();
+            if !tag.is_empty() && !tags.iter().any(|existing| existing == tag) {
+                tags.push(tag.to_string());
+            }
+        }
+
+        Ok(AccountMetadata {
+            name: text(metadata.name),
+            broker: text(metadata.broker),
+            server: text(metadata.server),
+            currency,
+            leverage: metadata.leverage,
+            timezone_offset_minutes: metadata.timezone_offset_minutes,
+            tags,
+        })
+    }
+}
@@ -0,0 +1,113 @@
+//! Multi-symbol series alignment
+//!
+//! Joins one field of several symbols on `time`, so instruments with different trading
+//! hours or missing bars can be overlaid, spread or correlated bar by bar.
+
+use crate::shared::error::AppError;
+use crate::features::asset::model::{
+    AlignedSeries, AlignedSeriesResult, FillPolicy, OhlcField, TimeRange,
+};
+use crate::features::asset::parquet_reader::ParquetReaderService;
+use polars::prelude::*;
+use std::path::PathBuf;
+
+/// Service for aligning the series of several symbols
+pub struct SeriesAlignmentService;
+
+impl SeriesAlignmentService {
+    /// Aligns `field` of every symbol on the union of their timestamps
+    /// `datasets` holds the symbol name and its parquet files, in output order
+    /// Symbols must be unique
+    pub fn align(
+        datasets: &[(String, Vec<PathBuf>)],
+        range: &TimeRange,
+        field: OhlcField,
+        fill: FillPolicy,
+    ) -> Result<AlignedSeriesResult, AppError> {
+        for (index, (symbol, _)) in datasets.iter().enumerate() {
+            if datasets[..index].iter().any(|(other, _)| other == symbol) {
+                return Err(AppError::new(format!("Duplicate symbol: {}", symbol)));
+            }
+        }
+
+        // Values get internal column names, so no symbol name can clash with `time` or another column
+        let value_columns: Vec<String> = (0..datasets.len()).map(Self::value_column).collect();
+
+        let mut frames = Vec::with_capacity(datasets.len());
+        for ((_, files), column) in datasets.iter().zip(&value_columns) {
+            let frame = ParquetReaderService::scan_ohlcv(files, range.start, range.end)?
+                .select([col("time"), col(field.column()).alias(column)]);
+            frames.push(frame);
+        }
+
+        // Every timestamp present in at least one symbol
+        let time_frames: Vec<LazyFrame> = frames
+            .iter()
+            .map(|frame| frame.clone().select([col("time")]))
+            .collect();
+        let mut aligned = concat(time_frames, UnionArgs::default())
+            .map_err(|e| AppError::new(format!("Failed to combine timestamps: {}", e)))?
+            .unique_stable(None, UniqueKeepStrategy::First)
+            .sort(["time"], SortMultipleOptions::default());
+
+        for frame in frames {
+            aligned = aligned.left_join(frame, col("time"), col("time"));
+        }
+
+        let symbol_columns: Vec<Expr> = value_columns.iter().map(|column| col(column)).collect();
+        aligned = match fill {
+            FillPolicy::ForwardFill => aligned.with_columns(
+                symbol_columns
+                    .iter()
+                    .map(|column| column.clone().forward_fill(None))
+                    .collect::<Vec<_>>(),
+            ),
+            FillPolicy::DropIncomplete => aligned.drop_nulls(Some(symbol_columns)),
+            FillPolicy::LeaveNull => aligned,
+        };
+
+        let df = aligned
+            .sort(["time"], SortMultipleOptions::default())
+            .collect()
+            .map_err(|e| AppError::new(format!("Failed to align series: {}", e)))?;
+
+        // Extract columns
+        let time = df
+            .column("time")
+            .map_err(|e| AppError::new(format!("Failed to get 'time' column: {}", e)))?
+            .i64()
+            .map_err(|e| AppError::new(format!("Failed to cast 'time' column to i64: {}", e)))?
+            .into_iter()
+            .map(|t| t.unwrap_or(0))
+            .collect();
+
+        let mut series = Vec::with_capacity(datasets.len());
+        for ((symbol, _), column) in datasets.iter().zip(&value_columns) {
+            let values = df
+                .column(column)
+                .map_err(|e| AppError::new(format!("Failed to get '{}' column: {}", symbol, e)))?
+                .cast(&DataType::Float64)
+                .map_err(|e| AppError::new(format!("Failed to cast '{}' column to f64: {}", symbol, e)))?;
+            let values = values
+                .f64()
+                .map_err(|e| AppError::new(format!("Failed to cast '{}' column to f64: {}", symbol, e)))?
+                .into_iter()
+                .collect();
+            series.push(AlignedSeries {
+                symbol: symbol.clone(),
+                values,
+            });
+        }
+
+        Ok(AlignedSeriesResult {
+            time,
+            field,
+            series,
+        })
+    }
+
+    /// Internal name of the value column of the dataset at `index`
+    fn value_column(index: usize) -> String {
+        format!("__sym_{}", index)
+    }
+}
@@ -0,0 +1,336 @@
+//! Asset cache disk management: usage reporting, deletion and quota enforcement
+
+use crate::shared::error::AppError;
+use crate::shared::utils::cache_dir;
+use crate::features::asset::manifest::AssetManifest;
+use crate::features::asset::model::{
+    CacheEvictionResult, CacheUsageReport, SymbolUsage, TimeframeUsage,
+};
+use std::collections::{BTreeMap, HashSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::UNIX_EPOCH;
+
+/// Reads of a dataset within this many seconds of the last recorded one are not written
+/// to the manifest; LRU eviction does not need a finer resolution
+const ACCESS_WRITE_INTERVAL_SECONDS: i64 = 60;
+
+/// Last access time written to the manifest per (symbol, timeframe) in this process
+static RECORDED_ACCESSES: Mutex<BTreeMap<(String, String), i64>> = Mutex::new(BTreeMap::new());
+
+/// A dataset on disk: every file or folder of a symbol sharing the same stem
+/// (e.g. `1M.parquet` and a partitioned `1M/` folder, or `ticks.csv` and `ticks/`)
+struct Dataset {
+    symbol: String,
+    timeframe: String,
+    paths: Vec<PathBuf>,
+    size_bytes: u64,
+    /// Latest modification time of the dataset files (Unix seconds)
+    modified: i64,
+}
+
+/// Service for inspecting and trimming the asset cache
+pub struct CacheManagerService;
+
+impl CacheManagerService {
+    /// Builds a disk usage report per symbol and timeframe
+    pub fn usage_report() -> Result<CacheUsageReport, AppError> {
+        let manifest = AssetManifest::load()?;
+        let datasets = Self::scan_datasets()?;
+
+        let mut symbols: BTreeMap<String, SymbolUsage> = BTreeMap::new();
+        for dataset in datasets {
+            let usage = symbols
+                .entry(dataset.symbol.clone())
+                .or_insert_with(|| SymbolUsage {
+                    symbol: dataset.symbol.clone(),
+                    size_bytes: 0,
+                    pinned: manifest.pinned_symbols.contains(&dataset.symbol),
+                    timeframes: Vec::new(),
+                });
+
+            usage.size_bytes += dataset.size_bytes;
+            usage.timeframes.push(TimeframeUsage {
+                last_accessed: manifest
+                    .entry(&dataset.symbol, &dataset.timeframe)
+                    .and_then(|e| e.last_accessed),
+                timeframe: dataset.timeframe,
+                size_bytes: dataset.size_bytes,
+            });
+        }
+
+        let symbols: Vec<SymbolUsage> = symbols.into_values().collect();
+        let total_bytes = symbols.iter().map(|s| s.size_bytes).sum();
+
+        Ok(CacheUsageReport {
+            total_bytes,
+            quota_bytes: manifest.quota_bytes,
+            symbols,
+        })
+    }
+
+    /// Deletes a symbol folder and its manifest entries
+    /// Returns the number of bytes freed
+    pub fn delete_symbol(symbol: &str) -> Result<u64, AppError> {
+        let symbol_dir = cache_dir::get_symbol_cache_dir(symbol)?;
+        if !symbol_dir.exists() {
+            return Err(AppError::new(format!(
+                "Symbol directory not found: {}",
+                symbol_dir.display()
+            )));
+        }
+
+        let freed = Self::path_size(&symbol_dir);
+        fs::remove_dir_all(&symbol_dir)
+            .map_err(|e| AppError::new(format!("Failed to remove symbol directory: {}", e)))?;
+
+        AssetManifest::update(|manifest| manifest.remove(symbol, None))?;
+        Self::forget_accesses(symbol, None);
+
+        Ok(freed)
+    }
+
+    /// Deletes a single timeframe (every file or folder with that stem) of a symbol
+    /// Removes the symbol folder as well when it becomes empty
+    /// Returns the number of bytes freed
+    pub fn delete_timeframe(symbol: &str, timeframe: &str) -> Result<u64, AppError> {
+        let dataset = Self::scan_datasets()?
+            .into_iter()
+            .find(|d| d.symbol == symbol && d.timeframe == timeframe)
+            .ok_or_else(|| {
+                AppError::new(format!("No cached data found for {} {}", symbol, timeframe))
+            })?;
+
+        Self::remove_dataset(&dataset)?;
+        AssetManifest::update(|manifest| manifest.remove(symbol, Some(timeframe)))?;
+        Self::forget_accesses(symbol, Some(timeframe));
+
+        Ok(dataset.size_bytes)
+    }
+
+    /// Pins or unpins a symbol; pinned symbols are never evicted by the quota
+    pub fn set_pinned(symbol: &str, pinned: bool) -> Result<(), AppError> {
+        AssetManifest::update(|manifest| {
+            if pinned {
+                manifest.pinned_symbols.insert(symbol.to_string());
+            } else {
+                manifest.pinned_symbols.remove(symbol);
+            }
+        })
+    }
+
+    /// Sets (or clears) the cache quota and enforces it immediately
+    pub fn set_quota(quota_bytes: Option<u64>) -> Result<CacheEvictionResult, AppError> {
+        AssetManifest::update(|manifest| manifest.quota_bytes = quota_bytes)?;
+        Self::enforce_quota(&[])
+    }
+
+    /// Records a read of a dataset for LRU eviction, ignoring failures
+    /// Access tracking only feeds eviction, so a manifest that cannot be written must not fail
+    /// the read that triggered it
+    pub fn record_access_best_effort(symbol: &str, timeframe: &str) {
+        let _ = Self::record_access(symbol, timeframe);
+    }
+
+    /// Records a read of a dataset for LRU eviction
+    /// The manifest is only rewritten when the last recorded read of the dataset is older
+    /// than `ACCESS_WRITE_INTERVAL_SECONDS`, so repeated chart loads and queries stay off the disk
+    fn record_access(symbol: &str, timeframe: &str) -> Result<(), AppError> {
+        let now = chrono::Utc::now().timestamp();
+        let key = (symbol.to_string(), timeframe.to_string());
+        {
+            let recorded = RECORDED_ACCESSES.lock().unwrap_or_else(|e| e.into_inner());
+            if recorded.get(&key).is_some_and(|last| now - last < ACCESS_WRITE_INTERVAL_SECONDS) {
+                return Ok(());
+            }
+        }
+
+        AssetManifest::update(|manifest| {
+            manifest.entry_mut(symbol, timeframe).last_accessed = Some(now);
+        })?;
+        RECORDED_ACCESSES
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .insert(key, now);
+        Ok(())
+    }
+
+    /// Evicts least recently used datasets until the cache fits in the quota
+    /// Pinned symbols and `protected_symbols` (e.g. the symbol just downloaded) are never evicted.
+    /// Datasets that were never read are ordered by their file modification time.
+    pub fn enforce_quota(protected_symbols: &[&str]) -> Result<CacheEvictionResult, AppError> {
+        let manifest = AssetManifest::load()?;
+        let datasets = Self::scan_datasets()?;
+        let mut total_bytes: u64 = datasets.iter().map(|d| d.size_bytes).sum();
+
+        let quota_bytes = match manifest.quota_bytes {
+            Some(quota) if total_bytes > quota => quota,
+            _ => {
+                return Ok(CacheEvictionResult {
+                    evicted: Vec::new(),
+                    freed_bytes: 0,
+                    total_bytes,
+                    quota_bytes: manifest.quota_bytes,
+                })
+            }
+        };
+
+        let mut candidates: Vec<(i64, Dataset)> = datasets
+            .into_iter()
+            .filter(|d| {
+                !manifest.pinned_symbols.contains(&d.symbol)
+                    && !protected_symbols.contains(&d.symbol.as_str())
+            })
+            .map(|d| {
+                let last_used = manifest
+                    .entry(&d.symbol, &d.timeframe)
+                    .and_then(|e| e.last_accessed)
+                    .unwrap_or(d.modified);
+                (last_used, d)
+            })
+            .collect();
+        candidates.sort_by_key(|(last_used, _)| *last_used);
+
+        let mut evicted = Vec::new();
+        let mut freed_bytes = 0;
+
+        for (_, dataset) in candidates {
+            if total_bytes <= quota_bytes {
+                break;
+            }
+
+            Self::remove_dataset(&dataset)?;
+            Self::forget_accesses(&dataset.symbol, Some(&dataset.timeframe));
+            total_bytes = total_bytes.saturating_sub(dataset.size_bytes);
+            freed_bytes += dataset.size_bytes;
+            evicted.push(AssetManifest::key(&dataset.symbol, &dataset.timeframe));
+        }
+
+        let evicted_keys: HashSet<&String> = evicted.iter().collect();
+        AssetManifest::update(|manifest| {
+            manifest.entries.retain(|key, _| !evicted_keys.contains(key));
+        })?;
+
+        Ok(CacheEvictionResult {
+            evicted,
+            freed_bytes,
+            total_bytes,
+            quota_bytes: Some(quota_bytes),
+        })
+    }
+
+    /// Drops the recorded reads of removed datasets so the next read is written again
+    fn forget_accesses(symbol: &str, timeframe: Option<&str>) {
+        RECORDED_ACCESSES
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .retain(|(s, t), _| s != symbol || timeframe.is_some_and(|tf| t != tf));
+    }
+
+    /// Lists every dataset of every symbol in the asset cache
+    fn scan_datasets() -> Result<Vec<Dataset>, AppError> {
+        let asset_dir = cache_dir::get_asset_cache_dir()?;
+        if !asset_dir.exists() {
+            return Ok(Vec::new());
+        }
+
+        let mut datasets = Vec::new();
+        let symbol_entries = fs::read_dir(&asset_dir)
+            .map_err(|e| AppError::new(format!("Failed to read cache directory: {}", e)))?;
+
+        for symbol_entry in symbol_entries {
+            let symbol_entry = symbol_entry
+                .map_err(|e| AppError::new(format!("Failed to read directory entry: {}", e)))?;
+            let symbol_path = symbol_entry.path();
+            if !symbol_path.is_dir() {
+                continue;
+            }
+            let symbol = symbol_entry.file_name().to_string_lossy().to_string();
+
+            let mut by_stem: BTreeMap<String, Dataset> = BTreeMap::new();
+            let entries = fs::read_dir(&symbol_path)
+                .map_err(|e| AppError::new(format!("Failed to read symbol directory: {}", e)))?;
+
+            for entry in entries {
+                let entry = entry
+                    .map_err(|e| AppError::new(format!("Failed to read directory entry: {}", e)))?;
+                let path = entry.path();
+                let stem = if path.is_dir() {
+                    entry.file_name().to_string_lossy().to_string()
+                } else {
+                    match path.file_stem() {
+                        Some(stem) => stem.to_string_lossy().to_string(),
+                        None => continue,
+                    }
+                };
+
+                let dataset = by_stem.entry(stem.clone()).or_insert_with(|| Dataset {
+                    symbol: symbol.clone(),
+                    timeframe: stem,
+                    paths: Vec::new(),
+                    size_bytes: 0,
+                    modified: 0,
+                });
+                dataset.size_bytes += Self::path_size(&path);
+                dataset.modified = dataset.modified.max(Self::modified_time(&path));
+                dataset.paths.push(path);
+            }
+
+            datasets.extend(by_stem.into_values());
+        }
+
+        Ok(datasets)
+    }
+
+    /// Removes every file or folder of a dataset, and the symbol folder if it ends up empty
+    fn remove_dataset(dataset: &Dataset) -> Result<(), AppError> {
+        for path in &dataset.paths {
+            let result = if path.is_dir() {
+                fs::remove_dir_all(path)
+            } else {
+                fs::remove_file(path)
+            };
+            result.map_err(|e| {
+                AppError::new(format!("Failed to remove {}: {}", path.display(), e))
+            })?;
+        }
+
+        if let Some(symbol_dir) = dataset.paths.first().and_then(|p| p.parent()) {
+            let is_empty = fs::read_dir(symbol_dir)
+                .map(|mut entries| entries.next().is_none())
+                .unwrap_or(false);
+            if is_empty {
+                let _ = fs::remove_dir(symbol_dir);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Total size of a file or folder (recursive)
+    fn path_size(path: &Path) -> u64 {
+        if path.is_dir() {
+            fs::read_dir(path)
+                .map(|entries| {
+                    entries
+                        .flatten()
+                        .map(|entry| Self::path_size(&entry.path()))
+                        .sum()
+                })
+                .unwrap_or(0)
+        } else {
+            fs::metadata(path).map(|m| m.len()).unwrap_or(0)
+        }
+    }
+
+    /// Modification time of a path in Unix seconds (0 if unavailable)
+    fn modified_time(path: &Path) -> i64 {
+        fs::metadata(path)
+            .and_then(|m| m.modified())
+            .ok()
+            .and_then(|t| t.duration_since(UNIX_EPOCH).ok())
+            .map(|d| d.as_secs() as i64)
+            .unwrap_or(0)
+    }
+}
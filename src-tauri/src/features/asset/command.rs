@@ -1,16 +1,25 @@
 //! Asset command handlers
 
-use crate::features::asset::model::{AssetOperationResult, OHLCData};
+use crate::features::asset::model::{
+    AlignedSeriesResult, AssetOperationResult, AssetOptimizationResult, AssetSyncResult,
+    CacheEvictionResult, CacheUsageReport, FillPolicy, OHLCData, OhlcField, OhlcImportFormat,
+    OhlcImportResult, TickData, TickPriceSource, TimeRange,
+};
 use crate::features::asset::service::AssetService;
+use crate::shared::error::AppError;
 
 /// Tauri command: pull_assets
 /// Downloads the entire assets repository
+/// 
+/// # Arguments
+/// * `optimize` - Optionally rewrite the downloaded parquet files for faster reads (defaults to false)
+/// 
 /// Returns success status and message
 #[tauri::command]
-pub async fn pull_assets() -> Result<AssetOperationResult, String> {
-    AssetService::pull_assets()
+pub async fn pull_assets(optimize: Option<bool>) -> Result<AssetOperationResult, String> {
+    AssetService::pull_assets(optimize.unwrap_or(false))
         .await
-        .map_err(|e| e.message)
+        .map_err(AppError::into_command_error)
 }
 
 /// Tauri command: pull_asset_by_symbol
@@ -18,13 +27,35 @@ pub async fn pull_assets() -> Result<AssetOperationResult, String> {
 /// 
 /// # Arguments
 /// * `symbol` - The symbol name (e.g., "EURUSD")
+/// * `optimize` - Optionally rewrite the downloaded parquet files for faster reads (defaults to false)
 /// 
 /// Returns success status and message
 #[tauri::command]
-pub async fn pull_asset_by_symbol(symbol: String) -> Result<AssetOperationResult, String> {
-    AssetService::pull_asset_by_symbol(&symbol)
+pub async fn pull_asset_by_symbol(
+    symbol: String,
+    optimize: Option<bool>,
+) -> Result<AssetOperationResult, String> {
+    AssetService::pull_asset_by_symbol(&symbol, optimize.unwrap_or(false))
         .await
-        .map_err(|e| e.message)
+        .map_err(AppError::into_command_error)
+}
+
+/// Tauri command: sync_asset_by_symbol
+/// Incrementally updates a symbol from the repository, downloading only new or changed partitions
+/// 
+/// # Arguments
+/// * `symbol` - The symbol name (e.g., "EURUSD")
+/// * `optimize` - Optionally rewrite the downloaded parquet files for faster reads (defaults to false)
+/// 
+/// Returns the downloaded, unchanged and removed files
+#[tauri::command]
+pub async fn sync_asset_by_symbol(
+    symbol: String,
+    optimize: Option<bool>,
+) -> Result<AssetSyncResult, String> {
+    AssetService::sync_asset_by_symbol(&symbol, optimize.unwrap_or(false))
+        .await
+        .map_err(AppError::into_command_error)
 }
 
 /// Tauri command: list_symbols
@@ -33,7 +64,7 @@ pub async fn pull_asset_by_symbol(symbol: String) -> Result<AssetOperationResult
 #[tauri::command]
 pub fn list_symbols() -> Result<Vec<String>, String> {
     AssetService::list_symbols()
-        .map_err(|e| e.message)
+        .map_err(AppError::into_command_error)
 }
 
 /// Tauri command: retrieve_asset_ochl
@@ -42,10 +73,196 @@ pub fn list_symbols() -> Result<Vec<String>, String> {
 /// # Arguments
 /// * `symbol` - The symbol name (e.g., "EURUSD")
 /// * `timeframe` - The timeframe (e.g., "1M")
+/// * `range` - Optional inclusive time range in Unix seconds (`{ start, end }`, either bound optional)
+/// 
+/// Returns a vector of OHLCData points
+#[tauri::command]
+pub fn retrieve_asset_ochl(
+    symbol: String,
+    timeframe: String,
+    range: Option<TimeRange>,
+) -> Result<Vec<OHLCData>, String> {
+    AssetService::retrieve_asset_ochl(&symbol, &timeframe, range)
+        .map_err(AppError::into_command_error)
+}
+
+/// Tauri command: retrieve_aligned_series
+/// Retrieves one field of several symbols aligned on the same timestamps
+/// 
+/// # Arguments
+/// * `symbols` - The symbol names (e.g., ["EURUSD", "GBPUSD"])
+/// * `timeframe` - The timeframe (e.g., "1M")
+/// * `range` - Optional inclusive time range in Unix seconds (`{ start, end }`, either bound optional)
+/// * `field` - Bar field to align: "open", "high", "low", "close" (default) or "volume"
+/// * `fill` - Gap handling: "forward_fill", "drop_incomplete" or "leave_null" (default)
+/// 
+/// Returns the timestamps and one column of values per symbol
+#[tauri::command]
+pub fn retrieve_aligned_series(
+    symbols: Vec<String>,
+    timeframe: String,
+    range: Option<TimeRange>,
+    field: Option<OhlcField>,
+    fill: Option<FillPolicy>,
+) -> Result<AlignedSeriesResult, String> {
+    AssetService::retrieve_aligned_series(
+        &symbols,
+        &timeframe,
+        range,
+        field.unwrap_or_default(),
+        fill.unwrap_or_default(),
+    )
+    .map_err(AppError::into_command_error)
+}
+
+/// Tauri command: import_ohlc
+/// Imports OHLC history from a local CSV file into the asset cache
+/// 
+/// # Arguments
+/// * `file_path` - Absolute path of the CSV file to import
+/// * `symbol` - The symbol name to store the data under (e.g., "EURUSD")
+/// * `timeframe` - The timeframe of the bars (e.g., "1M")
+/// * `format` - The CSV format (MT5 export, HistData or generic column mapping)
+/// 
+/// Returns OhlcImportResult with row counts and per-row warnings
+#[tauri::command]
+pub fn import_ohlc(
+    file_path: String,
+    symbol: String,
+    timeframe: String,
+    format: OhlcImportFormat,
+) -> Result<OhlcImportResult, String> {
+    AssetService::import_ohlc(&file_path, &symbol, &timeframe, &format)
+        .map_err(AppError::into_command_error)
+}
+
+/// Tauri command: build_bars_from_ticks
+/// Builds OHLC bars from the cached tick data of a symbol
+/// 
+/// # Arguments
+/// * `symbol` - The symbol name (e.g., "EURUSD")
+/// * `timeframe` - Bar length (e.g., "10S", "1M", "4H")
+/// * `price_source` - Price used for the bars: "bid", "ask" or "mid" (defaults to "bid")
+/// * `start_msc` / `end_msc` - Optional time window in Unix milliseconds
 /// 
 /// Returns a vector of OHLCData points
 #[tauri::command]
-pub fn retrieve_asset_ochl(symbol: String, timeframe: String) -> Result<Vec<OHLCData>, String> {
-    AssetService::retrieve_asset_ochl(&symbol, &timeframe)
-        .map_err(|e| e.message)
+pub fn build_bars_from_ticks(
+    symbol: String,
+    timeframe: String,
+    price_source: Option<TickPriceSource>,
+    start_msc: Option<i64>,
+    end_msc: Option<i64>,
+) -> Result<Vec<OHLCData>, String> {
+    AssetService::build_bars_from_ticks(
+        &symbol,
+        &timeframe,
+        price_source.unwrap_or_default(),
+        start_msc,
+        end_msc,
+    )
+    .map_err(AppError::into_command_error)
+}
+
+/// Tauri command: retrieve_ticks_around
+/// Retrieves ticks in a window around a point in time (e.g. a deal's `time_msc`)
+/// 
+/// # Arguments
+/// * `symbol` - The symbol name (e.g., "EURUSD")
+/// * `time_msc` - Center of the window in Unix milliseconds
+/// * `before_ms` - Milliseconds to include before `time_msc`
+/// * `after_ms` - Milliseconds to include after `time_msc`
+/// 
+/// Returns a vector of TickData points sorted by time
+#[tauri::command]
+pub fn retrieve_ticks_around(
+    symbol: String,
+    time_msc: i64,
+    before_ms: i64,
+    after_ms: i64,
+) -> Result<Vec<TickData>, String> {
+    AssetService::retrieve_ticks_around(&symbol, time_msc, before_ms, after_ms)
+        .map_err(AppError::into_command_error)
+}
+
+/// Tauri command: get_asset_cache_usage
+/// Reports disk usage of the asset cache per symbol and timeframe
+/// 
+/// Returns CacheUsageReport with sizes, last access times, pins and the quota
+#[tauri::command]
+pub fn get_asset_cache_usage() -> Result<CacheUsageReport, String> {
+    AssetService::get_cache_usage()
+        .map_err(AppError::into_command_error)
+}
+
+/// Tauri command: delete_asset_symbol
+/// Deletes all cached data of a symbol
+/// 
+/// # Arguments
+/// * `symbol` - The symbol name (e.g., "EURUSD")
+#[tauri::command]
+pub fn delete_asset_symbol(symbol: String) -> Result<AssetOperationResult, String> {
+    AssetService::delete_symbol(&symbol)
+        .map_err(AppError::into_command_error)
+}
+
+/// Tauri command: delete_asset_timeframe
+/// Deletes a single timeframe of a symbol
+/// 
+/// # Arguments
+/// * `symbol` - The symbol name (e.g., "EURUSD")
+/// * `timeframe` - The timeframe to delete (e.g., "1M", or "ticks")
+#[tauri::command]
+pub fn delete_asset_timeframe(symbol: String, timeframe: String) -> Result<AssetOperationResult, String> {
+    AssetService::delete_timeframe(&symbol, &timeframe)
+        .map_err(AppError::into_command_error)
+}
+
+/// Tauri command: set_asset_cache_quota
+/// Sets the maximum asset cache size and evicts least recently used data if needed
+/// 
+/// # Arguments
+/// * `quota_bytes` - Maximum size in bytes, or null to remove the limit
+#[tauri::command]
+pub fn set_asset_cache_quota(quota_bytes: Option<u64>) -> Result<CacheEvictionResult, String> {
+    AssetService::set_cache_quota(quota_bytes)
+        .map_err(AppError::into_command_error)
+}
+
+/// Tauri command: set_asset_symbol_pinned
+/// Pins or unpins a symbol; pinned symbols are never evicted by the quota
+/// 
+/// # Arguments
+/// * `symbol` - The symbol name (e.g., "EURUSD")
+/// * `pinned` - Whether the symbol should be pinned
+#[tauri::command]
+pub fn set_asset_symbol_pinned(symbol: String, pinned: bool) -> Result<AssetOperationResult, String> {
+    AssetService::set_symbol_pinned(&symbol, pinned)
+        .map_err(AppError::into_command_error)
+}
+
+/// Tauri command: enforce_asset_cache_quota
+/// Evicts least recently used data until the cache fits in the configured quota
+#[tauri::command]
+pub fn enforce_asset_cache_quota() -> Result<CacheEvictionResult, String> {
+    AssetService::enforce_cache_quota()
+        .map_err(AppError::into_command_error)
+}
+
+/// Tauri command: optimize_assets
+/// Rewrites cached parquet files sorted by time with zstd compression, column
+/// statistics and row groups sized for time-range pushdown
+/// 
+/// # Arguments
+/// * `symbol` - Optional symbol to optimize; all symbols when omitted
+/// * `force` - Re-optimize files already recorded as optimized (defaults to false)
+/// 
+/// Returns AssetOptimizationResult with file counts and sizes
+#[tauri::command]
+pub fn optimize_assets(
+    symbol: Option<String>,
+    force: Option<bool>,
+) -> Result<AssetOptimizationResult, String> {
+    AssetService::optimize_assets(symbol.as_deref(), force.unwrap_or(false))
+        .map_err(AppError::into_command_error)
 }
@@ -0,0 +1,426 @@
+//! OHLC CSV import service (MetaTrader 5, HistData and generic mapped CSV)
+
+use crate::shared::error::AppError;
+use crate::features::asset::model::{CsvColumnMapping, CsvColumnRef, OhlcImportFormat};
+use chrono::{NaiveDate, NaiveDateTime};
+use polars::prelude::*;
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+
+/// Maximum number of per-row warnings returned to the caller
+const MAX_WARNINGS: usize = 100;
+
+/// HistData ASCII files are stamped in EST without daylight saving
+const HISTDATA_UTC_OFFSET_MINUTES: i64 = -300;
+
+/// A single validated bar
+#[derive(Debug, Clone)]
+pub struct ImportedBar {
+    pub time: i64,
+    pub open: f64,
+    pub high: f64,
+    pub low: f64,
+    pub close: f64,
+    pub volume: Option<f64>,
+}
+
+/// Bars parsed from a CSV file along with the rows that were rejected
+pub struct ParsedBars {
+    pub bars: Vec<ImportedBar>,
+    pub has_volume: bool,
+    pub rows_rejected: usize,
+    pub warnings: Vec<String>,
+}
+
+/// How the timestamp column is interpreted
+enum TimeParser {
+    Unix,
+    UnixMs,
+    Format(String),
+}
+
+/// Column indices and parsing rules resolved from a format and the header row
+struct ResolvedLayout {
+    delimiter: char,
+    date: Option<usize>,
+    time: usize,
+    open: usize,
+    high: usize,
+    low: usize,
+    close: usize,
+    volume: Option<usize>,
+    parser: TimeParser,
+    utc_offset_seconds: i64,
+}
+
+/// Service for parsing OHLC CSV files and writing them as cache parquet files
+pub struct OhlcImportService;
+
+impl OhlcImportService {
+    /// Parses and validates an OHLC CSV file
+    /// Rows that fail validation are skipped and reported as warnings
+    /// Returned bars are sorted by time with duplicate timestamps removed (last row wins)
+    pub fn parse_file(file_path: &Path, format: &OhlcImportFormat) -> Result<ParsedBars, AppError> {
+        let bytes = fs::read(file_path)
+            .map_err(|e| AppError::new(format!("Failed to read file {}: {}", file_path.display(), e)))?;
+        let content = Self::decode_text(&bytes)?;
+
+        let mut lines = content
+            .lines()
+            .enumerate()
+            .map(|(index, line)| (index + 1, line.trim_end_matches('\r')))
+            .filter(|(_, line)| !line.trim().is_empty());
+
+        // Resolve the column layout, consuming the header row when the format has one
+        let layout = match format {
+            OhlcImportFormat::Mt5 { utc_offset_minutes } => {
+                let (_, header) = lines
+                    .next()
+                    .ok_or_else(|| AppError::new("File is empty"))?;
+                Self::resolve_mt5_layout(header, *utc_offset_minutes)?
+            }
+            OhlcImportFormat::HistData => ResolvedLayout {
+                delimiter: ';',
+                date: None,
+                time: 0,
+                open: 1,
+                high: 2,
+                low: 3,
+                close: 4,
+                volume: Some(5),
+                parser: TimeParser::Format("%Y%m%d %H%M%S".to_string()),
+                utc_offset_seconds: HISTDATA_UTC_OFFSET_MINUTES * 60,
+            },
+            OhlcImportFormat::Generic(mapping) => {
+                let header = if mapping.has_header {
+                    let (_, header) = lines
+                        .next()
+                        .ok_or_else(|| AppError::new("File is empty"))?;
+                    Some(header)
+                } else {
+                    None
+                };
+                Self::resolve_generic_layout(mapping, header)?
+            }
+        };
+
+        let mut bars = Vec::new();
+        let mut rows_rejected = 0;
+        let mut warnings = Vec::new();
+
+        for (line_number, line) in lines {
+            match Self::parse_row(line, &layout) {
+                Ok(bar) => bars.push(bar),
+                Err(reason) => {
+                    rows_rejected += 1;
+                    Self::push_warning(&mut warnings, format!("Line {}: {}", line_number, reason));
+                }
+            }
+        }
+
+        // Normalize ordering and drop duplicate timestamps, keeping the last occurrence
+        bars.sort_by_key(|bar| bar.time);
+        let before_dedup = bars.len();
+        let mut deduped: Vec<ImportedBar> = Vec::with_capacity(bars.len());
+        for bar in bars {
+            match deduped.last_mut() {
+                Some(last) if last.time == bar.time => *last = bar,
+                _ => deduped.push(bar),
+            }
+        }
+        let duplicates = before_dedup - deduped.len();
+        if duplicates > 0 {
+            Self::push_warning(
+                &mut warnings,
+                format!("{} duplicate timestamp(s) found, kept the last row for each", duplicates),
+            );
+        }
+
+        if warnings.len() == MAX_WARNINGS && rows_rejected > MAX_WARNINGS {
+            warnings.push(format!(
+                "... {} more rejected row(s) not listed",
+                rows_rejected - MAX_WARNINGS
+            ));
+        }
+
+        Ok(ParsedBars {
+            bars: deduped,
+            has_volume: layout.volume.is_some(),
+            rows_rejected,
+            warnings,
+        })
+    }
+
+    /// Writes bars to a parquet file with the cache OHLC layout
+    /// (`time` in Unix seconds plus `open`, `high`, `low`, `close` and optional `volume`)
+    /// The file is written to a temporary path first and then moved into place
+    pub fn write_parquet(bars: &[ImportedBar], has_volume: bool, output_path: &Path) -> Result<(), AppError> {
+        let mut columns = vec![
+            Series::new("time", bars.iter().map(|b| b.time).collect::<Vec<i64>>()),
+            Series::new("open", bars.iter().map(|b| b.open).collect::<Vec<f64>>()),
+            Series::new("high", bars.iter().map(|b| b.high).collect::<Vec<f64>>()),
+            Series::new("low", bars.iter().map(|b| b.low).collect::<Vec<f64>>()),
+            Series::new("close", bars.iter().map(|b| b.close).collect::<Vec<f64>>()),
+        ];
+        if has_volume {
+            columns.push(Series::new(
+                "volume",
+                bars.iter().map(|b| b.volume.unwrap_or(0.0)).collect::<Vec<f64>>(),
+            ));
+        }
+
+        let mut df = DataFrame::new(columns)
+            .map_err(|e| AppError::new(format!("Failed to build data frame: {}", e)))?;
+
+        if let Some(parent) = output_path.parent() {
+            fs::create_dir_all(parent)
+                .map_err(|e| AppError::new(format!("Failed to create directory: {}", e)))?;
+        }
+
+        let temp_path = output_path.with_extension("parquet.tmp");
+        let file = fs::File::create(&temp_path)
+            .map_err(|e| AppError::new(format!("Failed to create output file: {}", e)))?;
+
+        ParquetWriter::new(file)
+            .with_statistics(true)
+            .finish(&mut df)
+            .map_err(|e| {
+                let _ = fs::remove_file(&temp_path);
+                AppError::new(format!("Failed to write parquet file: {}", e))
+            })?;
+
+        fs::rename(&temp_path, output_path).map_err(|e| {
+            let _ = fs::remove_file(&temp_path);
+            AppError::new(format!("Failed to move parquet file into place: {}", e))
+        })?;
+
+        Ok(())
+    }
+
+    /// Decodes file bytes to text
+    /// MT5 exports are frequently UTF-16LE with a BOM, so UTF-16 is detected by its BOM
+    fn decode_text(bytes: &[u8]) -> Result<String, AppError> {
+        if bytes.starts_with(&[0xFF, 0xFE]) || bytes.starts_with(&[0xFE, 0xFF]) {
+            let little_endian = bytes[0] == 0xFF;
+            let units: Vec<u16> = bytes[2..]
+                .chunks_exact(2)
+                .map(|pair| {
+                    if little_endian {
+                        u16::from_le_bytes([pair[0], pair[1]])
+                    } else {
+                        u16::from_be_bytes([pair[0], pair[1]])
+                    }
+                })
+                .collect();
+            return String::from_utf16(&units)
+                .map_err(|e| AppError::new(format!("File is not valid UTF-16: {}", e)));
+        }
+
+        let bytes = bytes.strip_prefix(&[0xEF, 0xBB, 0xBF]).unwrap_or(bytes);
+        String::from_utf8(bytes.to_vec())
+            .map_err(|e| AppError::new(format!("File is not valid UTF-8: {}", e)))
+    }
+
+    /// Resolves the layout of an MT5 "Export bars" file from its header row
+    /// Daily and higher timeframes are exported without a `<TIME>` column
+    fn resolve_mt5_layout(header: &str, utc_offset_minutes: i64) -> Result<ResolvedLayout, AppError> {
+        let delimiter = Self::detect_delimiter(header);
+        let columns: HashMap<String, usize> = header
+            .split(delimiter)
+            .enumerate()
+            .map(|(index, name)| {
+                let name = name.trim().trim_start_matches('<').trim_end_matches('>');
+                (name.to_uppercase(), index)
+            })
+            .collect();
+
+        let find = |name: &str| -> Result<usize, AppError> {
+            columns.get(name).copied().ok_or_else(|| {
+                AppError::new(format!(
+                    "Missing <{}> column. Is this a MetaTrader 5 \"Export bars\" file?",
+                    name
+                ))
+            })
+        };
+
+        let date = find("DATE")?;
+        let (date, time, time_format) = match columns.get("TIME").copied() {
+            Some(time) => (Some(date), time, "%Y.%m.%d %H:%M:%S"),
+            None => (None, date, "%Y.%m.%d"),
+        };
+
+        Ok(ResolvedLayout {
+            delimiter,
+            date,
+            time,
+            open: find("OPEN")?,
+            high: find("HIGH")?,
+            low: find("LOW")?,
+            close: find("CLOSE")?,
+            // Tick volume is the meaningful volume for most MT5 symbols; real volume is often zero
+            volume: columns.get("TICKVOL").or_else(|| columns.get("VOL")).copied(),
+            parser: TimeParser::Format(time_format.to_string()),
+            utc_offset_seconds: utc_offset_minutes * 60,
+        })
+    }
+
+    /// Resolves the layout of a generic CSV file from its mapping profile
+    fn resolve_generic_layout(
+        mapping: &CsvColumnMapping,
+        header: Option<&str>,
+    ) -> Result<ResolvedLayout, AppError> {
+        let header_columns: Option<HashMap<String, usize>> = header.map(|header| {
+            header
+                .split(mapping.delimiter)
+                .enumerate()
+                .map(|(index, name)| (name.trim().to_string(), index))
+                .collect()
+        });
+
+        let resolve = |column: &CsvColumnRef, field: &str| -> Result<usize, AppError> {
+            match column {
+                CsvColumnRef::Index(index) => Ok(*index),
+                CsvColumnRef::Name(name) => match &header_columns {
+                    Some(columns) => columns.get(name.as_str()).copied().ok_or_else(|| {
+                        AppError::new(format!("Column '{}' mapped to {} not found in header", name, field))
+                    }),
+                    None => Err(AppError::new(format!(
+                        "Column '{}' mapped to {} is referenced by name but the file has no header",
+                        name, field
+                    ))),
+                },
+            }
+        };
+
+        let parser = match mapping.time_format.as_str() {
+            "unix" => TimeParser::Unix,
+            "unix_ms" => TimeParser::UnixMs,
+            "" => return Err(AppError::new("time_format cannot be empty")),
+            format => TimeParser::Format(format.to_string()),
+        };
+
+        Ok(ResolvedLayout {
+            delimiter: mapping.delimiter,
+            date: mapping.date.as_ref().map(|c| resolve(c, "date")).transpose()?,
+            time: resolve(&mapping.time, "time")?,
+            open: resolve(&mapping.open, "open")?,
+            high: resolve(&mapping.high, "high")?,
+            low: resolve(&mapping.low, "low")?,
+            close: resolve(&mapping.close, "close")?,
+            volume: mapping.volume.as_ref().map(|c| resolve(c, "volume")).transpose()?,
+            parser,
+            utc_offset_seconds: mapping.utc_offset_minutes * 60,
+        })
+    }
+
+    /// Picks the delimiter used by a header line
+    fn detect_delimiter(header: &str) -> char {
+        if header.contains('\t') {
+            '\t'
+        } else if header.contains(';') {
+            ';'
+        } else {
+            ','
+        }
+    }
+
+    /// Parses and validates a single data row
+    fn parse_row(line: &str, layout: &ResolvedLayout) -> Result<ImportedBar, String> {
+        let fields: Vec<&str> = line.split(layout.delimiter).map(|f| f.trim()).collect();
+
+        let field = |index: usize, name: &str| -> Result<&str, String> {
+            fields
+                .get(index)
+                .copied()
+                .ok_or_else(|| format!("missing {} field (column {})", name, index))
+        };
+        let number = |index: usize, name: &str| -> Result<f64, String> {
+            let raw = field(index, name)?;
+            let value: f64 = raw
+                .parse()
+                .map_err(|_| format!("invalid {} value '{}'", name, raw))?;
+            if !value.is_finite() {
+                return Err(format!("{} is not a finite number", name));
+            }
+            Ok(value)
+        };
+
+        let raw_time = match layout.date {
+            Some(date_index) => format!("{} {}", field(date_index, "date")?, field(layout.time, "time")?),
+            None => field(layout.time, "time")?.to_string(),
+        };
+        let local_time = Self::parse_timestamp(&raw_time, &layout.parser)?;
+        let time = local_time - layout.utc_offset_seconds;
+
+        let open = number(layout.open, "open")?;
+        let high = number(layout.high, "high")?;
+        let low = number(layout.low, "low")?;
+        let close = number(layout.close, "close")?;
+        let volume = match layout.volume {
+            Some(index) => Some(number(index, "volume")?),
+            None => None,
+        };
+
+        if open <= 0.0 || high <= 0.0 || low <= 0.0 || close <= 0.0 {
+            return Err("prices must be positive".to_string());
+        }
+        if high < low {
+            return Err(format!("high {} is below low {}", high, low));
+        }
+        if high < open.max(close) || low > open.min(close) {
+            return Err(format!(
+                "open {} / close {} outside the high-low range [{}, {}]",
+                open, close, low, high
+            ));
+        }
+        if volume.is_some_and(|v| v < 0.0) {
+            return Err("volume cannot be negative".to_string());
+        }
+
+        Ok(ImportedBar {
+            time,
+            open,
+            high,
+            low,
+            close,
+            volume,
+        })
+    }
+
+    /// Parses a timestamp into Unix seconds (still in the file's local time)
+    /// Date-only formats are accepted and mapped to midnight
+    fn parse_timestamp(raw: &str, parser: &TimeParser) -> Result<i64, String> {
+        match parser {
+            TimeParser::Unix => raw
+                .parse::<i64>()
+                .map_err(|_| format!("invalid unix timestamp '{}'", raw)),
+            TimeParser::UnixMs => raw
+                .parse::<i64>()
+                .map(|ms| ms.div_euclid(1000))
+                .map_err(|_| format!("invalid unix millisecond timestamp '{}'", raw)),
+            TimeParser::Format(format) => {
+                if let Ok(datetime) = NaiveDateTime::parse_from_str(raw, format) {
+                    return Ok(datetime.and_utc().timestamp());
+                }
+                // MT5 sometimes omits seconds ("HH:MM")
+                if let Some(short_format) = format.strip_suffix(":%S") {
+                    if let Ok(datetime) = NaiveDateTime::parse_from_str(raw, short_format) {
+                        return Ok(datetime.and_utc().timestamp());
+                    }
+                }
+                NaiveDate::parse_from_str(raw, format)
+                    .ok()
+                    .and_then(|date| date.and_hms_opt(0, 0, 0))
+                    .map(|datetime| datetime.and_utc().timestamp())
+                    .ok_or_else(|| format!("timestamp '{}' does not match format '{}'", raw, format))
+            }
+        }
+    }
+
+    fn push_warning(warnings: &mut Vec<String>, warning: String) {
+        if warnings.len() < MAX_WARNINGS {
+            warnings.push(warning);
+        }
+    }
+}
@@ -1,7 +1,7 @@
 //! Download service for fetching assets from GitHub
 
 use crate::shared::error::AppError;
-use crate::features::asset::model::AssetRepoConfig;
+use crate::features::asset::model::{AssetRepoConfig, RemoteAssetFile};
 use serde::Deserialize;
 use std::io::Write;
 use std::path::PathBuf;
@@ -85,49 +85,86 @@ impl DownloadService {
     }
 
     /// Gets the list of parquet files in a symbol folder from GitHub API
-    /// Uses the Contents API to list files in the symbol directory
+    /// Uses the Git Trees API so partition folders (e.g. `1M/2024.parquet`,
+    /// `1M/year=2024/data.parquet`) are listed recursively, with the blob hash of each file
     pub async fn list_symbol_files(
         config: &AssetRepoConfig,
         symbol: &str,
-    ) -> Result<Vec<String>, AppError> {
+    ) -> Result<Vec<RemoteAssetFile>, AppError> {
         #[derive(Deserialize)]
-        struct GitHubContentItem {
-            name: String,
+        struct GitTreeItem {
+            path: String,
             #[serde(rename = "type")]
             item_type: String,
-            path: String,
+            sha: String,
+            #[serde(default)]
+            size: u64,
         }
 
-        let url = format!(
-            "{}/contents/{}?ref={}",
+        #[derive(Deserialize)]
+        struct GitTree {
+            tree: Vec<GitTreeItem>,
+            #[serde(default)]
+            truncated: bool,
+        }
+
+        async fn fetch_tree(url: &str) -> Result<GitTree, AppError> {
+            let response = reqwest::get(url)
+                .await
+                .map_err(|e| AppError::new(format!("Failed to fetch file list: {}", e)))?;
+
+            if !response.status().is_success() {
+                return Err(AppError::new(format!(
+                    "Failed to get file list: HTTP {}",
+                    response.status()
+                )));
+            }
+
+            response
+                .json()
+                .await
+                .map_err(|e| AppError::new(format!("Failed to parse response: {}", e)))
+        }
+
+        // Resolve the symbol folder in the root tree, then list that folder recursively
+        let root = fetch_tree(&format!(
+            "{}/git/trees/{}",
             config.api_base_url(),
-            symbol,
             config.branch
-        );
+        ))
+        .await?;
 
-        let response = reqwest::get(&url)
-            .await
-            .map_err(|e| AppError::new(format!("Failed to fetch file list: {}", e)))?;
+        let symbol_tree = root
+            .tree
+            .into_iter()
+            .find(|item| item.item_type == "tree" && item.path == symbol)
+            .ok_or_else(|| {
+                AppError::new(format!("Symbol '{}' does not exist in the repository", symbol))
+            })?;
 
-        if !response.status().is_success() {
+        let tree = fetch_tree(&format!(
+            "{}/git/trees/{}?recursive=1",
+            config.api_base_url(),
+            symbol_tree.sha
+        ))
+        .await?;
+
+        if tree.truncated {
             return Err(AppError::new(format!(
-                "Failed to get file list: HTTP {}. Symbol '{}' may not exist.",
-                response.status(),
+                "File list for symbol '{}' is too large to be listed by the GitHub API",
                 symbol
             )));
         }
 
-        let contents: Vec<GitHubContentItem> = response
-            .json()
-            .await
-            .map_err(|e| AppError::new(format!("Failed to parse response: {}", e)))?;
-
-        let parquet_files: Vec<String> = contents
+        let parquet_files: Vec<RemoteAssetFile> = tree
+            .tree
             .into_iter()
-            .filter(|item| {
-                item.item_type == "file" && item.name.ends_with(".parquet")
+            .filter(|item| item.item_type == "blob" && item.path.ends_with(".parquet"))
+            .map(|item| RemoteAssetFile {
+                path: format!("{}/{}", symbol, item.path),
+                sha: item.sha,
+                size: item.size,
             })
-            .map(|item| item.path)
             .collect();
 
         Ok(parquet_files)
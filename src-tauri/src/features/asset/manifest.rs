@@ -0,0 +1,151 @@
+//! Asset cache manifest - persisted bookkeeping for cached asset files
+
+use crate::shared::error::AppError;
+use crate::shared::utils::cache_dir;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet};
+use std::fs;
+use std::sync::Mutex;
+
+/// Serializes read-modify-write cycles on the manifest file
+static MANIFEST_LOCK: Mutex<()> = Mutex::new(());
+
+/// Record of a file rewritten by the parquet optimizer
+/// The size and modification time identify the optimized file, so a file that is
+/// replaced later (e.g. by a new download) is optimized again
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OptimizationRecord {
+    /// When the file was optimized (Unix seconds)
+    pub optimized_at: i64,
+    /// Size of the optimized file in bytes
+    pub size_bytes: u64,
+    /// Modification time of the optimized file (Unix seconds)
+    pub modified: i64,
+}
+
+/// Bookkeeping for a single cached dataset (`SYMBOL/timeframe`)
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ManifestEntry {
+    /// Last time the dataset was read (Unix seconds)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_accessed: Option<i64>,
+    /// Optimized files of the dataset, keyed by path relative to the symbol folder
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub optimized_files: BTreeMap<String, OptimizationRecord>,
+    /// Files downloaded from the asset repository, keyed by path relative to the
+    /// symbol folder, with the git blob hash they were downloaded at
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub synced_files: BTreeMap<String, String>,
+}
+
+/// Asset cache manifest stored as JSON next to the asset directory
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct AssetManifest {
+    /// Maximum size of the asset cache in bytes (no limit when unset)
+    #[serde(default)]
+    pub quota_bytes: Option<u64>,
+    /// Symbols that are never evicted by the quota
+    #[serde(default)]
+    pub pinned_symbols: BTreeSet<String>,
+    /// Entries keyed by `SYMBOL/timeframe`
+    #[serde(default)]
+    pub entries: BTreeMap<String, ManifestEntry>,
+}
+
+impl AssetManifest {
+    /// Builds the manifest key of a dataset
+    pub fn key(symbol: &str, timeframe: &str) -> String {
+        format!("{}/{}", symbol, timeframe)
+    }
+
+    /// Dataset (timeframe) a file belongs to, from its path relative to the symbol folder:
+    /// the stem of the first component (`1M.parquet` and `1M/2024.parquet` both map to `1M`)
+    pub fn dataset_name(relative: &str) -> String {
+        let first = relative.split('/').next().unwrap_or(relative);
+        first.strip_suffix(".parquet").unwrap_or(first).to_string()
+    }
+
+    /// Loads the manifest, returning an empty one if it does not exist yet
+    pub fn load() -> Result<Self, AppError> {
+        let path = cache_dir::get_asset_manifest_path()?;
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+
+        let content = fs::read_to_string(&path)
+            .map_err(|e| AppError::new(format!("Failed to read asset manifest: {}", e)))?;
+        serde_json::from_str(&content)
+            .map_err(|e| AppError::new(format!("Failed to parse asset manifest: {}", e)))
+    }
+
+    /// Writes the manifest to disk
+    pub fn save(&self) -> Result<(), AppError> {
+        let path = cache_dir::get_asset_manifest_path()?;
+        if let Some(parent) = path.parent() {
+            cache_dir::ensure_cache_dir(parent)?;
+        }
+
+        let content = serde_json::to_string_pretty(self)
+            .map_err(|e| AppError::new(format!("Failed to serialize asset manifest: {}", e)))?;
+        let temp_path = path.with_extension("json.tmp");
+        fs::write(&temp_path, content)
+            .map_err(|e| AppError::new(format!("Failed to write asset manifest: {}", e)))?;
+        fs::rename(&temp_path, &path)
+            .map_err(|e| AppError::new(format!("Failed to write asset manifest: {}", e)))
+    }
+
+    /// Loads the manifest, applies `update` and saves it, holding the manifest lock throughout
+    pub fn update<T>(update: impl FnOnce(&mut AssetManifest) -> T) -> Result<T, AppError> {
+        let _guard = MANIFEST_LOCK
+            .lock()
+            .map_err(|_| AppError::new("Asset manifest lock is poisoned"))?;
+
+        let mut manifest = Self::load()?;
+        let result = update(&mut manifest);
+        manifest.save()?;
+        Ok(result)
+    }
+
+    /// Returns the entry of a dataset, creating it if needed
+    pub fn entry_mut(&mut self, symbol: &str, timeframe: &str) -> &mut ManifestEntry {
+        self.entries.entry(Self::key(symbol, timeframe)).or_default()
+    }
+
+    /// Returns the entry of a dataset, if any
+    pub fn entry(&self, symbol: &str, timeframe: &str) -> Option<&ManifestEntry> {
+        self.entries.get(&Self::key(symbol, timeframe))
+    }
+
+    /// Git blob hashes of every synced file of a symbol, keyed by relative path
+    pub fn synced_files(&self, symbol: &str) -> BTreeMap<String, String> {
+        let prefix = format!("{}/", symbol);
+        self.entries
+            .iter()
+            .filter(|(key, _)| key.starts_with(&prefix))
+            .flat_map(|(_, entry)| entry.synced_files.clone())
+            .collect()
+    }
+
+    /// Forgets every synced file of a symbol
+    pub fn clear_synced_files(&mut self, symbol: &str) {
+        let prefix = format!("{}/", symbol);
+        for (key, entry) in self.entries.iter_mut() {
+            if key.starts_with(&prefix) {
+                entry.synced_files.clear();
+            }
+        }
+    }
+
+    /// Removes the entries of a symbol (all datasets, or a single one)
+    pub fn remove(&mut self, symbol: &str, timeframe: Option<&str>) {
+        match timeframe {
+            Some(timeframe) => {
+                self.entries.remove(&Self::key(symbol, timeframe));
+            }
+            None => {
+                let prefix = format!("{}/", symbol);
+                self.entries.retain(|key, _| !key.starts_with(&prefix));
+            }
+        }
+    }
+}
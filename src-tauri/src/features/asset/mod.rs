@@ -5,10 +5,29 @@ pub mod model;
 pub mod download;
 pub mod file_cleanup;
 pub mod parquet_reader;
+pub mod partition;
+pub mod csv_import;
+pub mod tick_reader;
+pub mod manifest;
+pub mod cache_manager;
+pub mod optimizer;
+pub mod alignment;
 
 pub use command::{
     pull_assets,
     pull_asset_by_symbol,
+    sync_asset_by_symbol,
     list_symbols,
     retrieve_asset_ochl,
+    retrieve_aligned_series,
+    import_ohlc,
+    build_bars_from_ticks,
+    retrieve_ticks_around,
+    get_asset_cache_usage,
+    delete_asset_symbol,
+    delete_asset_timeframe,
+    set_asset_cache_quota,
+    set_asset_symbol_pinned,
+    enforce_asset_cache_quota,
+    optimize_assets,
 };
@@ -1,5 +1,6 @@
 //! Asset-related data models
 
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
 /// Result of an asset operation
@@ -64,3 +65,241 @@ pub struct OHLCData {
     pub low: f64,     // Lowest price during the interval
     pub close: f64,   // Closing price
 }
+
+/// Inclusive time range in Unix seconds; an unset bound is open-ended
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, JsonSchema)]
+pub struct TimeRange {
+    /// First bar time to include (Unix seconds)
+    #[serde(default)]
+    pub start: Option<i64>,
+    /// Last bar time to include (Unix seconds)
+    #[serde(default)]
+    pub end: Option<i64>,
+}
+
+/// Source format of an OHLC CSV file passed to `import_ohlc`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum OhlcImportFormat {
+    /// MetaTrader 5 "Export bars" file (tab separated, `<DATE>`/`<TIME>` header)
+    Mt5 {
+        /// Offset of the broker server time from UTC in minutes (e.g. 120 for UTC+2)
+        #[serde(default)]
+        utc_offset_minutes: i64,
+    },
+    /// HistData.com ASCII bars (`20240102 170000;open;high;low;close;volume`)
+    /// Timestamps are EST without daylight saving (UTC-5)
+    HistData,
+    /// Any delimited file described by a column-mapping profile
+    Generic(CsvColumnMapping),
+}
+
+/// Reference to a CSV column, either by header name or by zero-based index
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum CsvColumnRef {
+    Index(usize),
+    Name(String),
+}
+
+/// Column-mapping profile for generic OHLC CSV files
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CsvColumnMapping {
+    /// Field delimiter (defaults to ",")
+    #[serde(default = "CsvColumnMapping::default_delimiter")]
+    pub delimiter: char,
+    /// Whether the first line is a header row (defaults to true)
+    #[serde(default = "CsvColumnMapping::default_has_header")]
+    pub has_header: bool,
+    /// Column holding the timestamp (or the time part when `date` is set)
+    pub time: CsvColumnRef,
+    /// Optional separate date column, joined with `time` by a space before parsing
+    #[serde(default)]
+    pub date: Option<CsvColumnRef>,
+    pub open: CsvColumnRef,
+    pub high: CsvColumnRef,
+    pub low: CsvColumnRef,
+    pub close: CsvColumnRef,
+    #[serde(default)]
+    pub volume: Option<CsvColumnRef>,
+    /// chrono format string (e.g. "%Y-%m-%d %H:%M:%S"), or "unix" / "unix_ms" for epoch values
+    pub time_format: String,
+    /// Offset of the file timestamps from UTC in minutes
+    #[serde(default)]
+    pub utc_offset_minutes: i64,
+}
+
+impl CsvColumnMapping {
+    fn default_delimiter() -> char {
+        ','
+    }
+
+    fn default_has_header() -> bool {
+        true
+    }
+}
+
+/// Result of an OHLC CSV import
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OhlcImportResult {
+    pub success: bool,
+    pub message: String,
+    /// Number of bars written to the parquet file
+    pub rows_imported: usize,
+    /// Number of data rows that failed validation and were skipped
+    pub rows_rejected: usize,
+    /// Per-row problems (line number and reason), capped to keep the payload small
+    pub warnings: Vec<String>,
+    /// First and last bar time (Unix seconds, UTC) of the written series
+    pub first_time: Option<i64>,
+    pub last_time: Option<i64>,
+}
+
+/// Tick (quote) data point
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TickData {
+    pub time_msc: i64, // Unix timestamp in milliseconds
+    pub bid: f64,      // Bid price
+    pub ask: f64,      // Ask price
+    pub volume: f64,   // Tick volume (0 when the source has no volume column)
+}
+
+/// Price used when building bars from ticks
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TickPriceSource {
+    #[default]
+    Bid,
+    Ask,
+    Mid,
+}
+
+/// Disk usage of a single timeframe (or other dataset such as `ticks`) of a symbol
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TimeframeUsage {
+    pub timeframe: String,
+    pub size_bytes: u64,
+    /// Last time the data was read (Unix seconds), if it was ever read
+    pub last_accessed: Option<i64>,
+}
+
+/// Disk usage of a symbol folder
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SymbolUsage {
+    pub symbol: String,
+    pub size_bytes: u64,
+    pub pinned: bool,
+    pub timeframes: Vec<TimeframeUsage>,
+}
+
+/// Disk usage report of the asset cache
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CacheUsageReport {
+    pub total_bytes: u64,
+    pub quota_bytes: Option<u64>,
+    pub symbols: Vec<SymbolUsage>,
+}
+
+/// Result of a quota enforcement run
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CacheEvictionResult {
+    /// Evicted datasets as `SYMBOL/timeframe`
+    pub evicted: Vec<String>,
+    pub freed_bytes: u64,
+    pub total_bytes: u64,
+    pub quota_bytes: Option<u64>,
+}
+
+/// Result of a parquet optimization run over the asset cache
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AssetOptimizationResult {
+    pub success: bool,
+    pub message: String,
+    /// Number of files rewritten
+    pub optimized_files: usize,
+    /// Number of files skipped because they were already optimized
+    pub skipped_files: usize,
+    /// Total size of the rewritten files before and after optimization
+    pub bytes_before: u64,
+    pub bytes_after: u64,
+    /// Files that could not be optimized, with the reason
+    pub errors: Vec<String>,
+}
+
+/// A parquet file of a symbol in the remote asset repository
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RemoteAssetFile {
+    /// Path in the repository (e.g. "EURUSD/1M/2024.parquet")
+    pub path: String,
+    /// Git blob hash, changes whenever the file content changes
+    pub sha: String,
+    pub size: u64,
+}
+
+/// Result of an incremental symbol sync
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AssetSyncResult {
+    pub success: bool,
+    pub message: String,
+    /// Files downloaded because they were new or changed remotely
+    pub downloaded: Vec<String>,
+    /// Number of files already up to date
+    pub unchanged: usize,
+    /// Previously synced files removed because they no longer exist remotely
+    pub removed: Vec<String>,
+}
+
+/// Bar field selected for aligned multi-symbol series
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum OhlcField {
+    Open,
+    High,
+    Low,
+    #[default]
+    Close,
+    Volume,
+}
+
+impl OhlcField {
+    /// Column name in the OHLCV frame
+    pub fn column(&self) -> &'static str {
+        match self {
+            OhlcField::Open => "open",
+            OhlcField::High => "high",
+            OhlcField::Low => "low",
+            OhlcField::Close => "close",
+            OhlcField::Volume => "volume",
+        }
+    }
+}
+
+/// How timestamps missing for some symbols are handled in aligned series
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FillPolicy {
+    /// Repeat the previous value of the symbol (values before its first bar stay null)
+    ForwardFill,
+    /// Keep only timestamps where every symbol has a bar
+    DropIncomplete,
+    /// Keep every timestamp and leave missing values null
+    #[default]
+    LeaveNull,
+}
+
+/// Values of one symbol in an aligned result
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AlignedSeries {
+    pub symbol: String,
+    /// One value per timestamp of `AlignedSeriesResult::time`
+    pub values: Vec<Option<f64>>,
+}
+
+/// Several symbols joined on `time`, in columnar form
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AlignedSeriesResult {
+    /// Union of the bar timestamps (Unix seconds), sorted
+    pub time: Vec<i64>,
+    pub field: OhlcField,
+    pub series: Vec<AlignedSeries>,
+}
@@ -0,0 +1,204 @@
+//! Parquet optimization of cached asset files
+//!
+//! Downloaded files keep whatever layout the source used. The optimizer rewrites them
+//! sorted by time, zstd-compressed, with column statistics and row groups small enough
+//! for time-range predicates to skip most of a long history.
+
+use crate::shared::error::AppError;
+use crate::shared::utils::cache_dir;
+use crate::features::asset::manifest::{AssetManifest, OptimizationRecord};
+use crate::features::asset::model::AssetOptimizationResult;
+use polars::prelude::*;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::UNIX_EPOCH;
+
+/// Rows per row group (about six weeks of minute bars)
+const OPTIMIZED_ROW_GROUP_SIZE: usize = 65_536;
+
+/// Outcome of optimizing a single file
+struct FileOptimization {
+    bytes_before: u64,
+    bytes_after: u64,
+    record: OptimizationRecord,
+}
+
+/// Service for rewriting cached parquet files into a read-optimized layout
+pub struct ParquetOptimizerService;
+
+impl ParquetOptimizerService {
+    /// Optimizes every parquet file of the given symbol folders
+    /// Files already recorded as optimized in the manifest are skipped unless `force` is set
+    pub fn optimize_symbols(
+        symbol_dirs: &[PathBuf],
+        force: bool,
+    ) -> Result<AssetOptimizationResult, AppError> {
+        let manifest = AssetManifest::load()?;
+
+        let mut optimized: Vec<(String, String, String, OptimizationRecord)> = Vec::new();
+        let mut skipped_files = 0;
+        let mut bytes_before = 0;
+        let mut bytes_after = 0;
+        let mut errors = Vec::new();
+
+        for symbol_dir in symbol_dirs {
+            let symbol = match symbol_dir.file_name() {
+                Some(name) => name.to_string_lossy().to_string(),
+                None => continue,
+            };
+
+            let mut files = Vec::new();
+            cache_dir::collect_parquet_files(symbol_dir, &mut files)?;
+            files.sort();
+
+            for file_path in files {
+                let relative = Self::relative_key(symbol_dir, &file_path);
+                let dataset = AssetManifest::dataset_name(&relative);
+
+                let up_to_date = manifest
+                    .entry(&symbol, &dataset)
+                    .and_then(|entry| entry.optimized_files.get(&relative))
+                    .is_some_and(|record| Self::matches_record(&file_path, record));
+                if up_to_date && !force {
+                    skipped_files += 1;
+                    continue;
+                }
+
+                match Self::optimize_file(&file_path) {
+                    Ok(outcome) => {
+                        bytes_before += outcome.bytes_before;
+                        bytes_after += outcome.bytes_after;
+                        optimized.push((symbol.clone(), dataset, relative, outcome.record));
+                    }
+                    Err(e) => errors.push(format!("{}/{}: {}", symbol, relative, e.message)),
+                }
+            }
+        }
+
+        let optimized_files = optimized.len();
+        AssetManifest::update(|manifest| {
+            for (symbol, dataset, relative, record) in optimized {
+                manifest
+                    .entry_mut(&symbol, &dataset)
+                    .optimized_files
+                    .insert(relative, record);
+            }
+        })?;
+
+        let message = format!(
+            "Optimized {} file(s), skipped {} already optimized, {} failed",
+            optimized_files,
+            skipped_files,
+            errors.len()
+        );
+
+        Ok(AssetOptimizationResult {
+            success: errors.is_empty(),
+            message,
+            optimized_files,
+            skipped_files,
+            bytes_before,
+            bytes_after,
+            errors,
+        })
+    }
+
+    /// Rewrites a single parquet file sorted by its time column
+    /// (`time`, or `time_msc` for tick files) with zstd compression and statistics
+    fn optimize_file(file_path: &Path) -> Result<FileOptimization, AppError> {
+        let bytes_before = fs::metadata(file_path)
+            .map(|m| m.len())
+            .map_err(|e| AppError::new(format!("Failed to read file metadata: {}", e)))?;
+
+        let lazy = LazyFrame::scan_parquet(
+            file_path,
+            ScanArgsParquet {
+                n_rows: None,
+                cache: true,
+                parallel: ParallelStrategy::Auto,
+                rechunk: true,
+                row_index: None,
+                low_memory: false,
+                cloud_options: None,
+                use_statistics: true,
+                hive_options: Default::default(),
+                glob: false,
+            },
+        )
+        .map_err(|e| AppError::new(format!("Failed to scan parquet file: {}", e)))?;
+
+        let schema = lazy
+            .schema()
+            .map_err(|e| AppError::new(format!("Failed to read schema: {}", e)))?;
+        let sort_column = ["time", "time_msc"]
+            .into_iter()
+            .find(|name| schema.contains(name))
+            .ok_or_else(|| AppError::new("File has no 'time' or 'time_msc' column"))?;
+
+        let mut df = lazy
+            .sort([sort_column], SortMultipleOptions::default().with_maintain_order(true))
+            .collect()
+            .map_err(|e| AppError::new(format!("Failed to read parquet file: {}", e)))?;
+
+        let temp_path = file_path.with_extension("parquet.tmp");
+        let file = fs::File::create(&temp_path)
+            .map_err(|e| AppError::new(format!("Failed to create output file: {}", e)))?;
+
+        ParquetWriter::new(file)
+            .with_compression(ParquetCompression::Zstd(None))
+            .with_statistics(true)
+            .with_row_group_size(Some(OPTIMIZED_ROW_GROUP_SIZE))
+            .finish(&mut df)
+            .map_err(|e| {
+                let _ = fs::remove_file(&temp_path);
+                AppError::new(format!("Failed to write parquet file: {}", e))
+            })?;
+
+        fs::rename(&temp_path, file_path).map_err(|e| {
+            let _ = fs::remove_file(&temp_path);
+            AppError::new(format!("Failed to replace parquet file: {}", e))
+        })?;
+
+        let (bytes_after, modified) = Self::file_fingerprint(file_path)
+            .ok_or_else(|| AppError::new("Failed to read optimized file metadata"))?;
+
+        Ok(FileOptimization {
+            bytes_before,
+            bytes_after,
+            record: OptimizationRecord {
+                optimized_at: chrono::Utc::now().timestamp(),
+                size_bytes: bytes_after,
+                modified,
+            },
+        })
+    }
+
+    /// Path of a file relative to its symbol folder, with `/` separators
+    fn relative_key(symbol_dir: &Path, file_path: &Path) -> String {
+        file_path
+            .strip_prefix(symbol_dir)
+            .unwrap_or(file_path)
+            .components()
+            .map(|c| c.as_os_str().to_string_lossy().to_string())
+            .collect::<Vec<_>>()
+            .join("/")
+    }
+
+    /// Whether a file is still the one described by an optimization record
+    fn matches_record(file_path: &Path, record: &OptimizationRecord) -> bool {
+        Self::file_fingerprint(file_path)
+            .is_some_and(|(size, modified)| size == record.size_bytes && modified == record.modified)
+    }
+
+    /// Size and modification time (Unix seconds) of a file
+    fn file_fingerprint(file_path: &Path) -> Option<(u64, i64)> {
+        let metadata = fs::metadata(file_path).ok()?;
+        let modified = metadata
+            .modified()
+            .ok()?
+            .duration_since(UNIX_EPOCH)
+            .ok()?
+            .as_secs() as i64;
+        Some((metadata.len(), modified))
+    }
+}
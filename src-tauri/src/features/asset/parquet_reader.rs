@@ -9,36 +9,53 @@ use std::path::PathBuf;
 pub struct ParquetReaderService;
 
 impl ParquetReaderService {
-    /// Reads OHLC data from a parquet file
+    /// Reads OHLC data from one or more parquet files as a single series
+    /// Keeps bars with `start <= time <= end` (Unix seconds, either bound optional).
+    /// Bars present in several files are kept once, preferring the file listed last.
     /// Returns a vector of OHLCData points
-    pub fn read_ochl_data(file_path: &PathBuf) -> Result<Vec<OHLCData>, AppError> {
-        // Read parquet file using Polars
-        let df = LazyFrame::scan_parquet(
-            file_path,
-            ScanArgsParquet {
-                n_rows: None,
-                cache: true,
-                parallel: ParallelStrategy::Auto,
-                rechunk: false,
-                row_index: None,
-                low_memory: false,
-                cloud_options: None,
-                use_statistics: true,
-                hive_options: Default::default(),
-                glob: false,
-            },
-        )
-        .map_err(|e| AppError::new(format!("Failed to scan parquet file: {}", e)))?
-        .select([
-            col("time"),
-            col("open"),
-            col("high"),
-            col("low"),
-            col("close"),
-        ])
-        .sort(["time"], SortMultipleOptions::default())
-        .collect()
-        .map_err(|e| AppError::new(format!("Failed to collect data: {}", e)))?;
+    pub fn read_ochl_range(
+        file_paths: &[PathBuf],
+        start: Option<i64>,
+        end: Option<i64>,
+    ) -> Result<Vec<OHLCData>, AppError> {
+        if file_paths.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        Self::collect_ochl(Self::scan_ohlcv(file_paths, start, end)?)
+    }
+
+    /// Reads the OHLC bars of one or more parquet files inside any of `windows`
+    /// (`(start, end)` Unix seconds, inclusive, sorted and not overlapping)
+    /// Bars between the windows are filtered out before the frame is collected
+    pub fn read_ochl_windows(file_paths: &[PathBuf], windows: &[(i64, i64)]) -> Result<Vec<OHLCData>, AppError> {
+        let (Some(first), Some(last)) = (windows.first(), windows.last()) else {
+            return Ok(Vec::new());
+        };
+        if file_paths.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let in_windows = windows
+            .iter()
+            .map(|&(start, end)| col("time").gt_eq(lit(start)).and(col("time").lt_eq(lit(end))))
+            .reduce(|any, window| any.or(window))
+            .unwrap_or_else(|| lit(false));
+        Self::collect_ochl(Self::scan_ohlcv(file_paths, Some(first.0), Some(last.1))?.filter(in_windows))
+    }
+
+    /// Collects the OHLC columns of a `scan_ohlcv` frame
+    fn collect_ochl(lazy: LazyFrame) -> Result<Vec<OHLCData>, AppError> {
+        let df = lazy
+            .select([
+                col("time"),
+                col("open"),
+                col("high"),
+                col("low"),
+                col("close"),
+            ])
+            .collect()
+            .map_err(|e| AppError::new(format!("Failed to collect data: {}", e)))?;
 
         // Extract columns
         let time_col = df
@@ -89,4 +106,93 @@ impl ParquetReaderService {
 
         Ok(ochl_data)
     }
+
+    /// Lazily scans one or more parquet files as a single time-sorted OHLCV series
+    /// Columns: `time`, `open`, `high`, `low`, `close` and `volume` (Float64; taken from
+    /// `volume` or `tick_volume`, null when a file has neither).
+    /// Range filtering and de-duplication follow `read_ochl_range`.
+    /// Without files (e.g. every partition pruned by the range) the frame is empty.
+    pub fn scan_ohlcv(
+        file_paths: &[PathBuf],
+        start: Option<i64>,
+        end: Option<i64>,
+    ) -> Result<LazyFrame, AppError> {
+        let mut frames = Vec::with_capacity(file_paths.len());
+        for file_path in file_paths {
+            let frame = LazyFrame::scan_parquet(
+                file_path,
+                ScanArgsParquet {
+                    n_rows: None,
+                    cache: true,
+                    parallel: ParallelStrategy::Auto,
+                    rechunk: false,
+                    row_index: None,
+                    low_memory: false,
+                    cloud_options: None,
+                    use_statistics: true,
+                    hive_options: Default::default(),
+                    glob: false,
+                },
+            )
+            .map_err(|e| {
+                AppError::new(format!(
+                    "Failed to scan parquet file {}: {}",
+                    file_path.display(),
+                    e
+                ))
+            })?;
+
+            let schema = frame
+                .schema()
+                .map_err(|e| AppError::new(format!("Failed to read schema: {}", e)))?;
+            let volume = match ["volume", "tick_volume"]
+                .into_iter()
+                .find(|name| schema.contains(name))
+            {
+                Some(name) => col(name).cast(DataType::Float64),
+                None => lit(NULL).cast(DataType::Float64),
+            };
+
+            frames.push(frame.select([
+                col("time"),
+                col("open"),
+                col("high"),
+                col("low"),
+                col("close"),
+                volume.alias("volume"),
+            ]));
+        }
+
+        let mut lazy = match frames.len() {
+            0 => return Self::empty_ohlcv(),
+            1 => frames.remove(0),
+            _ => concat(frames, UnionArgs::default())
+                .map_err(|e| AppError::new(format!("Failed to combine parquet files: {}", e)))?,
+        };
+
+        // Range filters are pushed down to the scans, so row groups outside the range are skipped
+        if let Some(start) = start {
+            lazy = lazy.filter(col("time").gt_eq(lit(start)));
+        }
+        if let Some(end) = end {
+            lazy = lazy.filter(col("time").lt_eq(lit(end)));
+        }
+
+        if file_paths.len() > 1 {
+            lazy = lazy.unique_stable(Some(vec!["time".to_string()]), UniqueKeepStrategy::Last);
+        }
+
+        Ok(lazy.sort(["time"], SortMultipleOptions::default()))
+    }
+
+    /// Empty frame with the columns and types of `scan_ohlcv`
+    fn empty_ohlcv() -> Result<LazyFrame, AppError> {
+        let mut columns = vec![Series::new_empty("time", &DataType::Int64)];
+        for name in ["open", "high", "low", "close", "volume"] {
+            columns.push(Series::new_empty(name, &DataType::Float64));
+        }
+        DataFrame::new(columns)
+            .map(|df| df.lazy())
+            .map_err(|e| AppError::new(format!("Failed to create empty OHLCV frame: {}", e)))
+    }
 }
@@ -0,0 +1,146 @@
+//! Partitioned timeframe datasets
+//!
+//! A timeframe of a symbol can be stored as a single file (`EURUSD/1M.parquet`) or as a
+//! folder of partitions read as one logical series:
+//! - yearly or monthly files: `EURUSD/1M/2024.parquet`, `EURUSD/1M/2024-01.parquet`
+//! - hive-style folders: `EURUSD/1M/year=2024/month=01/data.parquet`
+//!
+//! Partition names carrying a date give each file a time span, so time-range reads can
+//! skip partitions that cannot contain matching bars.
+
+use crate::shared::error::AppError;
+use crate::shared::utils::cache_dir;
+use chrono::{Months, NaiveDate};
+use std::path::{Path, PathBuf};
+
+/// A parquet file belonging to a timeframe dataset
+#[derive(Debug, Clone)]
+pub struct Partition {
+    pub path: PathBuf,
+    /// Start of the partition span (Unix seconds, inclusive), if known from its name
+    pub start: Option<i64>,
+    /// End of the partition span (Unix seconds, exclusive), if known from its name
+    pub end: Option<i64>,
+}
+
+impl Partition {
+    /// Whether the partition may hold bars in `[start, end]`
+    pub fn overlaps(&self, start: Option<i64>, end: Option<i64>) -> bool {
+        let starts_after_range = matches!((self.start, end), (Some(p_start), Some(end)) if p_start > end);
+        let ends_before_range = matches!((self.end, start), (Some(p_end), Some(start)) if p_end <= start);
+        !starts_after_range && !ends_before_range
+    }
+}
+
+/// Service for resolving timeframe datasets to their parquet files
+pub struct PartitionService;
+
+impl PartitionService {
+    /// Resolves every parquet file of a timeframe: the single `{timeframe}.parquet` file
+    /// and/or all parquet files under the `{timeframe}/` folder, sorted by path
+    pub fn resolve_dataset(symbol_dir: &Path, timeframe: &str) -> Result<Vec<Partition>, AppError> {
+        let mut partitions = Vec::new();
+
+        let single_file = symbol_dir.join(format!("{}.parquet", timeframe));
+        if single_file.is_file() {
+            partitions.push(Partition {
+                path: single_file,
+                start: None,
+                end: None,
+            });
+        }
+
+        let partition_dir = symbol_dir.join(timeframe);
+        if partition_dir.is_dir() {
+            let mut files = Vec::new();
+            cache_dir::collect_parquet_files(&partition_dir, &mut files)?;
+            files.sort();
+
+            for path in files {
+                let relative = path.strip_prefix(&partition_dir).unwrap_or(&path);
+                let (start, end) = Self::partition_span(relative).unzip();
+                partitions.push(Partition { path, start, end });
+            }
+        }
+
+        Ok(partitions)
+    }
+
+    /// Keeps the partitions that may hold bars in `[start, end]`
+    pub fn prune(partitions: Vec<Partition>, start: Option<i64>, end: Option<i64>) -> Vec<Partition> {
+        partitions
+            .into_iter()
+            .filter(|p| p.overlaps(start, end))
+            .collect()
+    }
+
+    /// Derives the time span of a partition from its path relative to the timeframe folder
+    /// Recognizes `2024`, `2024-01`, `2024-01-15` names and `year=`, `month=`, `day=`, `date=` hive keys
+    fn partition_span(relative: &Path) -> Option<(i64, i64)> {
+        let mut year: Option<i32> = None;
+        let mut month: Option<u32> = None;
+        let mut day: Option<u32> = None;
+
+        for component in relative.components() {
+            let name = component.as_os_str().to_string_lossy();
+            let name = name.strip_suffix(".parquet").unwrap_or(&name);
+
+            if let Some((key, value)) = name.split_once('=') {
+                match key.to_ascii_lowercase().as_str() {
+                    "year" => year = value.parse().ok(),
+                    "month" => month = value.parse().ok(),
+                    "day" => day = value.parse().ok(),
+                    "date" => {
+                        if let Some((y, m, d)) = Self::parse_date_name(value) {
+                            year = Some(y);
+                            month = m;
+                            day = d;
+                        }
+                    }
+                    _ => {}
+                }
+            } else if let Some((y, m, d)) = Self::parse_date_name(name) {
+                year = Some(y);
+                month = m.or(month);
+                day = d.or(day);
+            }
+        }
+
+        let year = year?;
+        let start = NaiveDate::from_ymd_opt(year, month.unwrap_or(1), day.unwrap_or(1))?;
+        let end = match (month, day) {
+            (Some(_), Some(_)) => start.succ_opt()?,
+            (Some(_), None) => start.checked_add_months(Months::new(1))?,
+            _ => start.checked_add_months(Months::new(12))?,
+        };
+
+        Some((
+            start.and_hms_opt(0, 0, 0)?.and_utc().timestamp(),
+            end.and_hms_opt(0, 0, 0)?.and_utc().timestamp(),
+        ))
+    }
+
+    /// Parses `YYYY`, `YYYY-MM` or `YYYY-MM-DD` partition names
+    fn parse_date_name(name: &str) -> Option<(i32, Option<u32>, Option<u32>)> {
+        let mut parts = name.split('-');
+        let year_part = parts.next()?;
+        if year_part.len() != 4 {
+            return None;
+        }
+        let year: i32 = year_part.parse().ok()?;
+
+        let month = match parts.next() {
+            Some(part) => Some(part.parse::<u32>().ok().filter(|m| (1..=12).contains(m))?),
+            None => None,
+        };
+        let day = match parts.next() {
+            Some(part) => Some(part.parse::<u32>().ok().filter(|d| (1..=31).contains(d))?),
+            None => None,
+        };
+        if parts.next().is_some() {
+            return None;
+        }
+
+        Some((year, month, day))
+    }
+}
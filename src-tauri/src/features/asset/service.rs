@@ -1,21 +1,38 @@
 //! Asset service - orchestrates asset download and management operations
 
 use crate::shared::error::AppError;
-use crate::shared::utils::{cache_dir, validation, zip_extract};
-use crate::features::asset::model::{AssetOperationResult, AssetRepoConfig, OHLCData};
+use crate::shared::utils::{cache_dir, timeframe, validation, zip_extract};
+use crate::features::asset::model::{
+    AlignedSeriesResult, AssetOperationResult, AssetOptimizationResult, AssetRepoConfig,
+    AssetSyncResult, CacheEvictionResult, CacheUsageReport, FillPolicy, OHLCData, OhlcField,
+    OhlcImportFormat, OhlcImportResult, TickData, TickPriceSource, TimeRange,
+};
+use crate::features::asset::alignment::SeriesAlignmentService;
+use crate::features::asset::cache_manager::CacheManagerService;
+use crate::features::asset::csv_import::OhlcImportService;
 use crate::features::asset::download::DownloadService;
 use crate::features::asset::file_cleanup::FileCleanupService;
+use crate::features::asset::manifest::AssetManifest;
+use crate::features::asset::optimizer::ParquetOptimizerService;
 use crate::features::asset::parquet_reader::ParquetReaderService;
-use std::path::PathBuf;
+use crate::features::asset::partition::PartitionService;
+use crate::features::asset::tick_reader::TickReaderService;
+use std::collections::HashSet;
+use std::path::{Component, Path, PathBuf};
 use std::fs;
 
+/// Account-type suffixes brokers append to symbol names without a separator (e.g. `EURUSDm`)
+const BROKER_SYMBOL_SUFFIXES: &[&str] = &["M", "PRO", "ECN", "RAW", "STD", "MINI", "MICRO", "CASH"];
+
 /// Service for managing asset downloads and operations
 pub struct AssetService;
 
 impl AssetService {
     /// Pulls all assets from the repository
     /// Downloads the entire repo, extracts it, and cleans up unwanted files
-    pub async fn pull_assets() -> Result<AssetOperationResult, AppError> {
+    /// When `optimize` is set, every downloaded parquet file is rewritten for faster reads
+    /// (files that cannot be rewritten are listed in the message)
+    pub async fn pull_assets(optimize: bool) -> Result<AssetOperationResult, AppError> {
         let config = AssetRepoConfig::default();
         let cache_dir = cache_dir::get_asset_cache_dir()?;
 
@@ -49,15 +66,34 @@ impl AssetService {
         // Clean up unwanted files (.md, .git)
         FileCleanupService::cleanup_directory(&cache_dir)?;
 
+        // Optional post-download optimization
+        let optimization = if optimize {
+            let symbol_dirs = Self::symbol_dirs(&cache_dir)?;
+            Some(ParquetOptimizerService::optimize_symbols(&symbol_dirs, false)?)
+        } else {
+            None
+        };
+
+        // Everything in the cache was just downloaded, so evicting would only delete fresh data
+        // that the next pull downloads again; an overage is reported instead
+        let usage = CacheManagerService::usage_report()?;
+
         Ok(AssetOperationResult::success(format!(
-            "Successfully pulled all assets to {}",
-            cache_dir.display()
+            "Successfully pulled all assets to {}{}{}",
+            cache_dir.display(),
+            Self::optimization_errors(optimization.as_ref()),
+            Self::quota_overage(usage.total_bytes, usage.quota_bytes)
         )))
     }
 
     /// Pulls assets for a specific symbol
     /// Downloads only the parquet files for the given symbol
-    pub async fn pull_asset_by_symbol(symbol: &str) -> Result<AssetOperationResult, AppError> {
+    /// When `optimize` is set, the downloaded files are rewritten for faster reads
+    /// (files that cannot be rewritten are listed in the message)
+    pub async fn pull_asset_by_symbol(
+        symbol: &str,
+        optimize: bool,
+    ) -> Result<AssetOperationResult, AppError> {
         // Validate symbol
         validation::validate_non_empty(symbol, "symbol")?;
         
@@ -91,27 +127,133 @@ impl AssetService {
             )));
         }
 
-        // Download each parquet file
-        for file_path in &parquet_files {
-            let file_name = PathBuf::from(file_path)
-                .file_name()
-                .ok_or_else(|| AppError::new(format!("Invalid file path: {}", file_path)))?
-                .to_string_lossy()
-                .to_string();
-
-            let output_path = symbol_dir.join(&file_name);
+        // Download each parquet file, keeping partition folders
+        for remote_file in &parquet_files {
+            let relative = Self::remote_relative_path(symbol, &remote_file.path)?;
+            let output_path = symbol_dir.join(&relative);
 
-            DownloadService::download_file(&config, file_path, &output_path).await?;
+            DownloadService::download_file(&config, &remote_file.path, &output_path).await?;
         }
 
+        // Remember what was downloaded so later syncs only fetch changed partitions
+        AssetManifest::update(|manifest| {
+            manifest.clear_synced_files(symbol);
+            for remote_file in &parquet_files {
+                if let Ok(relative) = Self::remote_relative_path(symbol, &remote_file.path) {
+                    manifest
+                        .entry_mut(symbol, &AssetManifest::dataset_name(&relative))
+                        .synced_files
+                        .insert(relative, remote_file.sha.clone());
+                }
+            }
+        })?;
+
+        // Optional post-download optimization
+        let optimization = if optimize {
+            Some(ParquetOptimizerService::optimize_symbols(std::slice::from_ref(&symbol_dir), false)?)
+        } else {
+            None
+        };
+
+        // Make room for the new symbol without evicting it
+        CacheManagerService::enforce_quota(&[symbol])?;
+
         Ok(AssetOperationResult::success(format!(
-            "Successfully pulled {} parquet file(s) for symbol {} to {}",
+            "Successfully pulled {} parquet file(s) for symbol {} to {}{}",
             parquet_files.len(),
             symbol,
-            symbol_dir.display()
+            symbol_dir.display(),
+            Self::optimization_errors(optimization.as_ref())
         )))
     }
 
+    /// Incrementally syncs a symbol with the asset repository
+    /// Downloads only files (partitions) that are new or whose content changed since the
+    /// last pull/sync, and removes previously synced files that no longer exist remotely.
+    /// Local files that were never synced (e.g. imported history) are left untouched.
+    pub async fn sync_asset_by_symbol(
+        symbol: &str,
+        optimize: bool,
+    ) -> Result<AssetSyncResult, AppError> {
+        Self::validate_symbol(symbol)?;
+
+        let config = AssetRepoConfig::default();
+        let symbol_dir = cache_dir::get_symbol_cache_dir(symbol)?;
+        cache_dir::ensure_cache_dir(&symbol_dir)?;
+
+        let remote_files = DownloadService::list_symbol_files(&config, symbol).await?;
+        let synced = AssetManifest::load()?.synced_files(symbol);
+
+        let mut downloaded: Vec<(String, String)> = Vec::new();
+        let mut unchanged = 0;
+        let mut remote_paths = HashSet::new();
+
+        for remote_file in &remote_files {
+            let relative = Self::remote_relative_path(symbol, &remote_file.path)?;
+            let output_path = symbol_dir.join(&relative);
+            remote_paths.insert(relative.clone());
+
+            let up_to_date = output_path.is_file() && synced.get(&relative) == Some(&remote_file.sha);
+            if up_to_date {
+                unchanged += 1;
+                continue;
+            }
+
+            DownloadService::download_file(&config, &remote_file.path, &output_path).await?;
+            downloaded.push((relative, remote_file.sha.clone()));
+        }
+
+        // Files we synced earlier that were deleted upstream
+        let mut removed = Vec::new();
+        for relative in synced.keys().filter(|relative| !remote_paths.contains(*relative)) {
+            let path = symbol_dir.join(relative);
+            if path.is_file() {
+                fs::remove_file(&path).map_err(|e| {
+                    AppError::new(format!("Failed to remove {}: {}", path.display(), e))
+                })?;
+            }
+            removed.push(relative.clone());
+        }
+
+        AssetManifest::update(|manifest| {
+            for (relative, sha) in &downloaded {
+                let entry = manifest.entry_mut(symbol, &AssetManifest::dataset_name(relative));
+                entry.optimized_files.remove(relative);
+                entry.synced_files.insert(relative.clone(), sha.clone());
+            }
+            for relative in &removed {
+                let entry = manifest.entry_mut(symbol, &AssetManifest::dataset_name(relative));
+                entry.optimized_files.remove(relative);
+                entry.synced_files.remove(relative);
+            }
+        })?;
+
+        let optimization = if optimize && !downloaded.is_empty() {
+            Some(ParquetOptimizerService::optimize_symbols(std::slice::from_ref(&symbol_dir), false)?)
+        } else {
+            None
+        };
+
+        // Make room for the updated symbol without evicting it
+        CacheManagerService::enforce_quota(&[symbol])?;
+
+        let downloaded: Vec<String> = downloaded.into_iter().map(|(relative, _)| relative).collect();
+        Ok(AssetSyncResult {
+            success: true,
+            message: format!(
+                "Synced {}: {} file(s) downloaded, {} unchanged, {} removed{}",
+                symbol,
+                downloaded.len(),
+                unchanged,
+                removed.len(),
+                Self::optimization_errors(optimization.as_ref())
+            ),
+            downloaded,
+            unchanged,
+            removed,
+        })
+    }
+
     /// Lists all available symbols (folder names) in the cache directory
     /// Returns a vector of symbol names as strings
     pub fn list_symbols() -> Result<Vec<String>, AppError> {
@@ -149,12 +291,161 @@ impl AssetService {
         Ok(symbols)
     }
 
+    /// Maps a broker symbol (e.g. `EURUSD.m`, `EURUSDpro`, `eurusd`) to a cached symbol
+    /// Tries an exact match, a case-insensitive match, the part before a suffix separator
+    /// and finally a cached symbol followed by a known broker suffix (`BROKER_SYMBOL_SUFFIXES`)
+    pub fn resolve_cached_symbol(broker_symbol: &str) -> Result<String, AppError> {
+        validation::validate_non_empty(broker_symbol, "symbol")?;
+        let symbols = Self::list_symbols()?;
+
+        if symbols.iter().any(|symbol| symbol == broker_symbol) {
+            return Ok(broker_symbol.to_string());
+        }
+
+        let upper = broker_symbol.trim().to_ascii_uppercase();
+        let find = |name: &str| symbols.iter().find(|symbol| symbol.to_ascii_uppercase() == name);
+
+        let base = upper
+            .split(['.', '_', '-', '#', '!', '+'])
+            .next()
+            .unwrap_or(&upper);
+
+        find(&upper)
+            .or_else(|| find(base))
+            .or_else(|| {
+                // Any other remainder may name a different instrument (e.g. "EURUSDX")
+                symbols.iter().find(|symbol| {
+                    upper
+                        .strip_prefix(&symbol.to_ascii_uppercase())
+                        .is_some_and(|suffix| BROKER_SYMBOL_SUFFIXES.contains(&suffix))
+                })
+            })
+            .cloned()
+            .ok_or_else(|| {
+                AppError::new(format!(
+                    "No cached asset data matches symbol '{}'",
+                    broker_symbol
+                ))
+            })
+    }
+
+    /// Returns the shortest cached timeframe of a symbol (e.g. "1M" when both "1M" and "1H" exist)
+    /// Folders and files whose name is not a timeframe (such as tick data) are ignored
+    pub fn finest_timeframe(symbol: &str) -> Result<String, AppError> {
+        let symbol_dir = cache_dir::get_symbol_cache_dir(symbol)?;
+        if !symbol_dir.exists() {
+            return Err(AppError::new(format!(
+                "Symbol directory not found: {}",
+                symbol_dir.display()
+            )));
+        }
+
+        let entries = fs::read_dir(&symbol_dir)
+            .map_err(|e| AppError::new(format!("Failed to read symbol directory: {}", e)))?;
+
+        let mut finest: Option<(i64, String)> = None;
+        for entry in entries {
+            let entry = entry
+                .map_err(|e| AppError::new(format!("Failed to read directory entry: {}", e)))?;
+            let name = entry.file_name().to_string_lossy().to_string();
+            let name = name.strip_suffix(".parquet").unwrap_or(&name);
+
+            let Ok(seconds) = timeframe::parse_timeframe_seconds(name) else {
+                continue;
+            };
+            if finest.as_ref().is_some_and(|(best, _)| *best <= seconds) {
+                continue;
+            }
+            if !PartitionService::resolve_dataset(&symbol_dir, name)?.is_empty() {
+                finest = Some((seconds, name.to_string()));
+            }
+        }
+
+        finest
+            .map(|(_, timeframe)| timeframe)
+            .ok_or_else(|| AppError::new(format!("No cached timeframes for symbol '{}'", symbol)))
+    }
+
     /// Retrieves OHLC data for a specific symbol and timeframe
-    /// Reads the parquet file from the cache directory
+    /// Reads `{timeframe}.parquet` and/or the partitions under `{timeframe}/` as one series.
+    /// With a `range`, only bars inside it are returned and partitions whose name places
+    /// them outside the range are not read at all.
     pub fn retrieve_asset_ochl(
         symbol: &str,
         timeframe: &str,
+        range: Option<TimeRange>,
     ) -> Result<Vec<OHLCData>, AppError> {
+        let range = range.unwrap_or_default();
+        let files = Self::dataset_files(symbol, timeframe, &range)?;
+
+        // Read parquet files
+        let data = ParquetReaderService::read_ochl_range(&files, range.start, range.end)?;
+
+        CacheManagerService::record_access_best_effort(symbol, timeframe);
+
+        Ok(data)
+    }
+
+    /// Retrieves the OHLC bars of a symbol and timeframe inside any of `windows`
+    /// (`(start, end)` Unix seconds, inclusive, sorted and not overlapping)
+    /// Only the bars of the windows are kept in memory, so short windows spread over a
+    /// long history stay cheap
+    pub fn retrieve_asset_ochl_windows(
+        symbol: &str,
+        timeframe: &str,
+        windows: &[(i64, i64)],
+    ) -> Result<Vec<OHLCData>, AppError> {
+        let (Some(first), Some(last)) = (windows.first(), windows.last()) else {
+            return Ok(Vec::new());
+        };
+        let range = TimeRange {
+            start: Some(first.0),
+            end: Some(last.1),
+        };
+        let files = Self::dataset_files(symbol, timeframe, &range)?;
+        let data = ParquetReaderService::read_ochl_windows(&files, windows)?;
+
+        CacheManagerService::record_access_best_effort(symbol, timeframe);
+
+        Ok(data)
+    }
+
+    /// Retrieves one field of several symbols joined on `time`
+    /// Timestamps are the union of all symbols' bars; gaps are handled by `fill`
+    pub fn retrieve_aligned_series(
+        symbols: &[String],
+        timeframe: &str,
+        range: Option<TimeRange>,
+        field: OhlcField,
+        fill: FillPolicy,
+    ) -> Result<AlignedSeriesResult, AppError> {
+        if symbols.is_empty() {
+            return Err(AppError::new("At least one symbol is required"));
+        }
+
+        let range = range.unwrap_or_default();
+        let mut datasets: Vec<(String, Vec<PathBuf>)> = Vec::with_capacity(symbols.len());
+        for symbol in symbols {
+            let files = Self::dataset_files(symbol, timeframe, &range)?;
+            datasets.push((symbol.clone(), files));
+        }
+
+        let result = SeriesAlignmentService::align(&datasets, &range, field, fill)?;
+
+        for symbol in symbols {
+            CacheManagerService::record_access_best_effort(symbol, timeframe);
+        }
+
+        Ok(result)
+    }
+
+    /// Resolves the parquet files of a symbol timeframe that may hold bars in `range`
+    /// Fails when the symbol or the timeframe is not cached
+    pub fn dataset_files(
+        symbol: &str,
+        timeframe: &str,
+        range: &TimeRange,
+    ) -> Result<Vec<PathBuf>, AppError> {
         // Validate inputs
         validation::validate_non_empty(symbol, "symbol")?;
         validation::validate_non_empty(timeframe, "timeframe")?;
@@ -169,9 +460,14 @@ impl AssetService {
             return Err(AppError::new("Timeframe cannot contain path separators or dots"));
         }
 
-        // Construct file path: cache_dir/assets/symbol/timeframe.parquet
+        if let (Some(start), Some(end)) = (range.start, range.end) {
+            if start > end {
+                return Err(AppError::new("Range start must not be after range end"));
+            }
+        }
+
+        // Dataset location: cache_dir/assets/symbol/timeframe(.parquet | /partitions)
         let symbol_dir = cache_dir::get_symbol_cache_dir(symbol)?;
-        let file_path = symbol_dir.join(format!("{}.parquet", timeframe));
 
         // Check if symbol directory exists first
         if !symbol_dir.exists() {
@@ -181,8 +477,10 @@ impl AssetService {
             )));
         }
 
-        // Check if file exists
-        if !file_path.exists() {
+        let partitions = PartitionService::resolve_dataset(&symbol_dir, timeframe)?;
+
+        // Check if the dataset exists
+        if partitions.is_empty() {
             // List available files for debugging
             let mut available_files = Vec::new();
             if let Ok(entries) = fs::read_dir(&symbol_dir) {
@@ -194,8 +492,10 @@ impl AssetService {
             }
             
             return Err(AppError::new(format!(
-                "Parquet file not found: {}. Available files in {}: {}",
-                file_path.display(),
+                "No parquet data found for timeframe {} ({}.parquet or {}/). Available files in {}: {}",
+                timeframe,
+                timeframe,
+                timeframe,
                 symbol_dir.display(),
                 if available_files.is_empty() {
                     "none".to_string()
@@ -205,7 +505,314 @@ impl AssetService {
             )));
         }
 
-        // Read parquet file
-        ParquetReaderService::read_ochl_data(&file_path)
+        // Skip partitions outside the requested range
+        Ok(PartitionService::prune(partitions, range.start, range.end)
+            .into_iter()
+            .map(|partition| partition.path)
+            .collect())
+    }
+
+    /// Imports local OHLC history from a CSV file into the asset cache
+    /// Parses the file according to `format`, validates every row, normalizes
+    /// timestamps to UTC seconds and writes `{symbol}/{timeframe}.parquet`,
+    /// replacing any existing file for that timeframe
+    pub fn import_ohlc(
+        file_path: &str,
+        symbol: &str,
+        timeframe: &str,
+        format: &OhlcImportFormat,
+    ) -> Result<OhlcImportResult, AppError> {
+        // Validate inputs
+        validation::validate_non_empty(file_path, "file_path")?;
+        Self::validate_symbol(symbol)?;
+        // The file is only found by timeframe lookups if its name parses as a timeframe
+        timeframe::parse_timeframe_seconds(timeframe)?;
+
+        let source_path = Path::new(file_path);
+        if !source_path.is_file() {
+            return Err(AppError::new(format!(
+                "File does not exist: {}",
+                source_path.display()
+            )));
+        }
+
+        let parsed = OhlcImportService::parse_file(source_path, format)?;
+
+        if parsed.bars.is_empty() {
+            return Ok(OhlcImportResult {
+                success: false,
+                message: format!(
+                    "No valid bars found in {} ({} row(s) rejected)",
+                    source_path.display(),
+                    parsed.rows_rejected
+                ),
+                rows_imported: 0,
+                rows_rejected: parsed.rows_rejected,
+                warnings: parsed.warnings,
+                first_time: None,
+                last_time: None,
+            });
+        }
+
+        let symbol_dir = cache_dir::get_symbol_cache_dir(symbol)?;
+        cache_dir::ensure_cache_dir(&symbol_dir)?;
+        let output_path = symbol_dir.join(format!("{}.parquet", timeframe));
+
+        OhlcImportService::write_parquet(&parsed.bars, parsed.has_volume, &output_path)?;
+        CacheManagerService::record_access_best_effort(symbol, timeframe);
+        CacheManagerService::enforce_quota(&[symbol])?;
+
+        Ok(OhlcImportResult {
+            success: true,
+            message: format!(
+                "Imported {} bar(s) for {} {} to {}",
+                parsed.bars.len(),
+                symbol,
+                timeframe,
+                output_path.display()
+            ),
+            rows_imported: parsed.bars.len(),
+            rows_rejected: parsed.rows_rejected,
+            warnings: parsed.warnings,
+            first_time: parsed.bars.first().map(|b| b.time),
+            last_time: parsed.bars.last().map(|b| b.time),
+        })
+    }
+
+    /// Builds OHLC bars from the cached tick files of a symbol
+    /// Any timeframe accepted by `timeframe::parse_timeframe_seconds` is supported,
+    /// including second-level bars (e.g. "10S")
+    pub fn build_bars_from_ticks(
+        symbol: &str,
+        timeframe: &str,
+        price_source: TickPriceSource,
+        start_msc: Option<i64>,
+        end_msc: Option<i64>,
+    ) -> Result<Vec<OHLCData>, AppError> {
+        Self::validate_symbol(symbol)?;
+        let timeframe_seconds = timeframe::parse_timeframe_seconds(timeframe)?;
+
+        let sources = Self::tick_sources(symbol)?;
+        let bars = TickReaderService::build_bars(&sources, timeframe_seconds, price_source, start_msc, end_msc)?;
+        CacheManagerService::record_access_best_effort(symbol, "ticks");
+
+        Ok(bars)
+    }
+
+    /// Retrieves the ticks of a symbol in a window around `time_msc`
+    /// (e.g. a deal's execution time) to check fill plausibility
+    pub fn retrieve_ticks_around(
+        symbol: &str,
+        time_msc: i64,
+        before_ms: i64,
+        after_ms: i64,
+    ) -> Result<Vec<TickData>, AppError> {
+        Self::validate_symbol(symbol)?;
+        if before_ms < 0 || after_ms < 0 {
+            return Err(AppError::new("Tick window bounds cannot be negative"));
+        }
+
+        let sources = Self::tick_sources(symbol)?;
+        let ticks = TickReaderService::read_ticks(
+            &sources,
+            Some(time_msc.saturating_sub(before_ms)),
+            Some(time_msc.saturating_add(after_ms)),
+        )?;
+        CacheManagerService::record_access_best_effort(symbol, "ticks");
+
+        Ok(ticks)
+    }
+
+    /// Reports disk usage of the asset cache per symbol and timeframe
+    pub fn get_cache_usage() -> Result<CacheUsageReport, AppError> {
+        CacheManagerService::usage_report()
+    }
+
+    /// Deletes all cached data of a symbol
+    pub fn delete_symbol(symbol: &str) -> Result<AssetOperationResult, AppError> {
+        Self::validate_symbol(symbol)?;
+
+        let freed = CacheManagerService::delete_symbol(symbol)?;
+
+        Ok(AssetOperationResult::success(format!(
+            "Deleted {} ({} bytes freed)",
+            symbol, freed
+        )))
+    }
+
+    /// Deletes a single timeframe of a symbol
+    pub fn delete_timeframe(symbol: &str, timeframe: &str) -> Result<AssetOperationResult, AppError> {
+        Self::validate_symbol(symbol)?;
+        validation::validate_non_empty(timeframe, "timeframe")?;
+
+        // Validate timeframe format (no path separators, no extension)
+        if timeframe.contains('/') || timeframe.contains('\\') || timeframe.contains('.') {
+            return Err(AppError::new("Timeframe cannot contain path separators or dots"));
+        }
+
+        let freed = CacheManagerService::delete_timeframe(symbol, timeframe)?;
+
+        Ok(AssetOperationResult::success(format!(
+            "Deleted {} {} ({} bytes freed)",
+            symbol, timeframe, freed
+        )))
+    }
+
+    /// Sets (or clears, with `None`) the asset cache quota and evicts data if needed
+    pub fn set_cache_quota(quota_bytes: Option<u64>) -> Result<CacheEvictionResult, AppError> {
+        CacheManagerService::set_quota(quota_bytes)
+    }
+
+    /// Pins or unpins a symbol so it is protected from quota eviction
+    pub fn set_symbol_pinned(symbol: &str, pinned: bool) -> Result<AssetOperationResult, AppError> {
+        Self::validate_symbol(symbol)?;
+
+        CacheManagerService::set_pinned(symbol, pinned)?;
+
+        Ok(AssetOperationResult::success(if pinned {
+            format!("Pinned {}", symbol)
+        } else {
+            format!("Unpinned {}", symbol)
+        }))
+    }
+
+    /// Evicts least recently used data until the cache fits in the quota
+    pub fn enforce_cache_quota() -> Result<CacheEvictionResult, AppError> {
+        CacheManagerService::enforce_quota(&[])
+    }
+
+    /// Rewrites cached parquet files sorted by time with zstd compression,
+    /// statistics and small row groups, for one symbol or the whole cache
+    /// Files already optimized (per the manifest) are skipped unless `force` is set
+    pub fn optimize_assets(
+        symbol: Option<&str>,
+        force: bool,
+    ) -> Result<AssetOptimizationResult, AppError> {
+        let symbol_dirs = match symbol {
+            Some(symbol) => {
+                Self::validate_symbol(symbol)?;
+                let symbol_dir = cache_dir::get_symbol_cache_dir(symbol)?;
+                if !symbol_dir.exists() {
+                    return Err(AppError::new(format!(
+                        "Symbol directory not found: {}",
+                        symbol_dir.display()
+                    )));
+                }
+                vec![symbol_dir]
+            }
+            None => {
+                let asset_dir = cache_dir::get_asset_cache_dir()?;
+                if !asset_dir.exists() {
+                    return Ok(AssetOptimizationResult {
+                        success: true,
+                        message: "Asset cache is empty".to_string(),
+                        optimized_files: 0,
+                        skipped_files: 0,
+                        bytes_before: 0,
+                        bytes_after: 0,
+                        errors: Vec::new(),
+                    });
+                }
+                Self::symbol_dirs(&asset_dir)?
+            }
+        };
+
+        ParquetOptimizerService::optimize_symbols(&symbol_dirs, force)
+    }
+
+    /// Files a post-download optimization could not rewrite, as a sentence appended to the
+    /// operation message (empty when every file was optimized); the data is usable either way
+    fn optimization_errors(optimization: Option<&AssetOptimizationResult>) -> String {
+        const MAX_LISTED_ERRORS: usize = 5;
+
+        let Some(errors) = optimization.map(|result| &result.errors).filter(|errors| !errors.is_empty()) else {
+            return String::new();
+        };
+        let mut listed = errors.iter().take(MAX_LISTED_ERRORS).cloned().collect::<Vec<_>>().join("; ");
+        if errors.len() > MAX_LISTED_ERRORS {
+            listed.push_str(&format!("; and {} more", errors.len() - MAX_LISTED_ERRORS));
+        }
+        format!(". {} file(s) could not be optimized: {}", errors.len(), listed)
+    }
+
+    /// Message suffix when the cache is larger than its quota
+    fn quota_overage(total_bytes: u64, quota_bytes: Option<u64>) -> String {
+        match quota_bytes {
+            Some(quota) if total_bytes > quota => format!(
+                ". The cache uses {} bytes, over its quota of {} bytes; raise the quota or delete symbols",
+                total_bytes, quota
+            ),
+            _ => String::new(),
+        }
+    }
+
+    /// Lists the symbol folders of the asset cache
+    fn symbol_dirs(asset_dir: &Path) -> Result<Vec<PathBuf>, AppError> {
+        let entries = fs::read_dir(asset_dir)
+            .map_err(|e| AppError::new(format!("Failed to read cache directory: {}", e)))?;
+
+        let mut dirs = Vec::new();
+        for entry in entries {
+            let entry = entry
+                .map_err(|e| AppError::new(format!("Failed to read directory entry: {}", e)))?;
+            if entry.path().is_dir() {
+                dirs.push(entry.path());
+            }
+        }
+        dirs.sort();
+
+        Ok(dirs)
+    }
+
+    /// Lists the tick files of a symbol, failing when there are none
+    fn tick_sources(symbol: &str) -> Result<Vec<PathBuf>, AppError> {
+        let symbol_dir = cache_dir::get_symbol_cache_dir(symbol)?;
+        if !symbol_dir.exists() {
+            return Err(AppError::new(format!(
+                "Symbol directory not found: {}",
+                symbol_dir.display()
+            )));
+        }
+
+        let sources = TickReaderService::find_tick_sources(&symbol_dir)?;
+        if sources.is_empty() {
+            return Err(AppError::new(format!(
+                "No tick data found for {}. Expected ticks.parquet, ticks.csv or a ticks/ folder in {}",
+                symbol,
+                symbol_dir.display()
+            )));
+        }
+
+        Ok(sources)
+    }
+
+    /// Path of a remote repository file relative to its symbol folder
+    /// Rejects paths that would escape the symbol folder
+    fn remote_relative_path(symbol: &str, remote_path: &str) -> Result<String, AppError> {
+        let relative = remote_path
+            .strip_prefix(&format!("{}/", symbol))
+            .ok_or_else(|| AppError::new(format!("Invalid file path: {}", remote_path)))?;
+
+        let is_safe = !relative.is_empty()
+            && Path::new(relative)
+                .components()
+                .all(|c| matches!(c, Component::Normal(_)));
+        if !is_safe {
+            return Err(AppError::new(format!("Invalid file path: {}", remote_path)));
+        }
+
+        Ok(relative.to_string())
+    }
+
+    /// Validates a symbol name used as a cache folder name
+    fn validate_symbol(symbol: &str) -> Result<(), AppError> {
+        validation::validate_non_empty(symbol, "symbol")?;
+
+        // Validate symbol format (no path separators)
+        if symbol.contains('/') || symbol.contains('\\') || symbol == "." || symbol == ".." {
+            return Err(AppError::new("Symbol cannot contain path separators"));
+        }
+
+        Ok(())
     }
 }
@@ -0,0 +1,252 @@
+//! Tick data reading and bar building using Polars
+//!
+//! Tick files live next to the bar files of a symbol in the asset cache:
+//! `{symbol}/ticks.parquet`, `{symbol}/ticks.csv` or any parquet/CSV file in `{symbol}/ticks/`.
+//! Each file must provide `time_msc`, `bid` and `ask` columns; `volume` is optional.
+
+use crate::shared::error::AppError;
+use crate::shared::utils::timeframe;
+use crate::features::asset::model::{OHLCData, TickData, TickPriceSource};
+use polars::prelude::*;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Service for reading tick files and aggregating them into bars
+pub struct TickReaderService;
+
+impl TickReaderService {
+    /// Finds all tick files for a symbol directory, sorted by path
+    pub fn find_tick_sources(symbol_dir: &Path) -> Result<Vec<PathBuf>, AppError> {
+        let mut sources = Vec::new();
+
+        for name in ["ticks.parquet", "ticks.csv"] {
+            let path = symbol_dir.join(name);
+            if path.is_file() {
+                sources.push(path);
+            }
+        }
+
+        let ticks_dir = symbol_dir.join("ticks");
+        if ticks_dir.is_dir() {
+            let entries = fs::read_dir(&ticks_dir)
+                .map_err(|e| AppError::new(format!("Failed to read ticks directory: {}", e)))?;
+            for entry in entries {
+                let entry = entry
+                    .map_err(|e| AppError::new(format!("Failed to read directory entry: {}", e)))?;
+                let path = entry.path();
+                let extension = path.extension().and_then(|s| s.to_str());
+                if path.is_file() && matches!(extension, Some("parquet") | Some("csv")) {
+                    sources.push(path);
+                }
+            }
+        }
+
+        sources.sort();
+        Ok(sources)
+    }
+
+    /// Reads ticks in the `[start_msc, end_msc]` window, sorted by time
+    pub fn read_ticks(
+        sources: &[PathBuf],
+        start_msc: Option<i64>,
+        end_msc: Option<i64>,
+    ) -> Result<Vec<TickData>, AppError> {
+        let df = Self::scan_ticks(sources, start_msc, end_msc)?
+            .collect()
+            .map_err(|e| AppError::new(format!("Failed to collect tick data: {}", e)))?;
+
+        let time_col = df
+            .column("time_msc")
+            .map_err(|e| AppError::new(format!("Failed to get 'time_msc' column: {}", e)))?
+            .i64()
+            .map_err(|e| AppError::new(format!("Failed to cast 'time_msc' column to i64: {}", e)))?;
+        let bid_col = df
+            .column("bid")
+            .map_err(|e| AppError::new(format!("Failed to get 'bid' column: {}", e)))?
+            .f64()
+            .map_err(|e| AppError::new(format!("Failed to cast 'bid' column to f64: {}", e)))?;
+        let ask_col = df
+            .column("ask")
+            .map_err(|e| AppError::new(format!("Failed to get 'ask' column: {}", e)))?
+            .f64()
+            .map_err(|e| AppError::new(format!("Failed to cast 'ask' column to f64: {}", e)))?;
+        let volume_col = df
+            .column("volume")
+            .map_err(|e| AppError::new(format!("Failed to get 'volume' column: {}", e)))?
+            .f64()
+            .map_err(|e| AppError::new(format!("Failed to cast 'volume' column to f64: {}", e)))?;
+
+        let num_rows = df.height();
+        let mut ticks = Vec::with_capacity(num_rows);
+
+        for i in 0..num_rows {
+            ticks.push(TickData {
+                time_msc: time_col.get(i).unwrap_or(0),
+                bid: bid_col.get(i).unwrap_or(0.0),
+                ask: ask_col.get(i).unwrap_or(0.0),
+                volume: volume_col.get(i).unwrap_or(0.0),
+            });
+        }
+
+        Ok(ticks)
+    }
+
+    /// Aggregates ticks into OHLC bars of `timeframe_seconds` length
+    /// Bars are stamped with the start of their interval (Unix seconds); empty intervals are skipped
+    pub fn build_bars(
+        sources: &[PathBuf],
+        timeframe_seconds: i64,
+        price_source: TickPriceSource,
+        start_msc: Option<i64>,
+        end_msc: Option<i64>,
+    ) -> Result<Vec<OHLCData>, AppError> {
+        let price = match price_source {
+            TickPriceSource::Bid => col("bid"),
+            TickPriceSource::Ask => col("ask"),
+            TickPriceSource::Mid => (col("bid") + col("ask")) / lit(2.0),
+        };
+        let offset = timeframe::bucket_alignment_offset(timeframe_seconds);
+        let bucket = (col("time_msc").floor_div(lit(1000i64)) - lit(offset))
+            .floor_div(lit(timeframe_seconds))
+            * lit(timeframe_seconds)
+            + lit(offset);
+
+        let df = Self::scan_ticks(sources, start_msc, end_msc)?
+            .with_columns([price.alias("price"), bucket.alias("time")])
+            .group_by_stable([col("time")])
+            .agg([
+                col("price").first().alias("open"),
+                col("price").max().alias("high"),
+                col("price").min().alias("low"),
+                col("price").last().alias("close"),
+            ])
+            .sort(["time"], SortMultipleOptions::default())
+            .collect()
+            .map_err(|e| AppError::new(format!("Failed to build bars from ticks: {}", e)))?;
+
+        let time_col = df
+            .column("time")
+            .map_err(|e| AppError::new(format!("Failed to get 'time' column: {}", e)))?
+            .i64()
+            .map_err(|e| AppError::new(format!("Failed to cast 'time' column to i64: {}", e)))?;
+        let open_col = df
+            .column("open")
+            .map_err(|e| AppError::new(format!("Failed to get 'open' column: {}", e)))?
+            .f64()
+            .map_err(|e| AppError::new(format!("Failed to cast 'open' column to f64: {}", e)))?;
+        let high_col = df
+            .column("high")
+            .map_err(|e| AppError::new(format!("Failed to get 'high' column: {}", e)))?
+            .f64()
+            .map_err(|e| AppError::new(format!("Failed to cast 'high' column to f64: {}", e)))?;
+        let low_col = df
+            .column("low")
+            .map_err(|e| AppError::new(format!("Failed to get 'low' column: {}", e)))?
+            .f64()
+            .map_err(|e| AppError::new(format!("Failed to cast 'low' column to f64: {}", e)))?;
+        let close_col = df
+            .column("close")
+            .map_err(|e| AppError::new(format!("Failed to get 'close' column: {}", e)))?
+            .f64()
+            .map_err(|e| AppError::new(format!("Failed to cast 'close' column to f64: {}", e)))?;
+
+        let num_rows = df.height();
+        let mut bars = Vec::with_capacity(num_rows);
+
+        for i in 0..num_rows {
+            bars.push(OHLCData {
+                time: time_col.get(i).unwrap_or(0),
+                open: open_col.get(i).unwrap_or(0.0),
+                high: high_col.get(i).unwrap_or(0.0),
+                low: low_col.get(i).unwrap_or(0.0),
+                close: close_col.get(i).unwrap_or(0.0),
+            });
+        }
+
+        Ok(bars)
+    }
+
+    /// Builds a lazy query over all tick sources with normalized column types,
+    /// filtered to the requested window and sorted by `time_msc`
+    fn scan_ticks(
+        sources: &[PathBuf],
+        start_msc: Option<i64>,
+        end_msc: Option<i64>,
+    ) -> Result<LazyFrame, AppError> {
+        if sources.is_empty() {
+            return Err(AppError::new("No tick files found"));
+        }
+
+        let mut frames = Vec::with_capacity(sources.len());
+        for path in sources {
+            let frame = Self::scan_source(path)?;
+            let schema = frame
+                .schema()
+                .map_err(|e| AppError::new(format!("Failed to read schema of {}: {}", path.display(), e)))?;
+
+            for required in ["time_msc", "bid", "ask"] {
+                if !schema.contains(required) {
+                    return Err(AppError::new(format!(
+                        "Tick file {} is missing required column: {}",
+                        path.display(),
+                        required
+                    )));
+                }
+            }
+
+            let volume = if schema.contains("volume") {
+                col("volume").cast(DataType::Float64)
+            } else {
+                lit(0.0)
+            };
+
+            frames.push(frame.select([
+                col("time_msc").cast(DataType::Int64),
+                col("bid").cast(DataType::Float64),
+                col("ask").cast(DataType::Float64),
+                volume.alias("volume"),
+            ]));
+        }
+
+        let mut ticks = concat(frames, UnionArgs::default())
+            .map_err(|e| AppError::new(format!("Failed to combine tick files: {}", e)))?;
+
+        if let Some(start_msc) = start_msc {
+            ticks = ticks.filter(col("time_msc").gt_eq(lit(start_msc)));
+        }
+        if let Some(end_msc) = end_msc {
+            ticks = ticks.filter(col("time_msc").lt_eq(lit(end_msc)));
+        }
+
+        Ok(ticks.sort(["time_msc"], SortMultipleOptions::default().with_maintain_order(true)))
+    }
+
+    /// Creates a lazy scan for a single parquet or CSV tick file
+    fn scan_source(path: &Path) -> Result<LazyFrame, AppError> {
+        let is_csv = path.extension().and_then(|s| s.to_str()) == Some("csv");
+
+        if is_csv {
+            LazyCsvReader::new(path)
+                .with_has_header(true)
+                .finish()
+                .map_err(|e| AppError::new(format!("Failed to scan CSV file {}: {}", path.display(), e)))
+        } else {
+            LazyFrame::scan_parquet(
+                path,
+                ScanArgsParquet {
+                    n_rows: None,
+                    cache: true,
+                    parallel: ParallelStrategy::Auto,
+                    rechunk: false,
+                    row_index: None,
+                    low_memory: false,
+                    cloud_options: None,
+                    use_statistics: true,
+                    hive_options: Default::default(),
+                    glob: false,
+                },
+            )
+            .map_err(|e| AppError::new(format!("Failed to scan parquet file {}: {}", path.display(), e)))
+        }
+    }
+}
@@ -0,0 +1,18 @@
+//! Backtest command handlers
+
+use crate::features::backtest::model::{BacktestRequest, BacktestResult};
+use crate::features::backtest::service::BacktestService;
+use crate::shared::error::AppError;
+
+/// Tauri command: run_backtest
+/// Runs a declarative strategy over cached OHLC data and stores the synthetic deals as an account
+/// 
+/// # Arguments
+/// * `request` - Symbol, timeframe, optional range, strategy rules, balance and contract settings
+/// 
+/// Returns a summary of the run and the synthetic deals; the account can be passed to deals.* insights
+#[tauri::command]
+pub fn run_backtest(request: BacktestRequest) -> Result<BacktestResult, String> {
+    BacktestService::run_backtest(&request)
+        .map_err(AppError::into_command_error)
+}
@@ -0,0 +1,559 @@
+//! Bar-by-bar backtest simulation producing synthetic deals
+//!
+//! Conditions are evaluated on the close of a bar and filled on the open of the next bar.
+//! Stop-loss and take-profit are checked against the bar's high/low; when both are touched
+//! in the same bar the stop-loss is assumed to fill first.
+
+use crate::features::asset::model::OHLCData;
+use crate::features::backtest::model::{
+    BacktestRequest, Condition, ExitLevel, Operand, PositionSizing,
+};
+use crate::features::deals::model::{
+    Deal, DEAL_ENTRY_IN, DEAL_ENTRY_OUT, DEAL_REASON_EXPERT, DEAL_REASON_SL, DEAL_REASON_TP,
+    DEAL_TYPE_BALANCE, DEAL_TYPE_BUY, DEAL_TYPE_SELL,
+};
+use crate::features::indicators::model::{IndicatorResult, PriceSource};
+use crate::shared::error::AppError;
+use std::collections::HashMap;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Side {
+    Long,
+    Short,
+}
+
+/// Operand bound to its data
+enum Resolved<'a> {
+    Price(PriceSource),
+    Series(&'a [Option<f64>]),
+    Value(f64),
+}
+
+struct ResolvedCondition<'a> {
+    condition: &'a Condition,
+    left: Resolved<'a>,
+    right: Resolved<'a>,
+}
+
+struct OpenPosition {
+    side: Side,
+    ticket: i64,
+    volume: f64,
+    price: f64,
+    stop_loss: Option<f64>,
+    take_profit: Option<f64>,
+}
+
+/// Indicator id -> (line name, values) pairs
+type IndicatorLines<'a> = HashMap<&'a str, Vec<(&'a str, &'a [Option<f64>])>>;
+
+/// Order decided on a bar close, filled on the next open
+#[derive(Clone, Copy)]
+struct PendingOrder {
+    close: bool,
+    open: Option<Side>,
+    /// Bar whose values size the stop-loss/take-profit of the new position
+    signal_bar: usize,
+}
+
+/// Runs a strategy over a bar series
+pub struct BacktestEngine<'a> {
+    request: &'a BacktestRequest,
+    bars: &'a [OHLCData],
+    lines: IndicatorLines<'a>,
+    deals: Vec<Deal>,
+    balance: f64,
+    next_ticket: i64,
+}
+
+impl<'a> BacktestEngine<'a> {
+    /// Indicator id under which the service requests the ATR of an ATR-based exit level
+    pub fn exit_atr_id(period: usize) -> String {
+        format!("exit_atr_{}", period)
+    }
+
+    /// ATR periods referenced by the strategy's exit levels
+    pub fn exit_atr_periods(request: &BacktestRequest) -> Vec<usize> {
+        let mut periods: Vec<usize> = [&request.strategy.stop_loss, &request.strategy.take_profit]
+            .into_iter()
+            .filter_map(|level| match level {
+                Some(ExitLevel::Atr { period, .. }) => Some(*period),
+                _ => None,
+            })
+            .collect();
+        periods.sort_unstable();
+        periods.dedup();
+        periods
+    }
+
+    /// Checks the request for values the simulation cannot handle
+    pub fn validate(request: &BacktestRequest) -> Result<(), AppError> {
+        let strategy = &request.strategy;
+
+        if !(request.initial_balance.is_finite() && request.initial_balance > 0.0) {
+            return Err(AppError::new("Initial balance must be a positive number"));
+        }
+        if !(request.contract_size.is_finite() && request.contract_size > 0.0) {
+            return Err(AppError::new("Contract size must be a positive number"));
+        }
+        if !(request.volume_step.is_finite() && request.volume_step > 0.0) {
+            return Err(AppError::new("Volume step must be a positive number"));
+        }
+        if strategy.long_entry.is_empty() && strategy.short_entry.is_empty() {
+            return Err(AppError::new("Strategy needs at least one long or short entry condition"));
+        }
+
+        match strategy.sizing {
+            PositionSizing::FixedVolume { volume } if !(volume.is_finite() && volume > 0.0) => {
+                return Err(AppError::new("Fixed volume must be a positive number"));
+            }
+            PositionSizing::RiskPercent { percent } => {
+                if !(percent.is_finite() && percent > 0.0 && percent <= 100.0) {
+                    return Err(AppError::new("Risk percent must be between 0 and 100"));
+                }
+                if strategy.stop_loss.is_none() {
+                    return Err(AppError::new("Risk-based sizing requires a stop-loss"));
+                }
+            }
+            _ => {}
+        }
+
+        for level in [&strategy.stop_loss, &strategy.take_profit].into_iter().flatten() {
+            let valid = match level {
+                ExitLevel::Price { distance } => distance.is_finite() && *distance > 0.0,
+                ExitLevel::Percent { percent } => percent.is_finite() && *percent > 0.0,
+                ExitLevel::Atr { period, multiplier } => {
+                    *period > 0 && multiplier.is_finite() && *multiplier > 0.0
+                }
+            };
+            if !valid {
+                return Err(AppError::new("Stop-loss and take-profit distances must be positive"));
+            }
+        }
+
+        let costs = strategy.costs;
+        let non_negative = |value: f64| value.is_finite() && value >= 0.0;
+        if !non_negative(costs.spread) || !non_negative(costs.commission_per_lot) {
+            return Err(AppError::new("Spread and commission cannot be negative"));
+        }
+
+        Ok(())
+    }
+
+    /// Creates an engine over `bars`; `indicators` must be aligned with the bars
+    pub fn new(
+        request: &'a BacktestRequest,
+        bars: &'a [OHLCData],
+        indicators: &'a IndicatorResult,
+    ) -> Result<Self, AppError> {
+        if indicators.time.len() != bars.len() {
+            return Err(AppError::new("Indicator values are not aligned with the bars"));
+        }
+
+        let mut lines = IndicatorLines::new();
+        for indicator in &indicators.indicators {
+            lines.insert(
+                indicator.id.as_str(),
+                indicator
+                    .lines
+                    .iter()
+                    .map(|line| (line.name.as_str(), line.values.as_slice()))
+                    .collect(),
+            );
+        }
+
+        Ok(Self {
+            request,
+            bars,
+            lines,
+            deals: Vec::new(),
+            balance: request.initial_balance,
+            next_ticket: 1,
+        })
+    }
+
+    /// Simulates the strategy and returns the synthetic deals, starting with the initial balance deal
+    pub fn run(mut self) -> Result<Vec<Deal>, AppError> {
+        let request = self.request;
+        let bars = self.bars;
+        let strategy = &request.strategy;
+        let long_entry = self.resolve_all(&strategy.long_entry)?;
+        let long_exit = self.resolve_all(&strategy.long_exit)?;
+        let short_entry = self.resolve_all(&strategy.short_entry)?;
+        let short_exit = self.resolve_all(&strategy.short_exit)?;
+
+        let Some(first_bar) = bars.first() else {
+            return Ok(self.deals);
+        };
+        self.push_balance_deal(first_bar.time);
+
+        let mut position: Option<OpenPosition> = None;
+        let mut pending: Option<PendingOrder> = None;
+
+        for (i, bar) in bars.iter().enumerate() {
+
+            if let Some(order) = pending.take() {
+                if order.close {
+                    if let Some(open) = position.take() {
+                        let price = self.exit_price(open.side, bar.open);
+                        let comment = if order.open.is_some() { "reverse" } else { "exit signal" };
+                        self.close_position(&open, bar.time, price, DEAL_REASON_EXPERT, comment);
+                    }
+                }
+                if let Some(side) = order.open {
+                    position = self.open_position(side, bar.time, bar.open, order.signal_bar);
+                }
+            }
+
+            if let Some(open) = position.take() {
+                match self.protective_fill(&open, bar) {
+                    Some((price, reason, comment)) => {
+                        self.close_position(&open, bar.time, price, reason, comment);
+                    }
+                    None => position = Some(open),
+                }
+            }
+
+            // Signals on the last bar have no next open to fill on
+            if i + 1 == bars.len() {
+                break;
+            }
+
+            let wants_long = Self::all_hold(&long_entry, i, bars);
+            let wants_short = Self::all_hold(&short_entry, i, bars);
+
+            pending = match position.as_ref().map(|p| p.side) {
+                None => match (wants_long, wants_short) {
+                    (true, false) => Some(PendingOrder { close: false, open: Some(Side::Long), signal_bar: i }),
+                    (false, true) => Some(PendingOrder { close: false, open: Some(Side::Short), signal_bar: i }),
+                    // Conflicting or no signals
+                    _ => None,
+                },
+                Some(Side::Long) => {
+                    if wants_short {
+                        Some(PendingOrder { close: true, open: Some(Side::Short), signal_bar: i })
+                    } else if Self::all_hold(&long_exit, i, bars) {
+                        Some(PendingOrder { close: true, open: None, signal_bar: i })
+                    } else {
+                        None
+                    }
+                }
+                Some(Side::Short) => {
+                    if wants_long {
+                        Some(PendingOrder { close: true, open: Some(Side::Long), signal_bar: i })
+                    } else if Self::all_hold(&short_exit, i, bars) {
+                        Some(PendingOrder { close: true, open: None, signal_bar: i })
+                    } else {
+                        None
+                    }
+                }
+            };
+        }
+
+        if let (Some(open), Some(last)) = (position.take(), bars.last()) {
+            let price = self.exit_price(open.side, last.close);
+            self.close_position(&open, last.time, price, DEAL_REASON_EXPERT, "end of test");
+        }
+
+        Ok(self.deals)
+    }
+
+    /// Binds the operands of a condition list to their data
+    fn resolve_all(&self, conditions: &'a [Condition]) -> Result<Vec<ResolvedCondition<'a>>, AppError> {
+        conditions
+            .iter()
+            .map(|condition| {
+                let (left, right) = match condition {
+                    Condition::CrossesAbove { left, right }
+                    | Condition::CrossesBelow { left, right }
+                    | Condition::Above { left, right }
+                    | Condition::Below { left, right } => (left, right),
+                };
+                Ok(ResolvedCondition {
+                    condition,
+                    left: self.resolve(left)?,
+                    right: self.resolve(right)?,
+                })
+            })
+            .collect()
+    }
+
+    fn resolve(&self, operand: &'a Operand) -> Result<Resolved<'a>, AppError> {
+        match operand {
+            Operand::Price { source } => Ok(Resolved::Price(*source)),
+            Operand::Value { value } => Ok(Resolved::Value(*value)),
+            Operand::Indicator { id, line } => Ok(Resolved::Series(self.series(id, line.as_deref())?)),
+        }
+    }
+
+    /// Values of an indicator line (the first line when `line` is None)
+    fn series(&self, id: &str, line: Option<&str>) -> Result<&'a [Option<f64>], AppError> {
+        let lines = self
+            .lines
+            .get(id)
+            .ok_or_else(|| AppError::new(format!("Unknown indicator '{}' in strategy rules", id)))?;
+
+        let found = match line {
+            Some(name) => lines.iter().find(|(line_name, _)| *line_name == name),
+            None => lines.first(),
+        };
+
+        found.map(|(_, values)| *values).ok_or_else(|| {
+            AppError::new(format!(
+                "Indicator '{}' has no line '{}'",
+                id,
+                line.unwrap_or_default()
+            ))
+        })
+    }
+
+    fn all_hold(conditions: &[ResolvedCondition], index: usize, bars: &[OHLCData]) -> bool {
+        !conditions.is_empty()
+            && conditions
+                .iter()
+                .all(|condition| Self::holds(condition, index, bars))
+    }
+
+    fn holds(condition: &ResolvedCondition, index: usize, bars: &[OHLCData]) -> bool {
+        let value = |operand: &Resolved, i: usize| -> Option<f64> {
+            match operand {
+                Resolved::Price(source) => Some(Self::price(source, &bars[i])),
+                Resolved::Series(values) => values[i],
+                Resolved::Value(value) => Some(*value),
+            }
+        };
+        let (Some(left), Some(right)) = (value(&condition.left, index), value(&condition.right, index)) else {
+            return false;
+        };
+
+        match condition.condition {
+            Condition::Above { .. } => left > right,
+            Condition::Below { .. } => left < right,
+            Condition::CrossesAbove { .. } | Condition::CrossesBelow { .. } => {
+                let Some(previous) = index.checked_sub(1) else {
+                    return false;
+                };
+                let (Some(prev_left), Some(prev_right)) =
+                    (value(&condition.left, previous), value(&condition.right, previous))
+                else {
+                    return false;
+                };
+                match condition.condition {
+                    Condition::CrossesAbove { .. } => prev_left <= prev_right && left > right,
+                    _ => prev_left >= prev_right && left < right,
+                }
+            }
+        }
+    }
+
+    fn price(source: &PriceSource, bar: &OHLCData) -> f64 {
+        match source {
+            PriceSource::Open => bar.open,
+            PriceSource::High => bar.high,
+            PriceSource::Low => bar.low,
+            PriceSource::Close => bar.close,
+            PriceSource::Hl2 => (bar.high + bar.low) / 2.0,
+            PriceSource::Hlc3 => (bar.high + bar.low + bar.close) / 3.0,
+            PriceSource::Ohlc4 => (bar.open + bar.high + bar.low + bar.close) / 4.0,
+        }
+    }
+
+    /// Opens a position at the bar open; None when the position cannot be sized
+    fn open_position(&mut self, side: Side, time: i64, bid: f64, signal_bar: usize) -> Option<OpenPosition> {
+        let spread = self.request.strategy.costs.spread;
+        let price = match side {
+            Side::Long => bid + spread,
+            Side::Short => bid,
+        };
+
+        let stop_distance = self.exit_distance(self.request.strategy.stop_loss.as_ref(), price, signal_bar);
+        let take_distance = self.exit_distance(self.request.strategy.take_profit.as_ref(), price, signal_bar);
+
+        let volume = match self.request.strategy.sizing {
+            PositionSizing::FixedVolume { volume } => volume,
+            PositionSizing::RiskPercent { percent } => {
+                let distance = stop_distance?;
+                let risk = self.balance * percent / 100.0;
+                let step = self.request.volume_step;
+                // Round to whole steps; the epsilon keeps exact multiples from flooring one step down
+                let steps = (risk / (distance * self.request.contract_size) / step + 1e-9).floor();
+                (steps * step * 1e8).round() / 1e8
+            }
+        };
+        if !volume.is_finite() || volume <= 0.0 {
+            return None;
+        }
+
+        let direction = match side {
+            Side::Long => 1.0,
+            Side::Short => -1.0,
+        };
+
+        let ticket = self.push_trade_deal(
+            side,
+            DEAL_ENTRY_IN,
+            time,
+            volume,
+            price,
+            0.0,
+            None,
+            DEAL_REASON_EXPERT,
+            "entry",
+        );
+
+        Some(OpenPosition {
+            side,
+            ticket,
+            volume,
+            price,
+            stop_loss: stop_distance.map(|distance| price - direction * distance),
+            take_profit: take_distance.map(|distance| price + direction * distance),
+        })
+    }
+
+    /// Distance in price units of a stop-loss/take-profit level (None when unset or not computable)
+    fn exit_distance(&self, level: Option<&ExitLevel>, entry_price: f64, signal_bar: usize) -> Option<f64> {
+        let distance = match level? {
+            ExitLevel::Price { distance } => *distance,
+            ExitLevel::Percent { percent } => entry_price * percent / 100.0,
+            ExitLevel::Atr { period, multiplier } => {
+                let atr = self.series(&Self::exit_atr_id(*period), None).ok()?[signal_bar]?;
+                atr * multiplier
+            }
+        };
+        (distance.is_finite() && distance > 0.0).then_some(distance)
+    }
+
+    /// Fill of the stop-loss or take-profit inside a bar, if touched
+    /// Gaps through a level fill at the bar open
+    fn protective_fill(&self, position: &OpenPosition, bar: &OHLCData) -> Option<(f64, i64, &'static str)> {
+        let spread = self.request.strategy.costs.spread;
+
+        match position.side {
+            // Longs exit on the bid
+            Side::Long => {
+                if let Some(stop) = position.stop_loss.filter(|stop| bar.low <= *stop) {
+                    return Some((bar.open.min(stop), DEAL_REASON_SL, "sl"));
+                }
+                if let Some(target) = position.take_profit.filter(|target| bar.high >= *target) {
+                    return Some((bar.open.max(target), DEAL_REASON_TP, "tp"));
+                }
+            }
+            // Shorts exit on the ask
+            Side::Short => {
+                if let Some(stop) = position.stop_loss.filter(|stop| bar.high + spread >= *stop) {
+                    return Some(((bar.open + spread).max(stop), DEAL_REASON_SL, "sl"));
+                }
+                if let Some(target) = position.take_profit.filter(|target| bar.low + spread <= *target) {
+                    return Some(((bar.open + spread).min(target), DEAL_REASON_TP, "tp"));
+                }
+            }
+        }
+
+        None
+    }
+
+    fn exit_price(&self, side: Side, bid: f64) -> f64 {
+        match side {
+            Side::Long => bid,
+            Side::Short => bid + self.request.strategy.costs.spread,
+        }
+    }
+
+    fn close_position(&mut self, position: &OpenPosition, time: i64, price: f64, reason: i64, comment: &str) {
+        let direction = match position.side {
+            Side::Long => 1.0,
+            Side::Short => -1.0,
+        };
+        let profit = (price - position.price) * direction * position.volume * self.request.contract_size;
+
+        let exit_side = match position.side {
+            Side::Long => Side::Short,
+            Side::Short => Side::Long,
+        };
+        self.push_trade_deal(
+            exit_side,
+            DEAL_ENTRY_OUT,
+            time,
+            position.volume,
+            price,
+            profit,
+            Some(position.ticket),
+            reason,
+            comment,
+        );
+    }
+
+    /// Appends a buy/sell deal and books its profit and commission; returns its ticket
+    #[allow(clippy::too_many_arguments)]
+    fn push_trade_deal(
+        &mut self,
+        side: Side,
+        entry: i64,
+        time: i64,
+        volume: f64,
+        price: f64,
+        profit: f64,
+        position_id: Option<i64>,
+        reason: i64,
+        comment: &str,
+    ) -> i64 {
+        let ticket = self.next_ticket;
+        self.next_ticket += 1;
+
+        let commission = -self.request.strategy.costs.commission_per_lot * volume;
+        self.balance += profit + commission;
+
+        self.deals.push(Deal {
+            ticket,
+            order: ticket,
+            time,
+            time_msc: time * 1000,
+            r#type: match side {
+                Side::Long => DEAL_TYPE_BUY,
+                Side::Short => DEAL_TYPE_SELL,
+            },
+            entry,
+            magic: 0,
+            position_id: position_id.unwrap_or(ticket),
+            reason,
+            volume,
+            price,
+            commission,
+            swap: 0.0,
+            profit,
+            fee: 0.0,
+            symbol: self.request.symbol.clone(),
+            comment: comment.to_string(),
+            external_id: String::new(),
+        });
+
+        ticket
+    }
+
+    fn push_balance_deal(&mut self, time: i64) {
+        let ticket = self.next_ticket;
+        self.next_ticket += 1;
+
+        self.deals.push(Deal {
+            ticket,
+            order: 0,
+            time,
+            time_msc: time * 1000,
+            r#type: DEAL_TYPE_BALANCE,
+            entry: DEAL_ENTRY_IN,
+            magic: 0,
+            position_id: 0,
+            reason: 0,
+            volume: 0.0,
+            price: 0.0,
+            commission: 0.0,
+            swap: 0.0,
+            profit: self.request.initial_balance,
+            fee: 0.0,
+            symbol: String::new(),
+            comment: "initial deposit".to_string(),
+            external_id: String::new(),
+        });
+    }
+}
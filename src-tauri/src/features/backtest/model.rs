@@ -0,0 +1,162 @@
+//! Backtest data models
+
+use crate::features::asset::model::TimeRange;
+use crate::features::deals::model::Deal;
+use crate::features::indicators::model::{IndicatorSpec, PriceSource};
+use serde::{Deserialize, Serialize};
+
+/// A value a condition compares, evaluated per bar
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum Operand {
+    /// Price of the bar (bid side)
+    Price {
+        #[serde(default)]
+        source: PriceSource,
+    },
+    /// Output line of one of the strategy's indicators
+    Indicator {
+        /// Indicator id (the spec's `id`, or its default id such as "sma_20")
+        id: String,
+        /// Line name (e.g. "signal" for MACD); defaults to the first line
+        #[serde(default)]
+        line: Option<String>,
+    },
+    /// Constant threshold
+    Value { value: f64 },
+}
+
+/// Rule evaluated on the close of every bar
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum Condition {
+    /// `left` was at or below `right` on the previous bar and is above it now
+    CrossesAbove { left: Operand, right: Operand },
+    /// `left` was at or above `right` on the previous bar and is below it now
+    CrossesBelow { left: Operand, right: Operand },
+    /// `left` is above `right`
+    Above { left: Operand, right: Operand },
+    /// `left` is below `right`
+    Below { left: Operand, right: Operand },
+}
+
+/// Distance of a stop-loss or take-profit from the entry price
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "mode", rename_all = "snake_case")]
+pub enum ExitLevel {
+    /// Fixed distance in price units
+    Price { distance: f64 },
+    /// Percentage of the entry price
+    Percent { percent: f64 },
+    /// Multiple of the ATR on the signal bar
+    Atr {
+        period: usize,
+        #[serde(default = "ExitLevel::default_multiplier")]
+        multiplier: f64,
+    },
+}
+
+impl ExitLevel {
+    fn default_multiplier() -> f64 {
+        1.0
+    }
+}
+
+/// How the volume of each trade is chosen
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "mode", rename_all = "snake_case")]
+pub enum PositionSizing {
+    /// Same volume (lots) for every trade
+    FixedVolume { volume: f64 },
+    /// Volume that loses `percent` of the balance when the stop-loss is hit (requires a stop-loss)
+    RiskPercent { percent: f64 },
+}
+
+/// Trading costs applied to every fill
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize)]
+pub struct TradingCosts {
+    /// Spread in price units; bars are treated as bid prices, so buys fill at price + spread
+    #[serde(default)]
+    pub spread: f64,
+    /// Commission per lot charged on each side (entry and exit), in account currency
+    #[serde(default)]
+    pub commission_per_lot: f64,
+}
+
+/// Declarative strategy: indicators plus entry/exit rules
+/// Every list of conditions must hold entirely (AND); an empty list never fires
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Strategy {
+    #[serde(default)]
+    pub indicators: Vec<IndicatorSpec>,
+    #[serde(default)]
+    pub long_entry: Vec<Condition>,
+    #[serde(default)]
+    pub long_exit: Vec<Condition>,
+    #[serde(default)]
+    pub short_entry: Vec<Condition>,
+    #[serde(default)]
+    pub short_exit: Vec<Condition>,
+    #[serde(default)]
+    pub stop_loss: Option<ExitLevel>,
+    #[serde(default)]
+    pub take_profit: Option<ExitLevel>,
+    pub sizing: PositionSizing,
+    #[serde(default)]
+    pub costs: TradingCosts,
+}
+
+/// Backtest run request
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BacktestRequest {
+    pub symbol: String,
+    pub timeframe: String,
+    #[serde(default)]
+    pub range: Option<TimeRange>,
+    pub strategy: Strategy,
+    /// Starting balance, recorded as a balance deal (defaults to 10000)
+    #[serde(default = "BacktestRequest::default_initial_balance")]
+    pub initial_balance: f64,
+    /// Units per lot used to turn price moves into profit (defaults to 100000)
+    #[serde(default = "BacktestRequest::default_contract_size")]
+    pub contract_size: f64,
+    /// Smallest volume increment; risk-based volumes are rounded down to it (defaults to 0.01)
+    #[serde(default = "BacktestRequest::default_volume_step")]
+    pub volume_step: f64,
+    /// Deals file name (without extension) to write; defaults to "backtest_{symbol}_{timeframe}"
+    /// Names are prefixed with "backtest_" when they do not start with it, so imported accounts are never replaced
+    #[serde(default)]
+    pub account: Option<String>,
+}
+
+impl BacktestRequest {
+    fn default_initial_balance() -> f64 {
+        10_000.0
+    }
+
+    fn default_contract_size() -> f64 {
+        100_000.0
+    }
+
+    fn default_volume_step() -> f64 {
+        0.01
+    }
+}
+
+/// Result of a backtest run
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BacktestResult {
+    /// Account (deals file name) the synthetic deals were written to; usable as `account_number` in deals.* insights
+    pub account: String,
+    /// Number of closed trades
+    pub trades: usize,
+    pub winning_trades: usize,
+    /// Sum of trade profits and commissions
+    pub net_profit: f64,
+    pub total_commission: f64,
+    pub final_balance: f64,
+    /// Largest peak-to-trough decline of the closed-trade balance
+    pub max_drawdown: f64,
+    /// Synthetic deals in `deals_schema` layout
+    pub deals: Vec<Deal>,
+}
@@ -0,0 +1,87 @@
+//! Backtest service - runs strategies over cached OHLC data and stores the synthetic deals
+
+use crate::shared::error::AppError;
+use crate::features::accounts::account_id::{AccountId, BACKTEST_ACCOUNT_PREFIX};
+use crate::features::asset::service::AssetService;
+use crate::features::backtest::engine::BacktestEngine;
+use crate::features::backtest::model::{BacktestRequest, BacktestResult};
+use crate::features::deals::model::{Position, PositionStatus};
+use crate::features::deals::positions::PositionService;
+use crate::features::deals::service::DealService;
+use crate::features::indicators::model::{IndicatorKind, IndicatorSpec};
+use crate::features::indicators::service::IndicatorService;
+
+/// Service for running backtests
+pub struct BacktestService;
+
+impl BacktestService {
+    /// Runs a strategy over the bars of a symbol and timeframe
+    /// The synthetic deals are written to the deals cache as `{account}.parquet`,
+    /// so every deals.* insight can analyze them like an imported account
+    pub fn run_backtest(request: &BacktestRequest) -> Result<BacktestResult, AppError> {
+        BacktestEngine::validate(request)?;
+
+        // Account names become file names in the deals cache; the `backtest_` prefix keeps
+        // synthetic deals from ever replacing the deals of an imported account
+        let account = AccountId::parse(&match request.account.as_deref().map(str::trim) {
+            Some(account) if account.starts_with(BACKTEST_ACCOUNT_PREFIX) => account.to_string(),
+            Some(account) => format!("{}{}", BACKTEST_ACCOUNT_PREFIX, account),
+            None => format!("{}{}_{}", BACKTEST_ACCOUNT_PREFIX, request.symbol, request.timeframe),
+        })?;
+
+        let bars = AssetService::retrieve_asset_ochl(&request.symbol, &request.timeframe, request.range)?;
+        if bars.len() < 2 {
+            return Err(AppError::new(format!(
+                "Not enough bars to backtest {} {}",
+                request.symbol, request.timeframe
+            )));
+        }
+
+        // Strategy indicators plus the ATRs of ATR-based exit levels
+        let mut specs = request.strategy.indicators.clone();
+        for period in BacktestEngine::exit_atr_periods(request) {
+            specs.push(IndicatorSpec {
+                id: Some(BacktestEngine::exit_atr_id(period)),
+                kind: IndicatorKind::Atr { period },
+            });
+        }
+        let indicators =
+            IndicatorService::compute_indicators(&request.symbol, &request.timeframe, request.range, &specs)?;
+
+        let deals = BacktestEngine::new(request, &bars, &indicators)?.run()?;
+
+        DealService::write_deals(account.as_str(), &deals)?;
+
+        // Balance curve over the trade deals (the first deal is the initial balance)
+        let mut balance = request.initial_balance;
+        let mut peak = balance;
+        let mut max_drawdown: f64 = 0.0;
+        let mut total_commission = 0.0;
+
+        for deal in deals.iter().skip(1) {
+            balance += deal.profit + deal.commission;
+            total_commission += deal.commission;
+            peak = peak.max(balance);
+            max_drawdown = max_drawdown.max(peak - balance);
+        }
+
+        // A trade wins when its net P&L, including the entry and exit commissions, is positive
+        let closed: Vec<Position> = PositionService::build_positions(&deals, 0)
+            .into_iter()
+            .filter(|position| position.status == PositionStatus::Closed)
+            .collect();
+        let trades = closed.len();
+        let winning_trades = closed.iter().filter(|position| position.net_profit > 0.0).count();
+
+        Ok(BacktestResult {
+            account: account.into_string(),
+            trades,
+            winning_trades,
+            net_profit: balance - request.initial_balance,
+            total_commission,
+            final_balance: balance,
+            max_drawdown,
+            deals,
+        })
+    }
+}
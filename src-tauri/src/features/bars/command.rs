@@ -0,0 +1,27 @@
+//! Bar transform command handlers
+
+use crate::features::asset::model::TimeRange;
+use crate::features::bars::model::{BarTransformResult, BarType};
+use crate::features::bars::service::BarTransformService;
+use crate::shared::error::AppError;
+
+/// Tauri command: retrieve_transformed_bars
+/// Retrieves the bars of a symbol as Heikin-Ashi, Renko, range or point-and-figure bars
+/// 
+/// # Arguments
+/// * `symbol` - The symbol name (e.g., "EURUSD")
+/// * `timeframe` - The timeframe of the underlying bars (e.g., "1M")
+/// * `range` - Optional inclusive time range in Unix seconds (`{ start, end }`, either bound optional)
+/// * `bar_type` - The bar type (e.g., `{ "kind": "renko", "box_size": { "mode": "atr", "period": 14 } }`)
+/// 
+/// Returns the transformed bars and the box size used
+#[tauri::command]
+pub fn retrieve_transformed_bars(
+    symbol: String,
+    timeframe: String,
+    range: Option<TimeRange>,
+    bar_type: BarType,
+) -> Result<BarTransformResult, String> {
+    BarTransformService::retrieve_transformed_bars(&symbol, &timeframe, range, &bar_type)
+        .map_err(AppError::into_command_error)
+}
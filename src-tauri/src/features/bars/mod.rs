@@ -0,0 +1,7 @@
+/// Bars feature module
+pub mod command;
+pub mod service;
+pub mod model;
+pub mod transform;
+
+pub use command::retrieve_transformed_bars;
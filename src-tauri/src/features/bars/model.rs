@@ -0,0 +1,72 @@
+//! Bar transform data models
+
+use serde::{Deserialize, Serialize};
+
+/// Size of a Renko brick, range bar or point-and-figure box
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[serde(tag = "mode", rename_all = "snake_case")]
+pub enum BoxSize {
+    /// Fixed size in price units (e.g. 0.0010 for 10 pips on EURUSD)
+    Price { value: f64 },
+    /// ATR of the underlying bars over `period`, multiplied by `multiplier`
+    /// The ATR value at the end of the requested range is used for the whole series
+    Atr {
+        period: usize,
+        #[serde(default = "BoxSize::default_multiplier")]
+        multiplier: f64,
+    },
+}
+
+impl BoxSize {
+    fn default_multiplier() -> f64 {
+        1.0
+    }
+}
+
+/// Bar type produced from the underlying time bars
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum BarType {
+    /// Heikin-Ashi candles, one per time bar
+    HeikinAshi,
+    /// Close-based Renko bricks; a reversal needs a move of two bricks
+    Renko { box_size: BoxSize },
+    /// Bars whose high - low equals the box size
+    Range { box_size: BoxSize },
+    /// Close-based point-and-figure columns
+    PointAndFigure {
+        box_size: BoxSize,
+        /// Boxes needed to start a column in the opposite direction (defaults to 3)
+        #[serde(default = "BarType::default_reversal")]
+        reversal: usize,
+    },
+}
+
+impl BarType {
+    fn default_reversal() -> usize {
+        3
+    }
+}
+
+/// A transformed bar, with the underlying time bars it was built from
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TransformedBar {
+    /// Time of the first underlying bar (Unix seconds)
+    pub time: i64,
+    /// Time of the underlying bar that completed this bar (Unix seconds)
+    pub end_time: i64,
+    pub open: f64,
+    pub high: f64,
+    pub low: f64,
+    pub close: f64,
+    /// 1 for up (X column), -1 for down (O column), 0 for unchanged
+    pub direction: i8,
+}
+
+/// Result of `retrieve_transformed_bars`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BarTransformResult {
+    /// Box size used in price units (absent for Heikin-Ashi)
+    pub box_size: Option<f64>,
+    pub bars: Vec<TransformedBar>,
+}
@@ -0,0 +1,111 @@
+//! Bar transform service - builds alternative bar types from cached OHLC data
+
+use crate::shared::error::AppError;
+use crate::features::asset::model::{OHLCData, TimeRange};
+use crate::features::asset::service::AssetService;
+use crate::features::bars::model::{BarTransformResult, BarType, BoxSize};
+use crate::features::bars::transform::BarTransformer;
+use crate::features::indicators::model::{IndicatorKind, IndicatorSpec};
+use crate::features::indicators::service::IndicatorService;
+
+/// Service for transformed bar retrieval
+pub struct BarTransformService;
+
+impl BarTransformService {
+    /// Retrieves the bars of a symbol and timeframe inside `range` transformed to `bar_type`
+    pub fn retrieve_transformed_bars(
+        symbol: &str,
+        timeframe: &str,
+        range: Option<TimeRange>,
+        bar_type: &BarType,
+    ) -> Result<BarTransformResult, AppError> {
+        let bars = AssetService::retrieve_asset_ochl(symbol, timeframe, range)?;
+
+        let result = match bar_type {
+            BarType::HeikinAshi => BarTransformResult {
+                box_size: None,
+                bars: BarTransformer::heikin_ashi(&bars),
+            },
+            BarType::Renko { box_size } => {
+                let box_size = Self::resolve_box_size(symbol, timeframe, range, box_size, &bars)?;
+                BarTransformResult {
+                    box_size: Some(box_size),
+                    bars: BarTransformer::renko(&bars, box_size)?,
+                }
+            }
+            BarType::Range { box_size } => {
+                let box_size = Self::resolve_box_size(symbol, timeframe, range, box_size, &bars)?;
+                BarTransformResult {
+                    box_size: Some(box_size),
+                    bars: BarTransformer::range_bars(&bars, box_size)?,
+                }
+            }
+            BarType::PointAndFigure { box_size, reversal } => {
+                if *reversal == 0 {
+                    return Err(AppError::new("Point-and-figure 'reversal' must be at least 1"));
+                }
+                let box_size = Self::resolve_box_size(symbol, timeframe, range, box_size, &bars)?;
+                BarTransformResult {
+                    box_size: Some(box_size),
+                    bars: BarTransformer::point_and_figure(&bars, box_size, *reversal),
+                }
+            }
+        };
+
+        Ok(result)
+    }
+
+    /// Resolves a box size to price units
+    /// ATR-based sizes use the last ATR value of the range. Sizes below
+    /// `BarTransformer::min_box_size` of the highest price in `bars` are rejected
+    fn resolve_box_size(
+        symbol: &str,
+        timeframe: &str,
+        range: Option<TimeRange>,
+        box_size: &BoxSize,
+        bars: &[OHLCData],
+    ) -> Result<f64, AppError> {
+        let value = match box_size {
+            BoxSize::Price { value } => *value,
+            BoxSize::Atr { period, multiplier } => {
+                let spec = IndicatorSpec {
+                    id: None,
+                    kind: IndicatorKind::Atr { period: *period },
+                };
+                let result = IndicatorService::compute_indicators(
+                    symbol,
+                    timeframe,
+                    range,
+                    std::slice::from_ref(&spec),
+                )?;
+
+                let atr = result
+                    .indicators
+                    .first()
+                    .and_then(|indicator| indicator.lines.first())
+                    .and_then(|line| line.values.iter().rev().find_map(|value| *value))
+                    .ok_or_else(|| {
+                        AppError::new(format!(
+                            "Not enough bars to compute ATR({}) for {} {}",
+                            period, symbol, timeframe
+                        ))
+                    })?;
+                atr * multiplier
+            }
+        };
+
+        if !value.is_finite() || value <= 0.0 {
+            return Err(AppError::new("Box size must be a positive number"));
+        }
+        let max_price = bars.iter().map(|bar| bar.high.abs()).fold(0.0, f64::max);
+        let min_box_size = BarTransformer::min_box_size(max_price);
+        if value < min_box_size {
+            return Err(AppError::new(format!(
+                "Box size {} is too small for {} {} (at least {} at a price of {})",
+                value, symbol, timeframe, min_box_size, max_price
+            )));
+        }
+
+        Ok(value)
+    }
+}
@@ -0,0 +1,246 @@
+//! Bar transforms - build alternative bar types from time bars
+//!
+//! Every transformed bar records the time of the first underlying bar it covers and of
+//! the bar that completed it, so it can be mapped back onto the time-based chart.
+
+use crate::features::asset::model::OHLCData;
+use crate::features::bars::model::TransformedBar;
+use crate::shared::error::AppError;
+
+/// Tolerance for box boundaries, so prices that sit exactly on a boundary are not
+/// pushed to the neighbouring box by floating point error
+const BOX_EPSILON: f64 = 1e-9;
+
+/// Smallest box size relative to the price; smaller boxes would be lost in floating point error
+const MIN_RELATIVE_BOX_SIZE: f64 = 1e-6;
+
+/// Most bars a Renko or range transform may build (a box that is small for the range
+/// would otherwise build millions of bars from a single move)
+pub const MAX_TRANSFORMED_BARS: usize = 200_000;
+
+/// Builds alternative bar types from a time-sorted series of OHLC bars
+pub struct BarTransformer;
+
+impl BarTransformer {
+    /// Heikin-Ashi candles
+    /// close = (o + h + l + c) / 4, open = midpoint of the previous HA candle body
+    pub fn heikin_ashi(bars: &[OHLCData]) -> Vec<TransformedBar> {
+        let mut result: Vec<TransformedBar> = Vec::with_capacity(bars.len());
+
+        for bar in bars {
+            let close = (bar.open + bar.high + bar.low + bar.close) / 4.0;
+            let open = match result.last() {
+                Some(previous) => (previous.open + previous.close) / 2.0,
+                None => (bar.open + bar.close) / 2.0,
+            };
+
+            result.push(TransformedBar {
+                time: bar.time,
+                end_time: bar.time,
+                open,
+                high: bar.high.max(open).max(close),
+                low: bar.low.min(open).min(close),
+                close,
+                direction: Self::direction(open, close),
+            });
+        }
+
+        result
+    }
+
+    /// Smallest box size accepted for prices up to `price`
+    pub fn min_box_size(price: f64) -> f64 {
+        (BOX_EPSILON * 10.0).max(price.abs() * MIN_RELATIVE_BOX_SIZE)
+    }
+
+    /// Close-based Renko bricks of `box_size`
+    /// A new brick in the same direction needs a close one box beyond the last brick,
+    /// a reversal needs a close one box beyond the last brick's open
+    /// Fails once more than `MAX_TRANSFORMED_BARS` bricks would be built
+    pub fn renko(bars: &[OHLCData], box_size: f64) -> Result<Vec<TransformedBar>, AppError> {
+        let mut result = Vec::new();
+        let Some(first) = bars.first() else {
+            return Ok(result);
+        };
+
+        // Price span of the last brick; starts as a zero-height brick at the first close
+        let mut brick_high = first.close;
+        let mut brick_low = first.close;
+        // Start of the next brick: the first bar after the last completed brick
+        let mut brick_start = Some(first.time);
+
+        for bar in bars.iter().skip(1) {
+            let start = *brick_start.get_or_insert(bar.time);
+
+            while bar.close >= brick_high + box_size - BOX_EPSILON {
+                Self::check_bar_count(&result, box_size)?;
+                result.push(Self::brick(start, bar.time, brick_high, brick_high + box_size));
+                brick_low = brick_high;
+                brick_high += box_size;
+                brick_start = None;
+            }
+            while bar.close <= brick_low - box_size + BOX_EPSILON {
+                Self::check_bar_count(&result, box_size)?;
+                result.push(Self::brick(start, bar.time, brick_low, brick_low - box_size));
+                brick_high = brick_low;
+                brick_low -= box_size;
+                brick_start = None;
+            }
+        }
+
+        Ok(result)
+    }
+
+    /// Range bars whose high - low equals `box_size`
+    /// The path inside each time bar is assumed to be open, low, high, close for up bars
+    /// and open, high, low, close for down bars. Only completed bars are returned.
+    /// Fails once more than `MAX_TRANSFORMED_BARS` bars would be built
+    pub fn range_bars(bars: &[OHLCData], box_size: f64) -> Result<Vec<TransformedBar>, AppError> {
+        let mut result = Vec::new();
+        // Forming bar: (start time, open, high, low)
+        let mut forming: Option<(i64, f64, f64, f64)> = None;
+
+        for bar in bars {
+            let path = if bar.close >= bar.open {
+                [bar.open, bar.low, bar.high, bar.close]
+            } else {
+                [bar.open, bar.high, bar.low, bar.close]
+            };
+
+            for price in path {
+                let (mut start, mut open, mut high, mut low) =
+                    forming.unwrap_or((bar.time, price, price, price));
+
+                loop {
+                    if price > low + box_size + BOX_EPSILON {
+                        Self::check_bar_count(&result, box_size)?;
+                        let close = low + box_size;
+                        result.push(Self::bar(start, bar.time, open, close, low, close));
+                        (start, open, high, low) = (bar.time, close, close, close);
+                    } else if price < high - box_size - BOX_EPSILON {
+                        Self::check_bar_count(&result, box_size)?;
+                        let close = high - box_size;
+                        result.push(Self::bar(start, bar.time, open, high, close, close));
+                        (start, open, high, low) = (bar.time, close, close, close);
+                    } else {
+                        high = high.max(price);
+                        low = low.min(price);
+                        break;
+                    }
+                }
+
+                forming = Some((start, open, high, low));
+            }
+        }
+
+        Ok(result)
+    }
+
+    /// Close-based point-and-figure columns
+    /// A column extends by whole boxes; a new column starts after a move of `reversal` boxes
+    /// against the current one. The last (still open) column is included.
+    pub fn point_and_figure(bars: &[OHLCData], box_size: f64, reversal: usize) -> Vec<TransformedBar> {
+        let mut result = Vec::new();
+        let Some(first) = bars.first() else {
+            return result;
+        };
+
+        let reversal = reversal as f64 * box_size;
+        let floor_box = |price: f64| (price / box_size + BOX_EPSILON).floor() * box_size;
+        let ceil_box = |price: f64| (price / box_size - BOX_EPSILON).ceil() * box_size;
+
+        // Current column: direction (0 until the first box), top, bottom, start time
+        let mut direction: i8 = 0;
+        let mut top = floor_box(first.close);
+        let mut bottom = top;
+        let mut start = first.time;
+        let mut end = first.time;
+
+        for bar in bars.iter().skip(1) {
+            let price = bar.close;
+            match direction {
+                0 => {
+                    if price >= top + box_size - BOX_EPSILON {
+                        direction = 1;
+                        top = floor_box(price);
+                        end = bar.time;
+                    } else if price <= bottom - box_size + BOX_EPSILON {
+                        direction = -1;
+                        bottom = ceil_box(price);
+                        end = bar.time;
+                    }
+                }
+                1 => {
+                    if price >= top + box_size - BOX_EPSILON {
+                        top = floor_box(price);
+                        end = bar.time;
+                    } else if price <= top - reversal + BOX_EPSILON {
+                        result.push(Self::bar(start, end, bottom, top, bottom, top));
+                        direction = -1;
+                        top -= box_size;
+                        bottom = ceil_box(price);
+                        start = bar.time;
+                        end = bar.time;
+                    }
+                }
+                _ => {
+                    if price <= bottom - box_size + BOX_EPSILON {
+                        bottom = ceil_box(price);
+                        end = bar.time;
+                    } else if price >= bottom + reversal - BOX_EPSILON {
+                        result.push(Self::bar(start, end, top, top, bottom, bottom));
+                        direction = 1;
+                        bottom += box_size;
+                        top = floor_box(price);
+                        start = bar.time;
+                        end = bar.time;
+                    }
+                }
+            }
+        }
+
+        if direction != 0 {
+            let (open, close) = if direction > 0 { (bottom, top) } else { (top, bottom) };
+            result.push(Self::bar(start, end, open, top, bottom, close));
+        }
+
+        result
+    }
+
+    /// Errors when another bar would exceed `MAX_TRANSFORMED_BARS`
+    fn check_bar_count(result: &[TransformedBar], box_size: f64) -> Result<(), AppError> {
+        if result.len() >= MAX_TRANSFORMED_BARS {
+            return Err(AppError::new(format!(
+                "Box size {} builds more than {} bars; use a larger box size or a shorter range",
+                box_size, MAX_TRANSFORMED_BARS
+            )));
+        }
+        Ok(())
+    }
+
+    fn brick(time: i64, end_time: i64, open: f64, close: f64) -> TransformedBar {
+        Self::bar(time, end_time, open, open.max(close), open.min(close), close)
+    }
+
+    fn bar(time: i64, end_time: i64, open: f64, high: f64, low: f64, close: f64) -> TransformedBar {
+        TransformedBar {
+            time,
+            end_time,
+            open,
+            high,
+            low,
+            close,
+            direction: Self::direction(open, close),
+        }
+    }
+
+    fn direction(open: f64, close: f64) -> i8 {
+        if close > open {
+            1
+        } else if close < open {
+            -1
+        } else {
+            0
+        }
+    }
+}
@@ -1,21 +1,188 @@
 //! Deals command handlers
 
-use crate::features::deals::model::{Deal, DealImportResult};
+use crate::features::deals::import_profile::ImportProfile;
+use crate::features::deals::model::{
+    AccountSemanticReport, Deal, DealImportProgress, DealImportResult, ImportMode, ImportPreview, SemanticConfig,
+    StatementImportResult, ValidationPolicy,
+};
 use crate::features::deals::service::DealService;
+use crate::shared::error::AppError;
+use tauri::{AppHandle, Emitter};
+
+/// Event emitted after each file of `import_deals_from_paths`
+const IMPORT_PROGRESS_EVENT: &str = "deals-import-progress";
 
 /// Tauri command: validate_and_store_deals
 /// Validates and stores Parquet files for deals
 /// 
 /// # Arguments
 /// * `files` - Vector of tuples (filename, file_data) where file_data is Vec<u8>
+/// * `policy` - Optional validation policy (default: strict, default semantic severities)
+/// * `mode` - Optional import mode: replace, append or upsert by ticket (default: replace)
 /// 
 /// Returns DealImportResult with overall status and per-file messages
 #[tauri::command]
 pub fn validate_and_store_deals(
     files: Vec<(String, Vec<u8>)>,
+    policy: Option<ValidationPolicy>,
+    mode: Option<ImportMode>,
 ) -> Result<DealImportResult, String> {
-    DealService::validate_and_store_files(files)
-        .map_err(|e| e.message)
+    DealService::validate_and_store_files(files, policy.unwrap_or_default(), mode.unwrap_or_default())
+        .map_err(AppError::into_command_error)
+}
+
+/// Tauri command: import_deals_from_paths
+/// Validates and stores Parquet files read directly from disk, one at a time
+/// Emits a `deals-import-progress` event (DealImportProgress) after each file
+/// 
+/// # Arguments
+/// * `paths` - Paths of the files to import; the account is the file name without extension
+/// * `policy` - Optional validation policy (default: strict, default semantic severities)
+/// * `mode` - Optional import mode: replace, append or upsert by ticket (default: replace)
+/// 
+/// Returns DealImportResult with overall status and per-file messages
+#[tauri::command]
+pub async fn import_deals_from_paths(
+    app: AppHandle,
+    paths: Vec<String>,
+    policy: Option<ValidationPolicy>,
+    mode: Option<ImportMode>,
+) -> Result<DealImportResult, String> {
+    tauri::async_runtime::spawn_blocking(move || {
+        DealService::import_from_paths(
+            &paths,
+            policy.unwrap_or_default(),
+            mode.unwrap_or_default(),
+            |progress: &DealImportProgress| {
+                let _ = app.emit(IMPORT_PROGRESS_EVENT, progress);
+            },
+        )
+        .map_err(AppError::into_command_error)
+    })
+    .await
+    .map_err(|e| format!("Import task failed: {}", e))?
+}
+
+/// Tauri command: import_mt5_statements
+/// Imports MT5 "Report History" statements (HTML or XLSX) as deals
+/// 
+/// # Arguments
+/// * `files` - Vector of tuples (filename, file_data); the extension selects the format
+/// * `config` - Optional severity overrides of the semantic checks
+/// * `mode` - Optional import mode: replace, append or upsert by ticket (default: replace)
+/// 
+/// Returns one StatementImportResult per file with per-row warnings
+#[tauri::command]
+pub fn import_mt5_statements(
+    files: Vec<(String, Vec<u8>)>,
+    config: Option<SemanticConfig>,
+    mode: Option<ImportMode>,
+) -> Result<Vec<StatementImportResult>, String> {
+    DealService::import_mt5_statements(files, &config.unwrap_or_default(), mode.unwrap_or_default())
+        .map_err(AppError::into_command_error)
+}
+
+/// Tauri command: save_import_profile
+/// Saves (or replaces) a column-mapping profile for CSV/JSON deal imports
+/// 
+/// # Arguments
+/// * `profile` - The profile (column renames, value maps, time format, defaults)
+/// 
+/// Returns the saved profile
+#[tauri::command]
+pub fn save_import_profile(profile: ImportProfile) -> Result<ImportProfile, String> {
+    DealService::save_import_profile(profile)
+        .map_err(AppError::into_command_error)
+}
+
+/// Tauri command: list_import_profiles
+/// Lists the saved column-mapping profiles
+/// 
+/// Returns the profiles sorted by name
+#[tauri::command]
+pub fn list_import_profiles() -> Result<Vec<ImportProfile>, String> {
+    DealService::list_import_profiles()
+        .map_err(AppError::into_command_error)
+}
+
+/// Tauri command: delete_import_profile
+/// Deletes a saved column-mapping profile
+/// 
+/// # Arguments
+/// * `name` - The profile name
+#[tauri::command]
+pub fn delete_import_profile(name: String) -> Result<(), String> {
+    DealService::delete_import_profile(&name)
+        .map_err(AppError::into_command_error)
+}
+
+/// Tauri command: preview_deals_import
+/// Maps a CSV/JSON file with a profile without storing it
+/// 
+/// # Arguments
+/// * `file_data` - The file content
+/// * `profile` - The mapping profile (saved or not)
+/// * `rows` - Optional number of mapped deals to return (default: 20)
+/// 
+/// Returns the first mapped deals and the rows that could not be mapped
+#[tauri::command]
+pub fn preview_deals_import(
+    file_data: Vec<u8>,
+    profile: ImportProfile,
+    rows: Option<usize>,
+) -> Result<ImportPreview, String> {
+    DealService::preview_import(&file_data, &profile, rows)
+        .map_err(AppError::into_command_error)
+}
+
+/// Tauri command: import_deals_with_profile
+/// Imports a CSV/JSON file mapped with a profile into the deals cache
+/// 
+/// # Arguments
+/// * `filename` - The original filename
+/// * `file_data` - The file content
+/// * `profile` - The mapping profile
+/// * `account` - Optional account name (default: filename without extension)
+/// * `config` - Optional severity overrides of the semantic checks
+/// * `mode` - Optional import mode: replace, append or upsert by ticket (default: replace)
+/// 
+/// Returns a StatementImportResult with per-row warnings for skipped rows
+#[tauri::command]
+pub fn import_deals_with_profile(
+    filename: String,
+    file_data: Vec<u8>,
+    profile: ImportProfile,
+    account: Option<String>,
+    config: Option<SemanticConfig>,
+    mode: Option<ImportMode>,
+) -> Result<StatementImportResult, String> {
+    DealService::import_with_profile(
+        &filename,
+        &file_data,
+        &profile,
+        account,
+        &config.unwrap_or_default(),
+        mode.unwrap_or_default(),
+    )
+        .map_err(AppError::into_command_error)
+}
+
+/// Tauri command: validate_stored_deals
+/// Runs the semantic checks (duplicate tickets, invalid volumes, unknown codes,
+/// time mismatches, exits without entry) on accounts already in the deals cache
+/// 
+/// # Arguments
+/// * `accounts` - Optional accounts to check (default: all stored accounts)
+/// * `config` - Optional severity overrides per rule
+/// 
+/// Returns one AccountSemanticReport per account (with an error for accounts that could not be checked)
+#[tauri::command]
+pub fn validate_stored_deals(
+    accounts: Option<Vec<String>>,
+    config: Option<SemanticConfig>,
+) -> Result<Vec<AccountSemanticReport>, String> {
+    DealService::validate_stored_deals(accounts, &config.unwrap_or_default())
+        .map_err(AppError::into_command_error)
 }
 
 /// Tauri command: read_deals_from_file
@@ -28,16 +195,16 @@ pub fn validate_and_store_deals(
 #[tauri::command]
 pub fn read_deals_from_file(filename: String) -> Result<Vec<Deal>, String> {
     DealService::read_deals_from_file(&filename)
-        .map_err(|e| e.message)
+        .map_err(AppError::into_command_error)
 }
 
 /// Tauri command: read_all_deals
-/// Reads all deals from all Parquet files in the deals cache directory
+/// Reads the deals of every imported account (backtest accounts are left out)
 /// 
 /// Returns a combined vector of all Deal records from all files
 #[tauri::command]
 pub fn read_all_deals() -> Result<Vec<Deal>, String> {
     DealService::read_all_deals()
-        .map_err(|e| e.message)
+        .map_err(AppError::into_command_error)
 }
 
@@ -0,0 +1,149 @@
+//! Column-mapping profiles for importing deals exported by other platforms (CSV/JSON)
+
+use crate::shared::error::AppError;
+use crate::shared::utils::{cache_dir, validation};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::BTreeMap;
+use std::fs;
+use std::sync::Mutex;
+
+/// Serializes read-modify-write cycles on the profiles file
+static PROFILES_LOCK: Mutex<()> = Mutex::new(());
+
+/// Format of the source file
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SourceFormat {
+    #[default]
+    Csv,
+    Json,
+}
+
+/// How a deal field is mapped from the source rows
+///
+/// Fields of the deals schema are looked up in the source under the name given in
+/// `columns`, or under their own name when not listed. Text values are coerced to the
+/// field's type (integer, float or string); `value_maps` translate enum spellings first.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ImportProfile {
+    /// Unique profile name
+    pub name: String,
+
+    #[serde(default)]
+    pub format: SourceFormat,
+
+    /// CSV delimiter (defaults to ',')
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub delimiter: Option<char>,
+
+    /// Dotted path of the record array inside a JSON document (e.g. "data.deals")
+    /// If not provided, the document itself must be the array
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub records_path: Option<String>,
+
+    /// Deal field -> source column (dotted paths reach nested JSON fields)
+    #[serde(default)]
+    pub columns: BTreeMap<String, String>,
+
+    /// Deal field -> source value -> mapped value (e.g. `type`: {"Buy": 0, "Sell": 1})
+    /// Source values are matched exactly first, then case-insensitively
+    #[serde(default)]
+    pub value_maps: BTreeMap<String, BTreeMap<String, Value>>,
+
+    /// Format of `time`: "unix_seconds" (default), "unix_millis" or a chrono pattern
+    /// such as "%Y-%m-%d %H:%M:%S%.f" (interpreted as server time, like MT5 deal times)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub time_format: Option<String>,
+
+    /// Decimal separator of numbers written as text (defaults to '.')
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub decimal_separator: Option<char>,
+
+    /// Deal field -> value used when the source has no such column or the cell is empty
+    #[serde(default)]
+    pub defaults: BTreeMap<String, Value>,
+}
+
+impl ImportProfile {
+    /// Checks the profile refers to known deal fields and a supported time format
+    pub fn validate(&self) -> Result<(), AppError> {
+        use crate::features::deals::model::deals_schema;
+
+        validation::validate_non_empty(&self.name, "profile name")?;
+
+        let fields = self
+            .columns
+            .keys()
+            .chain(self.value_maps.keys())
+            .chain(self.defaults.keys());
+        for field in fields {
+            if !deals_schema::REQUIRED_COLUMNS.contains(&field.as_str()) {
+                return Err(AppError::new(format!(
+                    "Unknown deal field '{}' in profile '{}'",
+                    field, self.name
+                )));
+            }
+        }
+
+        if self.decimal_separator.is_some_and(|separator| separator.is_ascii_digit() || separator == '-') {
+            return Err(AppError::new("Decimal separator cannot be a digit or '-'"));
+        }
+        if let Some(ref format) = self.time_format {
+            validation::validate_non_empty(format, "time format")?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Saved profiles stored as JSON in the cache directory
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ImportProfileStore {
+    /// Profiles keyed by name
+    #[serde(default)]
+    pub profiles: BTreeMap<String, ImportProfile>,
+}
+
+impl ImportProfileStore {
+    /// Loads the profiles, returning an empty store if the file does not exist yet
+    pub fn load() -> Result<Self, AppError> {
+        let path = cache_dir::get_import_profiles_path()?;
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+
+        let content = fs::read_to_string(&path)
+            .map_err(|e| AppError::new(format!("Failed to read import profiles: {}", e)))?;
+        serde_json::from_str(&content)
+            .map_err(|e| AppError::new(format!("Failed to parse import profiles: {}", e)))
+    }
+
+    /// Writes the profiles to disk
+    pub fn save(&self) -> Result<(), AppError> {
+        let path = cache_dir::get_import_profiles_path()?;
+        if let Some(parent) = path.parent() {
+            cache_dir::ensure_cache_dir(parent)?;
+        }
+
+        let content = serde_json::to_string_pretty(self)
+            .map_err(|e| AppError::new(format!("Failed to serialize import profiles: {}", e)))?;
+        let temp_path = path.with_extension("json.tmp");
+        fs::write(&temp_path, content)
+            .map_err(|e| AppError::new(format!("Failed to write import profiles: {}", e)))?;
+        fs::rename(&temp_path, &path)
+            .map_err(|e| AppError::new(format!("Failed to write import profiles: {}", e)))
+    }
+
+    /// Loads the profiles, applies `update` and saves them, holding the profiles lock throughout
+    pub fn update<T>(update: impl FnOnce(&mut ImportProfileStore) -> T) -> Result<T, AppError> {
+        let _guard = PROFILES_LOCK
+            .lock()
+            .map_err(|_| AppError::new("Import profiles lock is poisoned"))?;
+
+        let mut store = Self::load()?;
+        let result = update(&mut store);
+        store.save()?;
+        Ok(result)
+    }
+}
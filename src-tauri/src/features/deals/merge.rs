@@ -0,0 +1,159 @@
+//! Merging imported deals into the stored deals of an account
+
+use crate::features::deals::model::MergeStats;
+use crate::shared::error::AppError;
+use polars::prelude::*;
+use std::collections::HashSet;
+
+/// Service for merging deal frames on `ticket`
+pub struct DealMerger;
+
+impl DealMerger {
+    /// Adds the `incoming` deals whose ticket is not in `stored`; stored deals are never modified
+    /// Returns the merged frame and the number of rows added and left unchanged
+    pub fn append(stored: &DataFrame, incoming: &DataFrame) -> Result<(DataFrame, MergeStats), AppError> {
+        Self::merge(stored, incoming, false)
+    }
+
+    /// Adds the `incoming` deals, replacing the stored deal with the same ticket
+    /// Returns the merged frame and the number of rows added, updated and left unchanged
+    pub fn upsert(stored: &DataFrame, incoming: &DataFrame) -> Result<(DataFrame, MergeStats), AppError> {
+        Self::merge(stored, incoming, true)
+    }
+
+    /// Merges two frames matching the deals schema on `ticket`
+    /// When a ticket repeats in `incoming`, its last deal is used. Extra columns of either frame
+    /// are kept, null in the rows of the frame that lacks them. The result is sorted by
+    /// `time_msc`, then `ticket`
+    fn merge(
+        stored: &DataFrame,
+        incoming: &DataFrame,
+        replace_stored: bool,
+    ) -> Result<(DataFrame, MergeStats), AppError> {
+        let (stored, incoming) = Self::union_columns(stored, incoming)?;
+        let incoming = Self::last_per_ticket(&incoming)?;
+
+        let stored_tickets: HashSet<i64> = Self::tickets(&stored)?.into_iter().collect();
+        let incoming_tickets: HashSet<i64> = Self::tickets(&incoming)?.into_iter().collect();
+
+        let is_new: Vec<bool> = Self::tickets(&incoming)?
+            .iter()
+            .map(|ticket| !stored_tickets.contains(ticket))
+            .collect();
+        let rows_added = is_new.iter().filter(|new| **new).count();
+
+        let (merged, stats) = if !replace_stored {
+            let added = incoming.filter(&Self::mask(&is_new)).map_err(Self::merge_error)?;
+            let merged = stored.vstack(&added).map_err(Self::merge_error)?;
+            let stats = MergeStats {
+                rows_added,
+                rows_updated: 0,
+                rows_unchanged: stored.height(),
+            };
+            (merged, stats)
+        } else {
+            let stored_tickets_list = Self::tickets(&stored)?;
+            let is_replaced: Vec<bool> = stored_tickets_list
+                .iter()
+                .map(|ticket| incoming_tickets.contains(ticket))
+                .collect();
+            let is_matched: Vec<bool> = is_new.iter().map(|new| !new).collect();
+
+            let replaced = stored.filter(&Self::mask(&is_replaced)).map_err(Self::merge_error)?;
+            let matched = incoming.filter(&Self::mask(&is_matched)).map_err(Self::merge_error)?;
+            let rows_updated = Self::count_changed(&Self::last_per_ticket(&replaced)?, &matched)?;
+
+            let kept_mask: Vec<bool> = is_replaced.iter().map(|replaced| !replaced).collect();
+            let kept = stored.filter(&Self::mask(&kept_mask)).map_err(Self::merge_error)?;
+            let merged = kept.vstack(&incoming).map_err(Self::merge_error)?;
+            let stats = MergeStats {
+                rows_added,
+                rows_updated,
+                rows_unchanged: kept.height() + matched.height() - rows_updated,
+            };
+            (merged, stats)
+        };
+
+        let merged = merged
+            .sort(
+                ["time_msc", "ticket"],
+                SortMultipleOptions::default().with_maintain_order(true),
+            )
+            .map_err(Self::merge_error)?;
+        Ok((merged, stats))
+    }
+
+    /// Gives both frames the union of their columns, in the same order
+    /// A column missing from one frame is added to it as nulls of the other frame's type
+    fn union_columns(stored: &DataFrame, incoming: &DataFrame) -> Result<(DataFrame, DataFrame), AppError> {
+        let mut columns: Vec<String> = stored.get_column_names().iter().map(|name| name.to_string()).collect();
+        for name in incoming.get_column_names() {
+            if !columns.iter().any(|column| column == name) {
+                columns.push(name.to_string());
+            }
+        }
+
+        let with_columns = |df: &DataFrame, other: &DataFrame| -> Result<DataFrame, AppError> {
+            let mut df = df.clone();
+            for name in &columns {
+                if df.get_column_names().contains(&name.as_str()) {
+                    continue;
+                }
+                let dtype = other.column(name).map_err(Self::merge_error)?.dtype().clone();
+                df.with_column(Series::full_null(name, df.height(), &dtype))
+                    .map_err(Self::merge_error)?;
+            }
+            df.select(&columns).map_err(Self::merge_error)
+        };
+        Ok((with_columns(stored, incoming)?, with_columns(incoming, stored)?))
+    }
+
+    /// Keeps the last deal of every ticket, in the original order
+    fn last_per_ticket(df: &DataFrame) -> Result<DataFrame, AppError> {
+        let tickets = Self::tickets(df)?;
+        let mut seen = HashSet::with_capacity(tickets.len());
+        let mut keep: Vec<bool> = tickets.iter().rev().map(|ticket| seen.insert(*ticket)).collect();
+        if keep.iter().all(|keep| *keep) {
+            return Ok(df.clone());
+        }
+        keep.reverse();
+        df.filter(&Self::mask(&keep)).map_err(Self::merge_error)
+    }
+
+    /// Number of rows of `stored` that differ from the `incoming` row with the same ticket
+    /// Both frames hold the same tickets once each
+    fn count_changed(stored: &DataFrame, incoming: &DataFrame) -> Result<usize, AppError> {
+        let by_ticket = |df: &DataFrame| {
+            df.sort(["ticket"], SortMultipleOptions::default())
+                .map_err(Self::merge_error)
+        };
+        let stored = by_ticket(stored)?;
+        let incoming = by_ticket(incoming)?;
+
+        let mut changed = vec![false; stored.height()];
+        for column in stored.get_columns() {
+            let other = incoming.column(column.name()).map_err(Self::merge_error)?;
+            let equal = column.equal_missing(other).map_err(Self::merge_error)?;
+            for (changed, equal) in changed.iter_mut().zip(&equal) {
+                *changed |= !equal.unwrap_or(false);
+            }
+        }
+        Ok(changed.into_iter().filter(|changed| *changed).count())
+    }
+
+    fn tickets(df: &DataFrame) -> Result<Vec<i64>, AppError> {
+        let tickets = df
+            .column("ticket")
+            .and_then(|series| series.i64().cloned())
+            .map_err(Self::merge_error)?;
+        Ok(tickets.into_iter().map(|ticket| ticket.unwrap_or_default()).collect())
+    }
+
+    fn mask(flags: &[bool]) -> BooleanChunked {
+        BooleanChunked::from_slice("mask", flags)
+    }
+
+    fn merge_error(e: PolarsError) -> AppError {
+        AppError::new(format!("Failed to merge deals: {}", e))
+    }
+}
@@ -3,6 +3,24 @@ pub mod command;
 pub mod service;
 pub mod model;
 pub mod validator;
+pub mod semantic;
+pub mod merge;
+pub mod positions;
+pub mod statement;
+pub mod import_profile;
+pub mod profile_mapper;
 
-pub use command::{validate_and_store_deals, read_deals_from_file, read_all_deals};
+pub use command::{
+    validate_and_store_deals,
+    import_deals_from_paths,
+    import_mt5_statements,
+    save_import_profile,
+    list_import_profiles,
+    delete_import_profile,
+    preview_deals_import,
+    import_deals_with_profile,
+    validate_stored_deals,
+    read_deals_from_file,
+    read_all_deals,
+};
 
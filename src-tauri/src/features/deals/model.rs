@@ -1,6 +1,9 @@
 //! Deals-related data models
 
+use crate::shared::error::AppError;
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 
 /// Result of a deal import operation
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -34,6 +37,21 @@ pub struct FileImportResult {
     pub filename: String,
     pub success: bool,
     pub message: String,
+    /// Machine-readable code of the failure, when it has one (e.g. "invalid_account")
+    #[serde(default)]
+    pub code: Option<String>,
+    /// Changes applied to make the file match the deals schema (coerce mode only)
+    #[serde(default)]
+    pub coercions: Vec<SchemaCoercion>,
+    /// Every schema and data problem found, when the file was validated
+    #[serde(default)]
+    pub validation: Option<ValidationReport>,
+    /// Findings of the semantic checks, when the file passed the schema validation
+    #[serde(default)]
+    pub semantic: Option<SemanticReport>,
+    /// Rows added, updated and left unchanged (append and upsert imports only)
+    #[serde(default)]
+    pub merge: Option<MergeStats>,
 }
 
 impl FileImportResult {
@@ -42,6 +60,11 @@ impl FileImportResult {
             filename: filename.into(),
             success: true,
             message: message.into(),
+            code: None,
+            coercions: Vec::new(),
+            validation: None,
+            semantic: None,
+            merge: None,
         }
     }
 
@@ -50,8 +73,404 @@ impl FileImportResult {
             filename: filename.into(),
             success: false,
             message: message.into(),
+            code: None,
+            coercions: Vec::new(),
+            validation: None,
+            semantic: None,
+            merge: None,
         }
     }
+
+    /// Failed import carrying the message and code of an error
+    pub fn from_error(filename: impl Into<String>, error: AppError) -> Self {
+        Self {
+            code: error.code,
+            ..Self::error(filename, error.message)
+        }
+    }
+
+    /// Failed import carrying the validation report that rejected the file
+    pub fn invalid(filename: String, validation: ValidationReport) -> Self {
+        Self::error(filename, validation.summary()).with_validation(validation)
+    }
+
+    pub fn with_coercions(mut self, coercions: Vec<SchemaCoercion>) -> Self {
+        self.coercions = coercions;
+        self
+    }
+
+    pub fn with_validation(mut self, validation: ValidationReport) -> Self {
+        self.validation = Some(validation);
+        self
+    }
+
+    pub fn with_semantic(mut self, semantic: SemanticReport) -> Self {
+        self.semantic = Some(semantic);
+        self
+    }
+
+    pub fn with_merge(mut self, merge: Option<MergeStats>) -> Self {
+        self.merge = merge;
+        self
+    }
+}
+
+/// Progress of a batch import, reported after each file
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DealImportProgress {
+    /// Files processed so far, including this one
+    pub processed: usize,
+    pub total: usize,
+    /// The file just processed
+    pub filename: String,
+    pub success: bool,
+}
+
+/// What happens to the stored deals of an account when a file for it is imported
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ImportMode {
+    /// The imported file replaces the stored deals
+    #[default]
+    Replace,
+    /// Deals whose ticket is not stored yet are added; stored deals are never modified
+    Append,
+    /// Deals are added, or replace the stored deal with the same ticket
+    Upsert,
+}
+
+/// Outcome of merging imported deals into the stored deals of an account
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MergeStats {
+    /// Deals whose ticket was not stored
+    pub rows_added: usize,
+    /// Stored deals replaced by an imported deal with different values
+    pub rows_updated: usize,
+    /// Stored deals kept as they were
+    pub rows_unchanged: usize,
+}
+
+/// Kind of problem found while validating a deal file
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ValidationIssueKind {
+    /// The file is missing or not valid Parquet
+    UnreadableFile,
+    MissingColumn,
+    UnexpectedColumn,
+    WrongType,
+    NullValues,
+    /// Values that cannot be converted to the schema type (coerce mode)
+    UnconvertibleValues,
+}
+
+/// One problem found while validating a deal file
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ValidationIssue {
+    pub kind: ValidationIssueKind,
+    /// Affected column (None for file-level problems)
+    pub column: Option<String>,
+    /// Expected data type
+    pub expected: Option<String>,
+    /// Actual data type
+    pub actual: Option<String>,
+    /// Number of null values (null_values issues)
+    pub null_count: Option<usize>,
+    /// Number of values that cannot be converted (unconvertible_values issues)
+    pub invalid_count: Option<usize>,
+    /// First affected row indices (0-based)
+    pub sample_rows: Vec<usize>,
+    pub message: String,
+}
+
+impl ValidationIssue {
+    pub fn new(kind: ValidationIssueKind, column: Option<&str>, message: impl Into<String>) -> Self {
+        Self {
+            kind,
+            column: column.map(|column| column.to_string()),
+            expected: None,
+            actual: None,
+            null_count: None,
+            invalid_count: None,
+            sample_rows: Vec::new(),
+            message: message.into(),
+        }
+    }
+}
+
+/// All problems found in a deal file in one pass
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ValidationReport {
+    pub row_count: usize,
+    pub issues: Vec<ValidationIssue>,
+}
+
+impl ValidationReport {
+    /// Report of a file that could not be read at all
+    pub fn unreadable(message: impl Into<String>) -> Self {
+        Self {
+            row_count: 0,
+            issues: vec![ValidationIssue::new(ValidationIssueKind::UnreadableFile, None, message)],
+        }
+    }
+
+    pub fn is_valid(&self) -> bool {
+        self.issues.is_empty()
+    }
+
+    /// One-line description of the issues for messages
+    pub fn summary(&self) -> String {
+        match self.issues.as_slice() {
+            [] => "No validation issues".to_string(),
+            [issue] => issue.message.clone(),
+            issues => format!(
+                "{} validation issues: {}",
+                issues.len(),
+                issues
+                    .iter()
+                    .map(|issue| issue.message.as_str())
+                    .collect::<Vec<_>>()
+                    .join("; ")
+            ),
+        }
+    }
+}
+
+/// How strictly an imported Parquet file must match the deals schema
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ValidationMode {
+    /// Exact column names and types, no nulls (the file is stored as is)
+    #[default]
+    Strict,
+    /// Compatible types are upcast and fillable nulls defaulted; a normalized file is stored
+    Coerce,
+}
+
+/// What coerce mode does with columns that are not part of the deals schema
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ExtraColumnsPolicy {
+    #[default]
+    Drop,
+    /// Stored after the schema columns; deal readers and insights ignore them
+    Keep,
+}
+
+/// Validation policy of a deal import
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ValidationPolicy {
+    #[serde(default)]
+    pub mode: ValidationMode,
+    #[serde(default)]
+    pub extra_columns: ExtraColumnsPolicy,
+    /// Severities of the semantic checks run once the file matches the schema
+    #[serde(default)]
+    pub semantic: SemanticConfig,
+}
+
+/// Severity of a semantic finding
+/// Errors reject an import, warnings are only reported
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Severity {
+    Error,
+    Warning,
+}
+
+/// Semantic check run on deals that already match the schema
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SemanticRule {
+    /// The same ticket appears on several deals
+    DuplicateTicket,
+    /// A buy or sell deal has a zero, negative or NaN volume
+    InvalidVolume,
+    /// `type` is not an MT5 deal type (0-17)
+    UnknownType,
+    /// `entry` is not an MT5 deal entry (0-3)
+    UnknownEntry,
+    /// `reason` is not an MT5 deal reason (0-10)
+    UnknownReason,
+    /// `time` differs from `time_msc / 1000`
+    TimeMismatch,
+    /// An exit deal whose position has no entry deal in the file
+    UnmatchedExit,
+}
+
+impl SemanticRule {
+    pub const ALL: &'static [SemanticRule] = &[
+        SemanticRule::DuplicateTicket,
+        SemanticRule::InvalidVolume,
+        SemanticRule::UnknownType,
+        SemanticRule::UnknownEntry,
+        SemanticRule::UnknownReason,
+        SemanticRule::TimeMismatch,
+        SemanticRule::UnmatchedExit,
+    ];
+
+    /// Severity used when the configuration does not override it
+    /// Histories exported from a date range often start with open positions, so exits
+    /// without an entry are only warnings
+    pub fn default_severity(self) -> Severity {
+        match self {
+            SemanticRule::DuplicateTicket | SemanticRule::InvalidVolume | SemanticRule::UnknownEntry => {
+                Severity::Error
+            }
+            SemanticRule::UnknownType
+            | SemanticRule::UnknownReason
+            | SemanticRule::TimeMismatch
+            | SemanticRule::UnmatchedExit => Severity::Warning,
+        }
+    }
+}
+
+/// Severity overrides of the semantic checks
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SemanticConfig {
+    /// Rule -> severity; rules not listed use their default severity
+    #[serde(default)]
+    pub severities: BTreeMap<SemanticRule, Severity>,
+}
+
+impl SemanticConfig {
+    pub fn severity(&self, rule: SemanticRule) -> Severity {
+        self.severities
+            .get(&rule)
+            .copied()
+            .unwrap_or_else(|| rule.default_severity())
+    }
+}
+
+/// Deals breaking one semantic rule
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SemanticFinding {
+    pub rule: SemanticRule,
+    pub severity: Severity,
+    /// Number of offending deals
+    pub count: usize,
+    /// First offending row indices (0-based)
+    pub sample_rows: Vec<usize>,
+    /// Tickets of the sampled rows
+    pub sample_tickets: Vec<i64>,
+    pub message: String,
+}
+
+/// Findings of the semantic checks of a deal file
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SemanticReport {
+    pub row_count: usize,
+    pub findings: Vec<SemanticFinding>,
+}
+
+impl SemanticReport {
+    pub fn has_errors(&self) -> bool {
+        self.findings.iter().any(|finding| finding.severity == Severity::Error)
+    }
+
+    /// One-line description of the error findings for messages
+    pub fn error_summary(&self) -> String {
+        self.findings
+            .iter()
+            .filter(|finding| finding.severity == Severity::Error)
+            .map(|finding| finding.message.as_str())
+            .collect::<Vec<_>>()
+            .join("; ")
+    }
+}
+
+/// Semantic report of a stored account
+/// Accounts whose deals cannot be read or no longer match the schema carry an error instead
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AccountSemanticReport {
+    pub account: String,
+    pub report: Option<SemanticReport>,
+    /// Schema problems of the stored file, when it does not match the schema
+    #[serde(default)]
+    pub validation: Option<ValidationReport>,
+    /// Why the semantic checks could not run
+    #[serde(default)]
+    pub error: Option<String>,
+}
+
+/// Kind of change applied while normalizing a deal file
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CoercionKind {
+    /// Column converted to the schema type (e.g. Int32 -> Int64)
+    Cast,
+    /// Null values replaced by the column default
+    FillNull,
+    /// Missing column created from defaults (or derived, like `time_msc` from `time`)
+    AddColumn,
+    DropColumn,
+    KeepColumn,
+}
+
+/// One change applied to a column while normalizing a deal file
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SchemaCoercion {
+    pub column: String,
+    pub kind: CoercionKind,
+    pub detail: String,
+    /// Rows whose value changed (all rows for casts and added columns)
+    pub rows_affected: usize,
+}
+
+/// Warning about a statement row that could not be (fully) mapped to a deal
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RowWarning {
+    /// 1-based row of the source (report table or worksheet row, CSV line, JSON record)
+    pub row: usize,
+    pub message: String,
+}
+
+/// Result of importing deals from a foreign format (MT5 report, mapped CSV/JSON export)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StatementImportResult {
+    pub filename: String,
+    pub success: bool,
+    pub message: String,
+    /// Machine-readable code of the failure, when it has one (e.g. "invalid_account")
+    #[serde(default)]
+    pub code: Option<String>,
+    /// Account the deals were stored under (deals file name without extension)
+    pub account: Option<String>,
+    pub deals_imported: usize,
+    pub rows_skipped: usize,
+    pub warnings: Vec<RowWarning>,
+    /// Findings of the semantic checks of the mapped deals
+    #[serde(default)]
+    pub semantic: Option<SemanticReport>,
+    /// Rows added, updated and left unchanged (append and upsert imports only)
+    #[serde(default)]
+    pub merge: Option<MergeStats>,
+}
+
+impl StatementImportResult {
+    /// Marks the import as failed with the message and code of an error
+    pub fn fail(&mut self, error: AppError) {
+        self.success = false;
+        self.message = error.message;
+        self.code = error.code;
+    }
+}
+
+/// Dry run of a mapped CSV/JSON import
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ImportPreview {
+    /// Column names found in the source
+    pub source_columns: Vec<String>,
+    /// Deal fields with neither a source column nor a default (imported as zero/empty)
+    pub unmapped_fields: Vec<String>,
+    pub total_rows: usize,
+    /// Number of rows that would be imported
+    pub valid_rows: usize,
+    /// First mapped deals
+    pub deals: Vec<Deal>,
+    /// Rows that cannot be mapped (capped; see `error_count` for the total)
+    pub errors: Vec<RowWarning>,
+    pub error_count: usize,
 }
 
 /// Deal record from Parquet file
@@ -77,6 +496,109 @@ pub struct Deal {
     pub external_id: String,
 }
 
+/// MT5 deal types (`ENUM_DEAL_TYPE`) stored in the `type` column
+pub const DEAL_TYPE_BUY: i64 = 0;
+pub const DEAL_TYPE_SELL: i64 = 1;
+pub const DEAL_TYPE_BALANCE: i64 = 2;
+
+/// MT5 deal entries (`ENUM_DEAL_ENTRY`) stored in the `entry` column
+pub const DEAL_ENTRY_IN: i64 = 0;
+pub const DEAL_ENTRY_OUT: i64 = 1;
+pub const DEAL_ENTRY_INOUT: i64 = 2;
+pub const DEAL_ENTRY_OUT_BY: i64 = 3;
+
+/// MT5 deal reasons (`ENUM_DEAL_REASON`) stored in the `reason` column
+pub const DEAL_REASON_CLIENT: i64 = 0;
+pub const DEAL_REASON_EXPERT: i64 = 3;
+pub const DEAL_REASON_SL: i64 = 4;
+pub const DEAL_REASON_TP: i64 = 5;
+pub const DEAL_REASON_SO: i64 = 6;
+
+/// Remaining volume below this is treated as a fully closed position
+pub const VOLUME_EPSILON: f64 = 1e-9;
+
+/// Role of a buy/sell deal in its position
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DealRole {
+    /// Opens the position
+    Entry,
+    /// Adds to the open volume
+    ScaleIn,
+    /// Reduces the open volume without closing it
+    PartialClose,
+    /// Closes the remaining volume
+    Exit,
+    /// In/out deal closing the position and opening the remaining volume the other way
+    Reversal,
+}
+
+/// Direction of a position, from its first deal
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PositionDirection {
+    Buy,
+    Sell,
+}
+
+impl PositionDirection {
+    /// 1 for buy positions, -1 for sell positions
+    pub fn sign(self) -> f64 {
+        match self {
+            Self::Buy => 1.0,
+            Self::Sell => -1.0,
+        }
+    }
+}
+
+/// Whether a position still has volume open
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum PositionStatus {
+    Open,
+    Closed,
+}
+
+/// Position rebuilt from the buy/sell deals sharing a `position_id`
+/// A reversal splits a `position_id` into a closed position and a reversed one opened by
+/// the remainder of the in/out deal
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Position {
+    pub position_id: i64,
+    pub symbol: String,
+    pub direction: PositionDirection,
+    pub status: PositionStatus,
+    /// First entry time (Unix seconds)
+    pub open_time: i64,
+    /// Last exit time (Unix seconds), when closed
+    pub close_time: Option<i64>,
+    /// Seconds between the first entry and the last exit, when closed
+    pub duration_seconds: Option<i64>,
+    /// Volume weighted price of the entry deals
+    pub entry_price: f64,
+    /// Volume weighted price of the exit deals, when any
+    pub exit_price: Option<f64>,
+    /// Total volume of the entry deals
+    pub entry_volume: f64,
+    /// Total volume of the exit deals
+    pub exit_volume: f64,
+    /// Largest volume open at once
+    pub max_volume: f64,
+    /// Volume still open (0 when closed)
+    pub open_volume: f64,
+    /// Exit deals that left part of the volume open
+    pub partial_closes: usize,
+    pub deal_count: usize,
+    /// True when the position was opened by the remainder of an in/out (reversal) deal
+    pub reversed: bool,
+    /// Sum of the deal profits
+    pub gross_profit: f64,
+    pub commission: f64,
+    pub swap: f64,
+    pub fee: f64,
+    /// Gross profit plus commission, swap and fee
+    pub net_profit: f64,
+}
+
 /// Expected schema for deals Parquet files
 pub mod deals_schema {
     use polars::prelude::DataType;
@@ -0,0 +1,391 @@
+//! Reconstruction of positions from their deals
+
+use crate::features::deals::model::{
+    Deal, DealRole, Position, PositionDirection, PositionStatus, DEAL_ENTRY_IN, DEAL_ENTRY_INOUT,
+    DEAL_TYPE_BUY, DEAL_TYPE_SELL, VOLUME_EPSILON,
+};
+use std::collections::BTreeMap;
+
+/// Service for rebuilding positions from deals
+pub struct PositionService;
+
+impl PositionService {
+    /// Groups the buy/sell deals by `position_id` and rebuilds the positions of each group
+    /// Deal times are server times; `offset_seconds` (the account's UTC offset) is subtracted
+    /// from the position times. Exits without an entry deal (history starting mid-position)
+    /// are skipped. A reversal closes the position and opens the rest of the in/out deal's
+    /// volume as a new position with the same `position_id`. Positions are returned by
+    /// `position_id`, then in time order
+    pub fn build_positions(deals: &[Deal], offset_seconds: i64) -> Vec<Position> {
+        let mut by_position: BTreeMap<i64, Vec<&Deal>> = BTreeMap::new();
+        for deal in deals {
+            if deal.r#type == DEAL_TYPE_BUY || deal.r#type == DEAL_TYPE_SELL {
+                by_position.entry(deal.position_id).or_default().push(deal);
+            }
+        }
+
+        by_position
+            .into_iter()
+            .flat_map(|(position_id, mut deals)| {
+                deals.sort_by_key(|deal| (deal.time_msc, deal.time, deal.ticket));
+                Self::build_position(position_id, &deals, offset_seconds)
+            })
+            .collect()
+    }
+
+    /// Roles of the deals of one position, given in time order
+    pub fn deal_roles(deals: &[&Deal]) -> Vec<DealRole> {
+        let mut open_volume: f64 = 0.0;
+        deals
+            .iter()
+            .map(|deal| {
+                let role = if deal.entry == DEAL_ENTRY_INOUT && open_volume > VOLUME_EPSILON {
+                    DealRole::Reversal
+                } else if deal.entry == DEAL_ENTRY_IN || deal.entry == DEAL_ENTRY_INOUT {
+                    if open_volume > VOLUME_EPSILON {
+                        DealRole::ScaleIn
+                    } else {
+                        DealRole::Entry
+                    }
+                } else if open_volume - deal.volume > VOLUME_EPSILON {
+                    DealRole::PartialClose
+                } else {
+                    DealRole::Exit
+                };
+
+                open_volume = match role {
+                    DealRole::Entry => deal.volume,
+                    DealRole::ScaleIn => open_volume + deal.volume,
+                    DealRole::PartialClose => open_volume - deal.volume,
+                    DealRole::Exit => 0.0,
+                    DealRole::Reversal => (deal.volume - open_volume).max(0.0),
+                };
+                role
+            })
+            .collect()
+    }
+
+    /// Rebuilds the positions of one `position_id` from its deals in time order
+    fn build_position(position_id: i64, deals: &[&Deal], offset_seconds: i64) -> Vec<Position> {
+        let mut positions = Vec::new();
+        let mut leg: Option<Leg> = None;
+
+        for (deal, role) in deals.iter().copied().zip(Self::deal_roles(deals)) {
+            match (role, leg.take()) {
+                (DealRole::Entry, _) => {
+                    let mut opened = Leg::new(position_id, deal, false);
+                    opened.add_entry(deal.price, deal.volume);
+                    opened.book(deal, 1.0, deal.profit);
+                    leg = Some(opened);
+                }
+                (DealRole::ScaleIn, Some(mut current)) => {
+                    current.add_entry(deal.price, deal.volume);
+                    current.book(deal, 1.0, deal.profit);
+                    leg = Some(current);
+                }
+                (DealRole::PartialClose, Some(mut current)) => {
+                    current.add_exit(deal.price, deal.volume);
+                    current.book(deal, 1.0, deal.profit);
+                    current.partial_closes += 1;
+                    leg = Some(current);
+                }
+                (DealRole::Exit, Some(mut current)) => {
+                    current.add_exit(deal.price, deal.volume);
+                    current.book(deal, 1.0, deal.profit);
+                    positions.push(current.finish(Some(deal.time), offset_seconds));
+                }
+                (DealRole::Reversal, Some(mut current)) => {
+                    // The closed volume takes the deal's profit and its share of the charges,
+                    // the remainder opens the reversed position
+                    let closed_volume = current.open_volume;
+                    let share = (closed_volume / deal.volume).min(1.0);
+                    current.add_exit(deal.price, closed_volume);
+                    current.book(deal, share, deal.profit);
+                    positions.push(current.finish(Some(deal.time), offset_seconds));
+
+                    let remainder = deal.volume - closed_volume;
+                    if remainder > VOLUME_EPSILON {
+                        let mut reversed = Leg::new(position_id, deal, true);
+                        reversed.add_entry(deal.price, remainder);
+                        reversed.book(deal, 1.0 - share, 0.0);
+                        leg = Some(reversed);
+                    }
+                }
+                // Deals acting on volume opened before the history starts
+                (_, None) => {}
+            }
+        }
+
+        if let Some(current) = leg {
+            positions.push(current.finish(None, offset_seconds));
+        }
+        positions
+    }
+}
+
+/// Position being rebuilt while walking its deals (times are server times)
+struct Leg {
+    position_id: i64,
+    symbol: String,
+    direction: PositionDirection,
+    open_time: i64,
+    open_price: f64,
+    entry_volume: f64,
+    entry_value: f64,
+    exit_volume: f64,
+    exit_value: f64,
+    open_volume: f64,
+    max_volume: f64,
+    partial_closes: usize,
+    deal_count: usize,
+    reversed: bool,
+    gross_profit: f64,
+    commission: f64,
+    swap: f64,
+    fee: f64,
+}
+
+impl Leg {
+    /// Position opened by `deal`, in the deal's direction
+    fn new(position_id: i64, deal: &Deal, reversed: bool) -> Self {
+        Self {
+            position_id,
+            symbol: deal.symbol.clone(),
+            direction: if deal.r#type == DEAL_TYPE_BUY {
+                PositionDirection::Buy
+            } else {
+                PositionDirection::Sell
+            },
+            open_time: deal.time,
+            open_price: deal.price,
+            entry_volume: 0.0,
+            entry_value: 0.0,
+            exit_volume: 0.0,
+            exit_value: 0.0,
+            open_volume: 0.0,
+            max_volume: 0.0,
+            partial_closes: 0,
+            deal_count: 0,
+            reversed,
+            gross_profit: 0.0,
+            commission: 0.0,
+            swap: 0.0,
+            fee: 0.0,
+        }
+    }
+
+    fn add_entry(&mut self, price: f64, volume: f64) {
+        self.entry_volume += volume;
+        self.entry_value += volume * price;
+        self.open_volume += volume;
+        self.max_volume = self.max_volume.max(self.open_volume);
+    }
+
+    fn add_exit(&mut self, price: f64, volume: f64) {
+        self.exit_volume += volume;
+        self.exit_value += volume * price;
+        self.open_volume = (self.open_volume - volume).max(0.0);
+    }
+
+    /// Counts a deal with `share` of its commission, swap and fee and the given profit
+    fn book(&mut self, deal: &Deal, share: f64, profit: f64) {
+        self.deal_count += 1;
+        self.gross_profit += profit;
+        self.commission += deal.commission * share;
+        self.swap += deal.swap * share;
+        self.fee += deal.fee * share;
+    }
+
+    /// Position closed at `close_time` (server time), or still open without one
+    fn finish(self, close_time: Option<i64>, offset_seconds: i64) -> Position {
+        let open_time = self.open_time - offset_seconds;
+        let close_time = close_time.map(|time| time - offset_seconds);
+
+        Position {
+            position_id: self.position_id,
+            symbol: self.symbol,
+            direction: self.direction,
+            status: if close_time.is_some() {
+                PositionStatus::Closed
+            } else {
+                PositionStatus::Open
+            },
+            open_time,
+            close_time,
+            duration_seconds: close_time.map(|close_time| close_time - open_time),
+            // A zero-volume entry (e.g. a correction deal) has no weighted price
+            entry_price: if self.entry_volume > 0.0 {
+                self.entry_value / self.entry_volume
+            } else {
+                self.open_price
+            },
+            exit_price: (self.exit_volume > 0.0).then(|| self.exit_value / self.exit_volume),
+            entry_volume: self.entry_volume,
+            exit_volume: self.exit_volume,
+            max_volume: self.max_volume,
+            open_volume: if close_time.is_some() { 0.0 } else { self.open_volume },
+            partial_closes: self.partial_closes,
+            deal_count: self.deal_count,
+            reversed: self.reversed,
+            gross_profit: self.gross_profit,
+            commission: self.commission,
+            swap: self.swap,
+            fee: self.fee,
+            net_profit: self.gross_profit + self.commission + self.swap + self.fee,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::features::deals::model::{DEAL_ENTRY_OUT, DEAL_TYPE_BALANCE};
+
+    fn deal(ticket: i64, time: i64, r#type: i64, entry: i64, volume: f64, price: f64, profit: f64) -> Deal {
+        Deal {
+            ticket,
+            order: ticket,
+            time,
+            time_msc: time * 1000,
+            r#type,
+            entry,
+            magic: 0,
+            position_id: 1,
+            reason: 0,
+            volume,
+            price,
+            commission: -1.0,
+            swap: 0.0,
+            profit,
+            fee: 0.0,
+            symbol: "EURUSD".to_string(),
+            comment: String::new(),
+            external_id: String::new(),
+        }
+    }
+
+    fn assert_close(actual: f64, expected: f64) {
+        assert!((actual - expected).abs() < 1e-9, "{} != {}", actual, expected);
+    }
+
+    #[test]
+    fn rebuilds_a_round_trip() {
+        let deals = [
+            deal(1, 1_000, DEAL_TYPE_BUY, DEAL_ENTRY_IN, 1.0, 1.1000, 0.0),
+            deal(2, 4_600, DEAL_TYPE_SELL, DEAL_ENTRY_OUT, 1.0, 1.1050, 50.0),
+        ];
+        let positions = PositionService::build_positions(&deals, 600);
+
+        assert_eq!(positions.len(), 1);
+        let position = &positions[0];
+        assert_eq!(position.direction, PositionDirection::Buy);
+        assert_eq!(position.status, PositionStatus::Closed);
+        assert_eq!((position.open_time, position.close_time), (400, Some(4_000)));
+        assert_eq!(position.duration_seconds, Some(3_600));
+        assert_close(position.entry_price, 1.1000);
+        assert_eq!(position.exit_price, Some(1.1050));
+        assert_eq!((position.partial_closes, position.deal_count), (0, 2));
+        assert!(!position.reversed);
+        assert_close(position.net_profit, 48.0);
+    }
+
+    #[test]
+    fn keeps_a_partially_closed_position_open() {
+        let deals = [
+            deal(1, 1_000, DEAL_TYPE_SELL, DEAL_ENTRY_IN, 1.0, 1.2000, 0.0),
+            deal(2, 2_000, DEAL_TYPE_SELL, DEAL_ENTRY_IN, 1.0, 1.2100, 0.0),
+            deal(3, 3_000, DEAL_TYPE_BUY, DEAL_ENTRY_OUT, 0.5, 1.1900, 75.0),
+            deal(4, 4_000, DEAL_TYPE_BALANCE, DEAL_ENTRY_IN, 0.0, 0.0, 1_000.0),
+        ];
+        let positions = PositionService::build_positions(&deals, 0);
+
+        assert_eq!(positions.len(), 1);
+        let position = &positions[0];
+        assert_eq!(position.direction, PositionDirection::Sell);
+        assert_eq!(position.status, PositionStatus::Open);
+        assert_eq!(position.close_time, None);
+        assert_close(position.entry_price, 1.2050);
+        assert_close(position.entry_volume, 2.0);
+        assert_close(position.exit_volume, 0.5);
+        assert_close(position.max_volume, 2.0);
+        assert_close(position.open_volume, 1.5);
+        assert_eq!((position.partial_closes, position.deal_count), (1, 3));
+        assert_close(position.net_profit, 72.0);
+    }
+
+    #[test]
+    fn splits_a_reversal_into_two_positions() {
+        let deals = [
+            deal(1, 1_000, DEAL_TYPE_BUY, DEAL_ENTRY_IN, 1.0, 1.1000, 0.0),
+            deal(2, 2_000, DEAL_TYPE_SELL, DEAL_ENTRY_INOUT, 3.0, 1.1020, 20.0),
+            deal(3, 3_000, DEAL_TYPE_BUY, DEAL_ENTRY_OUT, 2.0, 1.1000, 40.0),
+        ];
+        let positions = PositionService::build_positions(&deals, 0);
+
+        assert_eq!(positions.len(), 2);
+        let (closed, reversed) = (&positions[0], &positions[1]);
+
+        assert_eq!(closed.direction, PositionDirection::Buy);
+        assert_eq!(closed.status, PositionStatus::Closed);
+        assert_eq!(closed.close_time, Some(2_000));
+        assert_close(closed.entry_volume, 1.0);
+        assert_close(closed.exit_volume, 1.0);
+        assert_eq!(closed.exit_price, Some(1.1020));
+        assert!(!closed.reversed);
+        // Entry commission plus a third of the in/out deal's
+        assert_close(closed.commission, -1.0 - 1.0 / 3.0);
+        assert_close(closed.gross_profit, 20.0);
+
+        assert_eq!(reversed.position_id, 1);
+        assert_eq!(reversed.direction, PositionDirection::Sell);
+        assert_eq!(reversed.status, PositionStatus::Closed);
+        assert_eq!((reversed.open_time, reversed.close_time), (2_000, Some(3_000)));
+        assert_close(reversed.entry_volume, 2.0);
+        assert_close(reversed.entry_price, 1.1020);
+        assert_close(reversed.exit_volume, 2.0);
+        assert_eq!(reversed.exit_price, Some(1.1000));
+        assert!(reversed.reversed);
+        assert_close(reversed.commission, -2.0 / 3.0 - 1.0);
+        assert_close(reversed.gross_profit, 40.0);
+        assert_eq!(reversed.deal_count, 2);
+    }
+
+    #[test]
+    fn prices_a_zero_volume_entry_at_its_deal() {
+        let deals = [
+            deal(1, 1_000, DEAL_TYPE_BUY, DEAL_ENTRY_IN, 0.0, 1.1000, 0.0),
+            deal(2, 2_000, DEAL_TYPE_SELL, DEAL_ENTRY_OUT, 0.0, 1.1050, 0.0),
+        ];
+        let positions = PositionService::build_positions(&deals, 0);
+
+        assert_eq!(positions.len(), 1);
+        let position = &positions[0];
+        assert_eq!(position.status, PositionStatus::Closed);
+        assert_close(position.entry_price, 1.1000);
+        assert_close(position.entry_volume, 0.0);
+        assert_eq!(position.exit_price, None);
+    }
+
+    #[test]
+    fn classifies_deal_roles() {
+        let deals = [
+            deal(1, 1_000, DEAL_TYPE_BUY, DEAL_ENTRY_IN, 1.0, 1.1000, 0.0),
+            deal(2, 2_000, DEAL_TYPE_BUY, DEAL_ENTRY_IN, 1.0, 1.1010, 0.0),
+            deal(3, 3_000, DEAL_TYPE_SELL, DEAL_ENTRY_OUT, 0.5, 1.1020, 0.0),
+            deal(4, 4_000, DEAL_TYPE_SELL, DEAL_ENTRY_INOUT, 2.5, 1.1030, 0.0),
+            deal(5, 5_000, DEAL_TYPE_BUY, DEAL_ENTRY_OUT, 1.0, 1.1000, 0.0),
+        ];
+        let deals: Vec<&Deal> = deals.iter().collect();
+
+        assert_eq!(
+            PositionService::deal_roles(&deals),
+            vec![
+                DealRole::Entry,
+                DealRole::ScaleIn,
+                DealRole::PartialClose,
+                DealRole::Reversal,
+                DealRole::Exit,
+            ]
+        );
+    }
+}
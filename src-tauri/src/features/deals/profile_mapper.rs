@@ -0,0 +1,527 @@
+//! Maps CSV/JSON deal exports to the deals schema using an import profile
+
+use crate::features::deals::import_profile::{ImportProfile, SourceFormat};
+use crate::features::deals::model::{deals_schema, Deal, RowWarning};
+use crate::shared::error::AppError;
+use chrono::{DateTime, NaiveDateTime};
+use serde_json::Value;
+use std::collections::BTreeSet;
+
+/// Fields a row cannot be mapped without
+const MANDATORY_FIELDS: &[&str] = &["ticket", "time", "type"];
+
+/// JSON records inspected when listing the source columns
+const COLUMN_SAMPLE_RECORDS: usize = 100;
+
+/// Records of the source file
+enum SourceRecords {
+    Csv {
+        headers: Vec<String>,
+        /// Records with their 1-based line number
+        rows: Vec<(usize, csv::StringRecord)>,
+    },
+    Json(Vec<Value>),
+}
+
+/// Deals mapped from a source file
+pub struct MappedDeals {
+    /// Column names found in the source
+    pub source_columns: Vec<String>,
+    /// Deal fields with neither a source column nor a default (filled with zero/empty values)
+    pub unmapped_fields: Vec<String>,
+    pub total_rows: usize,
+    pub deals: Vec<Deal>,
+    pub errors: Vec<RowWarning>,
+}
+
+/// Applies an import profile to CSV or JSON data
+pub struct ProfileMapper<'a> {
+    profile: &'a ImportProfile,
+}
+
+impl<'a> ProfileMapper<'a> {
+    pub fn new(profile: &'a ImportProfile) -> Result<Self, AppError> {
+        profile.validate()?;
+        Ok(Self { profile })
+    }
+
+    /// Maps every record; rows that cannot be mapped are reported in `errors`
+    pub fn map(&self, data: &[u8]) -> Result<MappedDeals, AppError> {
+        let records = self.read_records(data)?;
+        let source_columns = Self::source_columns(&records);
+
+        let unmapped_fields = deals_schema::REQUIRED_COLUMNS
+            .iter()
+            // time_msc is derived from time when absent
+            .filter(|field| **field != "time_msc" && !self.profile.defaults.contains_key(**field))
+            .filter(|field| !Self::has_column(&records, self.source_name(field)))
+            .map(|field| field.to_string())
+            .collect();
+
+        let mut mapped = MappedDeals {
+            source_columns,
+            unmapped_fields,
+            total_rows: 0,
+            deals: Vec::new(),
+            errors: Vec::new(),
+        };
+
+        let mut push = |row: usize, result: Result<Deal, String>| match result {
+            Ok(deal) => mapped.deals.push(deal),
+            Err(message) => mapped.errors.push(RowWarning { row, message }),
+        };
+
+        match &records {
+            SourceRecords::Csv { headers, rows } => {
+                for (line, record) in rows {
+                    let lookup = |column: &str| {
+                        headers
+                            .iter()
+                            .position(|header| header == column)
+                            .and_then(|index| record.get(index))
+                            .map(|value| Value::String(value.to_string()))
+                    };
+                    push(*line, self.map_record(lookup));
+                }
+                mapped.total_rows = rows.len();
+            }
+            SourceRecords::Json(items) => {
+                for (index, item) in items.iter().enumerate() {
+                    let lookup = |column: &str| Self::json_path(item, column).cloned();
+                    push(index + 1, self.map_record(lookup));
+                }
+                mapped.total_rows = items.len();
+            }
+        }
+
+        Ok(mapped)
+    }
+
+    /// Parses the source into records according to the profile format
+    fn read_records(&self, data: &[u8]) -> Result<SourceRecords, AppError> {
+        let data = data.strip_prefix(b"\xEF\xBB\xBF").unwrap_or(data);
+
+        match self.profile.format {
+            SourceFormat::Csv => {
+                let delimiter = self.profile.delimiter.unwrap_or(',');
+                if !delimiter.is_ascii() {
+                    return Err(AppError::new("CSV delimiter must be an ASCII character"));
+                }
+
+                let mut reader = csv::ReaderBuilder::new()
+                    .delimiter(delimiter as u8)
+                    .flexible(true)
+                    .trim(csv::Trim::All)
+                    .from_reader(data);
+                let headers = reader
+                    .headers()
+                    .map_err(|e| AppError::new(format!("Failed to read CSV header: {}", e)))?
+                    .iter()
+                    .map(|header| header.to_string())
+                    .collect();
+
+                let mut rows = Vec::new();
+                for record in reader.records() {
+                    let record = record.map_err(|e| AppError::new(format!("Failed to read CSV: {}", e)))?;
+                    let line = record.position().map(|position| position.line() as usize).unwrap_or_default();
+                    rows.push((line, record));
+                }
+                Ok(SourceRecords::Csv { headers, rows })
+            }
+            SourceFormat::Json => {
+                let document: Value = serde_json::from_slice(data)
+                    .map_err(|e| AppError::new(format!("Failed to parse JSON: {}", e)))?;
+                let records = match self.profile.records_path {
+                    Some(ref path) => Self::json_path(&document, path)
+                        .ok_or_else(|| AppError::new(format!("JSON has no '{}' field", path)))?,
+                    None => &document,
+                };
+                match records {
+                    Value::Array(items) => Ok(SourceRecords::Json(items.clone())),
+                    _ => Err(AppError::new("JSON records must be an array of objects")),
+                }
+            }
+        }
+    }
+
+    /// Column names of the source (JSON: top-level keys of the records)
+    fn source_columns(records: &SourceRecords) -> Vec<String> {
+        match records {
+            SourceRecords::Csv { headers, .. } => headers.clone(),
+            SourceRecords::Json(items) => items
+                .iter()
+                .take(COLUMN_SAMPLE_RECORDS)
+                .filter_map(|item| item.as_object())
+                .flat_map(|object| object.keys().cloned())
+                .collect::<BTreeSet<_>>()
+                .into_iter()
+                .collect(),
+        }
+    }
+
+    /// Whether the source has a column (JSON: a value at the path in any of the first records)
+    fn has_column(records: &SourceRecords, column: &str) -> bool {
+        match records {
+            SourceRecords::Csv { headers, .. } => headers.iter().any(|header| header == column),
+            SourceRecords::Json(items) => items
+                .iter()
+                .take(COLUMN_SAMPLE_RECORDS)
+                .any(|item| Self::json_path(item, column).is_some()),
+        }
+    }
+
+    /// Source column of a deal field
+    fn source_name<'f>(&'f self, field: &'f str) -> &'f str {
+        self.profile.columns.get(field).map(|s| s.as_str()).unwrap_or(field)
+    }
+
+    /// Builds a deal from one record; `lookup` returns the raw value of a source column
+    fn map_record(&self, lookup: impl Fn(&str) -> Option<Value>) -> Result<Deal, String> {
+        // Raw value of a field after value mapping, falling back to the profile default
+        let value = |field: &str| -> Option<Value> {
+            let raw = lookup(self.source_name(field)).filter(|value| !Self::is_empty(value));
+            let mapped = raw.map(|raw| match self.profile.value_maps.get(field) {
+                Some(map) => {
+                    let text = Self::text(&raw);
+                    map.get(&text)
+                        .or_else(|| {
+                            map.iter()
+                                .find(|(key, _)| key.eq_ignore_ascii_case(&text))
+                                .map(|(_, value)| value)
+                        })
+                        .cloned()
+                        .unwrap_or(raw)
+                }
+                None => raw,
+            });
+            mapped.or_else(|| self.profile.defaults.get(field).cloned())
+        };
+
+        let mut missing = Vec::new();
+        for field in MANDATORY_FIELDS {
+            if value(field).is_none() {
+                missing.push(*field);
+            }
+        }
+        if !missing.is_empty() {
+            return Err(format!("Missing {}", missing.join(", ")));
+        }
+
+        let int = |field: &str| -> Result<i64, String> {
+            value(field).map_or(Ok(0), |value| {
+                self.to_int(&value).ok_or_else(|| self.invalid_number(field, &value))
+            })
+        };
+        let float = |field: &str| -> Result<f64, String> {
+            value(field).map_or(Ok(0.0), |value| {
+                self.to_float(&value).ok_or_else(|| self.invalid_number(field, &value))
+            })
+        };
+        let string = |field: &str| value(field).map(|value| Self::text(&value)).unwrap_or_default();
+
+        let time_value = value("time").unwrap_or_default();
+        let time_msc = self
+            .to_time_msc(&time_value)
+            .ok_or_else(|| format!("Invalid time '{}'", Self::text(&time_value)))?;
+        let time_msc = match value("time_msc") {
+            Some(_) => int("time_msc")?,
+            None => time_msc,
+        };
+
+        Ok(Deal {
+            ticket: int("ticket")?,
+            order: int("order")?,
+            time: time_msc.div_euclid(1000),
+            time_msc,
+            r#type: int("type")?,
+            entry: int("entry")?,
+            magic: int("magic")?,
+            position_id: int("position_id")?,
+            reason: int("reason")?,
+            volume: float("volume")?,
+            price: float("price")?,
+            commission: float("commission")?,
+            swap: float("swap")?,
+            profit: float("profit")?,
+            fee: float("fee")?,
+            symbol: string("symbol"),
+            comment: string("comment"),
+            external_id: string("external_id"),
+        })
+    }
+
+    /// Milliseconds since the epoch of a `time` value according to the profile's time format
+    fn to_time_msc(&self, value: &Value) -> Option<i64> {
+        let format = self.profile.time_format.as_deref().unwrap_or("unix_seconds");
+        match format {
+            "unix_seconds" => self.to_float(value).map(|seconds| (seconds * 1000.0).round() as i64),
+            "unix_millis" => self.to_float(value).map(|millis| millis.round() as i64),
+            "rfc3339" => DateTime::parse_from_rfc3339(&Self::text(value))
+                .ok()
+                .map(|time| time.timestamp_millis()),
+            pattern if pattern.contains("%z") || pattern.contains("%:z") => {
+                DateTime::parse_from_str(&Self::text(value), pattern)
+                    .ok()
+                    .map(|time| time.timestamp_millis())
+            }
+            pattern => NaiveDateTime::parse_from_str(&Self::text(value), pattern)
+                .ok()
+                .map(|time| time.and_utc().timestamp_millis()),
+        }
+    }
+
+    /// Coerces a value to an integer (whole floats and numeric text included)
+    fn to_int(&self, value: &Value) -> Option<i64> {
+        match value {
+            Value::Number(number) => number
+                .as_i64()
+                .or_else(|| number.as_f64().filter(|f| f.fract() == 0.0).map(|f| f as i64)),
+            Value::Bool(flag) => Some(*flag as i64),
+            _ => {
+                let text = self.normalize_number(&Self::text(value))?;
+                text.parse::<i64>()
+                    .ok()
+                    .or_else(|| text.parse::<f64>().ok().filter(|f| f.fract() == 0.0).map(|f| f as i64))
+            }
+        }
+    }
+
+    /// Coerces a value to a float (numeric text included)
+    fn to_float(&self, value: &Value) -> Option<f64> {
+        match value {
+            Value::Number(number) => number.as_f64(),
+            Value::Bool(flag) => Some(*flag as i64 as f64),
+            _ => self
+                .normalize_number(&Self::text(value))?
+                .parse::<f64>()
+                .ok()
+                .filter(|f| f.is_finite()),
+        }
+    }
+
+    /// Removes grouping characters and turns the profile's decimal separator into '.'
+    /// Grouping characters (the other separator, `'` or spaces) are only accepted between
+    /// groups of three digits before the decimal separator; "0,01" or "1,5" with '.' as the
+    /// decimal separator give `None` instead of 1 or 15
+    fn normalize_number(&self, text: &str) -> Option<String> {
+        let decimal = self.decimal_separator();
+        let grouping = if decimal == '.' { ',' } else { '.' };
+        let is_grouping = |c: char| c == grouping || c == '\'' || c.is_whitespace();
+
+        let (integer, fraction) = match text.split_once(decimal) {
+            Some((integer, fraction)) => (integer, Some(fraction)),
+            None => (text, None),
+        };
+        if fraction.is_some_and(|fraction| fraction.contains(is_grouping)) {
+            return None;
+        }
+
+        let mut groups = integer.split(is_grouping);
+        let lead = groups.next().unwrap_or_default();
+        let mut normalized = lead.to_string();
+        for group in groups {
+            if group.len() != 3 || !group.chars().all(|c| c.is_ascii_digit()) {
+                return None;
+            }
+            normalized.push_str(group);
+        }
+        if normalized.len() > lead.len() {
+            let digits = lead.trim_start_matches(['-', '+']);
+            if digits.is_empty() || digits.len() > 3 || !digits.chars().all(|c| c.is_ascii_digit()) {
+                return None;
+            }
+        }
+
+        if let Some(fraction) = fraction {
+            normalized.push('.');
+            normalized.push_str(fraction);
+        }
+        Some(normalized)
+    }
+
+    fn decimal_separator(&self) -> char {
+        self.profile.decimal_separator.unwrap_or('.')
+    }
+
+    /// Row error for a value that is not a valid number
+    fn invalid_number(&self, field: &str, value: &Value) -> String {
+        let text = Self::text(value);
+        let decimal = self.decimal_separator();
+        let other = if decimal == '.' { ',' } else { '.' };
+        if text.contains(other) {
+            format!("Invalid {} '{}' (the decimal separator is '{}')", field, text, decimal)
+        } else {
+            format!("Invalid {} '{}'", field, text)
+        }
+    }
+
+    /// Text of a value (strings without quotes)
+    fn text(value: &Value) -> String {
+        match value {
+            Value::String(text) => text.trim().to_string(),
+            Value::Null => String::new(),
+            other => other.to_string(),
+        }
+    }
+
+    fn is_empty(value: &Value) -> bool {
+        match value {
+            Value::Null => true,
+            Value::String(text) => text.trim().is_empty(),
+            _ => false,
+        }
+    }
+
+    /// Follows a dotted path ("a.b") into a JSON value; a key containing dots is matched as a whole first
+    fn json_path<'v>(value: &'v Value, path: &str) -> Option<&'v Value> {
+        if let Some(found) = value.get(path) {
+            return Some(found);
+        }
+        path.split('.').try_fold(value, |current, key| current.get(key))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn profile(value: Value) -> ImportProfile {
+        serde_json::from_value(value).unwrap()
+    }
+
+    fn with_time_format(time_format: &str) -> ImportProfile {
+        profile(json!({ "name": "test", "time_format": time_format }))
+    }
+
+    fn with_decimal_separator(decimal_separator: char) -> ImportProfile {
+        profile(json!({ "name": "test", "decimal_separator": decimal_separator }))
+    }
+
+    #[test]
+    fn maps_csv_rows() {
+        let profile = profile(json!({
+            "name": "broker csv",
+            "delimiter": ";",
+            "decimal_separator": ",",
+            "time_format": "%Y.%m.%d %H:%M:%S",
+            "columns": {
+                "ticket": "Ticket",
+                "time": "Time",
+                "type": "Side",
+                "volume": "Lots",
+                "price": "Price",
+                "profit": "Profit",
+                "symbol": "Symbol"
+            },
+            "value_maps": { "type": { "Buy": 0, "Sell": 1 } }
+        }));
+        let csv = "Ticket;Time;Side;Lots;Price;Profit;Symbol\n\
+                   1;2024.01.15 10:00:00;BUY;0,5;1,1;0;EURUSD\n\
+                   2;2024.01.15 11:00:00;Sell;0,5;1,105;1.234,50;EURUSD\n\
+                   3;2024.01.15 12:00:00;Hold;0,5;1,1;0;EURUSD\n\
+                   4;;Buy;0,5;1,1;0;EURUSD\n";
+
+        let mapped = ProfileMapper::new(&profile).unwrap().map(csv.as_bytes()).unwrap();
+        assert_eq!(mapped.source_columns, vec!["Ticket", "Time", "Side", "Lots", "Price", "Profit", "Symbol"]);
+        assert!(mapped.unmapped_fields.contains(&"commission".to_string()));
+        assert!(!mapped.unmapped_fields.contains(&"ticket".to_string()));
+        assert_eq!(mapped.total_rows, 4);
+
+        assert_eq!(mapped.deals.len(), 2);
+        let deal = &mapped.deals[1];
+        assert_eq!((deal.ticket, deal.r#type), (2, 1));
+        assert_eq!((deal.time, deal.time_msc), (1_705_316_400, 1_705_316_400_000));
+        assert_eq!((deal.volume, deal.price, deal.profit), (0.5, 1.105, 1_234.5));
+        assert_eq!(deal.symbol, "EURUSD");
+        assert_eq!(mapped.deals[0].r#type, 0);
+
+        let errors: Vec<(usize, &str)> = mapped.errors.iter().map(|error| (error.row, error.message.as_str())).collect();
+        assert_eq!(errors, vec![(4, "Invalid type 'Hold'"), (5, "Missing time")]);
+    }
+
+    #[test]
+    fn maps_json_records_at_the_records_path() {
+        let profile = profile(json!({
+            "name": "broker json",
+            "format": "json",
+            "records_path": "data.deals",
+            "time_format": "unix_millis",
+            "columns": {
+                "ticket": "id",
+                "time": "ts",
+                "type": "side",
+                "volume": "lots",
+                "symbol": "details.symbol"
+            },
+            "value_maps": { "type": { "buy": 0, "sell": 1 } },
+            "defaults": { "comment": "imported" }
+        }));
+        let document = json!({
+            "data": {
+                "deals": [
+                    { "id": 1, "ts": 1_705_312_800_123_i64, "side": "buy", "lots": "0.5", "details": { "symbol": "EURUSD" } },
+                    { "id": 2, "ts": 1_705_316_400_000_i64, "side": "SELL", "lots": 1, "details": { "symbol": "GBPUSD" } }
+                ]
+            }
+        });
+
+        let mapped = ProfileMapper::new(&profile)
+            .unwrap()
+            .map(document.to_string().as_bytes())
+            .unwrap();
+        assert_eq!(mapped.source_columns, vec!["details", "id", "lots", "side", "ts"]);
+        assert!(mapped.errors.is_empty());
+
+        let first = &mapped.deals[0];
+        assert_eq!((first.ticket, first.r#type, first.volume), (1, 0, 0.5));
+        assert_eq!((first.time, first.time_msc), (1_705_312_800, 1_705_312_800_123));
+        assert_eq!((first.symbol.as_str(), first.comment.as_str()), ("EURUSD", "imported"));
+        let second = &mapped.deals[1];
+        assert_eq!((second.r#type, second.volume, second.symbol.as_str()), (1, 1.0, "GBPUSD"));
+
+        let mut missing_path = profile.clone();
+        missing_path.records_path = Some("deals".to_string());
+        assert!(ProfileMapper::new(&missing_path).unwrap().map(document.to_string().as_bytes()).is_err());
+    }
+
+    #[test]
+    fn reads_every_time_format() {
+        let cases = [
+            ("unix_seconds", json!(1_705_312_800.5), Some(1_705_312_800_500)),
+            ("unix_seconds", json!("1705312800"), Some(1_705_312_800_000)),
+            ("unix_millis", json!(1_705_312_800_123_i64), Some(1_705_312_800_123)),
+            ("rfc3339", json!("2024-01-15T12:00:00+02:00"), Some(1_705_312_800_000)),
+            ("rfc3339", json!("yesterday"), None),
+            ("%Y-%m-%d %H:%M:%S %z", json!("2024-01-15 12:00:00 +0200"), Some(1_705_312_800_000)),
+            ("%d/%m/%Y %H:%M", json!("15/01/2024 10:00"), Some(1_705_312_800_000)),
+            ("%d/%m/%Y %H:%M", json!("2024-01-15 10:00"), None),
+        ];
+        for (time_format, value, expected) in cases {
+            let profile = with_time_format(time_format);
+            let mapper = ProfileMapper::new(&profile).unwrap();
+            assert_eq!(mapper.to_time_msc(&value), expected, "{} {}", time_format, value);
+        }
+    }
+
+    #[test]
+    fn strips_grouping_only_between_groups_of_three_digits() {
+        let point = with_decimal_separator('.');
+        let point = ProfileMapper::new(&point).unwrap();
+        assert_eq!(point.normalize_number("1,234,567.5").as_deref(), Some("1234567.5"));
+        assert_eq!(point.normalize_number("-1,234").as_deref(), Some("-1234"));
+        assert_eq!(point.normalize_number("1 234.5").as_deref(), Some("1234.5"));
+        assert_eq!(point.normalize_number("0.01").as_deref(), Some("0.01"));
+        assert_eq!(point.normalize_number("0,01"), None);
+        assert_eq!(point.normalize_number("1,5"), None);
+        assert_eq!(point.normalize_number("1234,567"), None);
+        assert_eq!(point.normalize_number("1.234,5"), None);
+
+        let comma = with_decimal_separator(',');
+        let comma = ProfileMapper::new(&comma).unwrap();
+        assert_eq!(comma.normalize_number("1.234,5").as_deref(), Some("1234.5"));
+        assert_eq!(comma.normalize_number("1'234,5").as_deref(), Some("1234.5"));
+        assert_eq!(comma.normalize_number("1,5").as_deref(), Some("1.5"));
+        assert_eq!(comma.normalize_number("1.5"), None);
+    }
+}
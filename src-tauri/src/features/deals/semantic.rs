@@ -0,0 +1,301 @@
+//! Semantic checks of deals that already match the deals schema
+
+use crate::features::deals::model::{
+    SemanticConfig, SemanticFinding, SemanticReport, SemanticRule, DEAL_ENTRY_IN, DEAL_ENTRY_INOUT,
+    DEAL_ENTRY_OUT, DEAL_ENTRY_OUT_BY, DEAL_TYPE_BUY, DEAL_TYPE_SELL,
+};
+use crate::shared::error::AppError;
+use polars::prelude::*;
+use std::collections::HashSet;
+
+/// Row indices (and tickets) reported per finding
+const MAX_SAMPLE_ROWS: usize = 5;
+
+/// MT5 deal types: DEAL_TYPE_BUY (0) to DEAL_TAX (17)
+const MAX_DEAL_TYPE: i64 = 17;
+/// MT5 deal entries: DEAL_ENTRY_IN (0) to DEAL_ENTRY_OUT_BY (3)
+const MAX_DEAL_ENTRY: i64 = 3;
+/// MT5 deal reasons: DEAL_REASON_CLIENT (0) to DEAL_REASON_CORPORATE_ACTION (10)
+const MAX_DEAL_REASON: i64 = 10;
+
+/// Columns of a deals frame used by the checks
+/// Nulls are kept (NaN for `volume`) so the rules report them instead of reading them as 0
+struct DealColumns {
+    ticket: Vec<Option<i64>>,
+    time: Vec<Option<i64>>,
+    time_msc: Vec<Option<i64>>,
+    deal_type: Vec<Option<i64>>,
+    entry: Vec<Option<i64>>,
+    position_id: Vec<Option<i64>>,
+    reason: Vec<Option<i64>>,
+    volume: Vec<f64>,
+}
+
+/// Service for the semantic validation of deals
+pub struct SemanticValidator;
+
+impl SemanticValidator {
+    /// Runs every semantic rule on a frame matching the deals schema
+    /// Returns one finding per broken rule, with the configured severity. A null `type`,
+    /// `entry` or `reason` counts as unknown, a null time as a mismatch, a null volume as
+    /// invalid and a null `position_id` on an exit as unmatched
+    pub fn check(df: &DataFrame, config: &SemanticConfig) -> Result<SemanticReport, AppError> {
+        let columns = Self::columns(df)?;
+        let rows = columns.ticket.len();
+
+        let mut findings = Vec::new();
+        for &rule in SemanticRule::ALL {
+            let flags = Self::offending_rows(rule, &columns);
+            let count = flags.iter().filter(|flag| **flag).count();
+            if count == 0 {
+                continue;
+            }
+
+            let sample_rows: Vec<usize> = flags
+                .iter()
+                .enumerate()
+                .filter(|(_, flag)| **flag)
+                .map(|(index, _)| index)
+                .take(MAX_SAMPLE_ROWS)
+                .collect();
+            findings.push(SemanticFinding {
+                rule,
+                severity: config.severity(rule),
+                count,
+                sample_tickets: sample_rows.iter().map(|&row| columns.ticket[row].unwrap_or_default()).collect(),
+                sample_rows,
+                message: Self::message(rule, count),
+            });
+        }
+
+        Ok(SemanticReport {
+            row_count: rows,
+            findings,
+        })
+    }
+
+    /// Flags the rows breaking a rule
+    fn offending_rows(rule: SemanticRule, columns: &DealColumns) -> Vec<bool> {
+        let rows = columns.ticket.len();
+        let is_trade = |row: usize| matches!(columns.deal_type[row], Some(DEAL_TYPE_BUY | DEAL_TYPE_SELL));
+        let outside = |values: &[Option<i64>], max: i64| -> Vec<bool> {
+            values
+                .iter()
+                .map(|value| !value.is_some_and(|value| (0..=max).contains(&value)))
+                .collect()
+        };
+
+        match rule {
+            SemanticRule::DuplicateTicket => {
+                // The first deal of a ticket is kept as the original, later ones are flagged
+                let mut seen = HashSet::with_capacity(rows);
+                columns.ticket.iter().map(|ticket| !seen.insert(*ticket)).collect()
+            }
+            SemanticRule::InvalidVolume => (0..rows)
+                .map(|row| is_trade(row) && (columns.volume[row].is_nan() || columns.volume[row] <= 0.0))
+                .collect(),
+            SemanticRule::UnknownType => outside(&columns.deal_type, MAX_DEAL_TYPE),
+            SemanticRule::UnknownEntry => outside(&columns.entry, MAX_DEAL_ENTRY),
+            SemanticRule::UnknownReason => outside(&columns.reason, MAX_DEAL_REASON),
+            SemanticRule::TimeMismatch => columns
+                .time
+                .iter()
+                .zip(&columns.time_msc)
+                .map(|(time, time_msc)| match (time, time_msc) {
+                    (Some(time), Some(time_msc)) => *time != time_msc.div_euclid(1000),
+                    _ => true,
+                })
+                .collect(),
+            SemanticRule::UnmatchedExit => {
+                // An in or in/out (reversal) deal opens the position
+                let opened: HashSet<i64> = (0..rows)
+                    .filter(|&row| is_trade(row) && matches!(columns.entry[row], Some(DEAL_ENTRY_IN | DEAL_ENTRY_INOUT)))
+                    .filter_map(|row| columns.position_id[row])
+                    .collect();
+                (0..rows)
+                    .map(|row| {
+                        is_trade(row)
+                            && matches!(columns.entry[row], Some(DEAL_ENTRY_OUT | DEAL_ENTRY_OUT_BY))
+                            && !columns.position_id[row].is_some_and(|position_id| opened.contains(&position_id))
+                    })
+                    .collect()
+            }
+        }
+    }
+
+    fn message(rule: SemanticRule, count: usize) -> String {
+        match rule {
+            SemanticRule::DuplicateTicket => format!("{} deal(s) repeat the ticket of an earlier deal", count),
+            SemanticRule::InvalidVolume => format!("{} buy/sell deal(s) have a zero, negative or missing volume", count),
+            SemanticRule::UnknownType => format!("{} deal(s) have an unknown or missing type (expected 0-{})", count, MAX_DEAL_TYPE),
+            SemanticRule::UnknownEntry => {
+                format!("{} deal(s) have an unknown or missing entry (expected 0-{})", count, MAX_DEAL_ENTRY)
+            }
+            SemanticRule::UnknownReason => {
+                format!("{} deal(s) have an unknown or missing reason (expected 0-{})", count, MAX_DEAL_REASON)
+            }
+            SemanticRule::TimeMismatch => format!("{} deal(s) have a missing time or one that differs from time_msc / 1000", count),
+            SemanticRule::UnmatchedExit => {
+                format!("{} exit deal(s) have no entry deal with the same position_id", count)
+            }
+        }
+    }
+
+    fn columns(df: &DataFrame) -> Result<DealColumns, AppError> {
+        let ints = |name: &str| -> Result<Vec<Option<i64>>, AppError> {
+            let column = df
+                .column(name)
+                .and_then(|series| series.i64().cloned())
+                .map_err(|e| AppError::new(format!("Failed to read column '{}': {}", name, e)))?;
+            Ok(column.into_iter().collect())
+        };
+
+        let volume = df
+            .column("volume")
+            .and_then(|series| series.f64().cloned())
+            .map_err(|e| AppError::new(format!("Failed to read column 'volume': {}", e)))?
+            .into_iter()
+            .map(|value| value.unwrap_or(f64::NAN))
+            .collect();
+
+        Ok(DealColumns {
+            ticket: ints("ticket")?,
+            time: ints("time")?,
+            time_msc: ints("time_msc")?,
+            deal_type: ints("type")?,
+            entry: ints("entry")?,
+            position_id: ints("position_id")?,
+            reason: ints("reason")?,
+            volume,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::features::deals::model::{Deal, Severity, DEAL_TYPE_BALANCE};
+    use crate::features::deals::service::DealService;
+
+    fn deal(ticket: i64, r#type: i64, entry: i64, position_id: i64, volume: f64) -> Deal {
+        Deal {
+            ticket,
+            order: ticket,
+            time: 1_700_000_000 + ticket,
+            time_msc: (1_700_000_000 + ticket) * 1000 + 250,
+            r#type,
+            entry,
+            magic: 0,
+            position_id,
+            reason: 0,
+            volume,
+            price: 1.1,
+            commission: 0.0,
+            swap: 0.0,
+            profit: 0.0,
+            fee: 0.0,
+            symbol: "EURUSD".to_string(),
+            comment: String::new(),
+            external_id: String::new(),
+        }
+    }
+
+    /// An entry and its exit, which break no rule
+    fn round_trip() -> Vec<Deal> {
+        vec![
+            deal(1, DEAL_TYPE_BUY, DEAL_ENTRY_IN, 1, 1.0),
+            deal(2, DEAL_TYPE_SELL, DEAL_ENTRY_OUT, 1, 1.0),
+        ]
+    }
+
+    fn check(deals: &[Deal]) -> SemanticReport {
+        let df = DealService::deals_to_dataframe(deals).unwrap();
+        SemanticValidator::check(&df, &SemanticConfig::default()).unwrap()
+    }
+
+    /// The only finding of a report, with its sample rows
+    fn only_finding(report: &SemanticReport) -> (SemanticRule, Vec<usize>) {
+        assert_eq!(report.findings.len(), 1, "{:?}", report.findings);
+        let finding = &report.findings[0];
+        (finding.rule, finding.sample_rows.clone())
+    }
+
+    #[test]
+    fn accepts_consistent_deals() {
+        let mut deals = round_trip();
+        deals.push(deal(3, DEAL_TYPE_BALANCE, DEAL_ENTRY_IN, 0, 0.0));
+        assert!(check(&deals).findings.is_empty());
+    }
+
+    #[test]
+    fn flags_duplicate_tickets_after_the_first() {
+        let mut deals = round_trip();
+        deals.push(deal(2, DEAL_TYPE_SELL, DEAL_ENTRY_OUT, 1, 1.0));
+        let report = check(&deals);
+        assert_eq!(only_finding(&report), (SemanticRule::DuplicateTicket, vec![2]));
+        assert_eq!(report.findings[0].sample_tickets, vec![2]);
+    }
+
+    #[test]
+    fn flags_trades_without_a_positive_volume() {
+        let mut deals = round_trip();
+        deals[1].volume = 0.0;
+        deals.push(deal(3, DEAL_TYPE_BUY, DEAL_ENTRY_IN, 3, -1.0));
+        assert_eq!(only_finding(&check(&deals)), (SemanticRule::InvalidVolume, vec![1, 2]));
+    }
+
+    #[test]
+    fn flags_unknown_types() {
+        let mut deals = round_trip();
+        deals.push(deal(3, 18, DEAL_ENTRY_IN, 0, 0.0));
+        assert_eq!(only_finding(&check(&deals)), (SemanticRule::UnknownType, vec![2]));
+    }
+
+    #[test]
+    fn flags_unknown_entries() {
+        let mut deals = round_trip();
+        deals.push(deal(3, DEAL_TYPE_BALANCE, 4, 0, 0.0));
+        assert_eq!(only_finding(&check(&deals)), (SemanticRule::UnknownEntry, vec![2]));
+    }
+
+    #[test]
+    fn flags_unknown_reasons() {
+        let mut deals = round_trip();
+        deals[0].reason = 11;
+        assert_eq!(only_finding(&check(&deals)), (SemanticRule::UnknownReason, vec![0]));
+    }
+
+    #[test]
+    fn flags_times_that_differ_from_time_msc() {
+        let mut deals = round_trip();
+        deals[1].time += 3_600;
+        assert_eq!(only_finding(&check(&deals)), (SemanticRule::TimeMismatch, vec![1]));
+    }
+
+    #[test]
+    fn flags_exits_without_an_entry_as_warnings() {
+        let mut deals = round_trip();
+        deals.push(deal(3, DEAL_TYPE_BUY, DEAL_ENTRY_OUT, 7, 1.0));
+        let report = check(&deals);
+        assert_eq!(only_finding(&report), (SemanticRule::UnmatchedExit, vec![2]));
+        assert_eq!(report.findings[0].severity, Severity::Warning);
+    }
+
+    #[test]
+    fn reports_nulls_instead_of_reading_them_as_zero() {
+        let mut df = DealService::deals_to_dataframe(&round_trip()).unwrap();
+        df.with_column(Series::new("type", &[Some(DEAL_TYPE_BUY), None])).unwrap();
+        df.with_column(Series::new("time", &[None, Some(1_700_000_002_i64)])).unwrap();
+
+        let report = SemanticValidator::check(&df, &SemanticConfig::default()).unwrap();
+        let rules: Vec<(SemanticRule, Vec<usize>)> = report
+            .findings
+            .iter()
+            .map(|finding| (finding.rule, finding.sample_rows.clone()))
+            .collect();
+        assert_eq!(
+            rules,
+            vec![(SemanticRule::UnknownType, vec![1]), (SemanticRule::TimeMismatch, vec![0])]
+        );
+    }
+}
@@ -2,11 +2,45 @@
 
 use crate::shared::error::AppError;
 use crate::shared::utils::cache_dir;
-use crate::features::deals::model::{Deal, DealImportResult, FileImportResult};
+use crate::features::deals::model::{
+    AccountSemanticReport, Deal, DealImportProgress, DealImportResult, ExtraColumnsPolicy, FileImportResult,
+    ImportMode, ImportPreview, MergeStats, SemanticConfig, StatementImportResult, ValidationMode, ValidationPolicy,
+    ValidationReport,
+};
+use crate::features::deals::import_profile::{ImportProfile, ImportProfileStore};
+use crate::features::deals::profile_mapper::ProfileMapper;
+use crate::features::deals::statement::Mt5StatementParser;
+use crate::features::deals::merge::DealMerger;
+use crate::features::deals::semantic::SemanticValidator;
 use crate::features::deals::validator::ParquetValidator;
+use crate::features::accounts::account_id::AccountId;
+use crate::features::accounts::model::ResolvedAccount;
+use crate::features::accounts::service::AccountService;
 use polars::prelude::*;
-use std::path::Path;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
 use std::fs;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+
+/// Per-account locks of the deals files
+static ACCOUNT_LOCKS: Mutex<BTreeMap<String, Arc<Mutex<()>>>> = Mutex::new(BTreeMap::new());
+/// Numbers the temp directories of the imports of this process
+static IMPORT_COUNTER: AtomicU64 = AtomicU64::new(0);
+
+/// Mapped rows shown by an import preview when not specified, and the most allowed
+const DEFAULT_PREVIEW_ROWS: usize = 20;
+const MAX_PREVIEW_ROWS: usize = 1_000;
+/// Row errors returned by an import preview
+const MAX_PREVIEW_ERRORS: usize = 200;
+
+/// Content of a deals file to import
+enum DealFileSource<'a> {
+    /// File data sent by the frontend
+    Data(&'a [u8]),
+    /// File read directly from disk
+    Path(&'a Path),
+}
 
 /// Service for managing deal imports and operations
 pub struct DealService;
@@ -15,11 +49,57 @@ impl DealService {
     /// Validates and stores Parquet files for deals
     /// Accepts file data as Vec<u8> and original filenames
     /// For each file:
-    /// - Validates schema using ParquetValidator
-    /// - If valid, stores file in deals cache directory (keeping original name)
+    /// - Strict policy: validates the schema using ParquetValidator and, if valid, stores the
+    ///   file in the deals cache directory (keeping original name)
+    /// - Coerce policy: normalizes the file to the schema and stores the normalized file,
+    ///   reporting every coercion applied
+    /// - Either way, the semantic checks run on the valid deals; error findings reject the file
+    /// - The import mode decides whether the file replaces the stored deals of the account or is
+    ///   merged into them on `ticket` (append, upsert)
     /// Returns DealImportResult with overall status and per-file messages
     pub fn validate_and_store_files(
         files: Vec<(String, Vec<u8>)>,
+        policy: ValidationPolicy,
+        mode: ImportMode,
+    ) -> Result<DealImportResult, AppError> {
+        let sources = files
+            .iter()
+            .map(|(filename, data)| (filename.clone(), DealFileSource::Data(data)))
+            .collect();
+        Self::import_files(sources, &policy, mode, |_| {})
+    }
+
+    /// Imports Parquet deal files read directly from disk
+    /// Files are read in place one at a time, so only the file being validated is held in
+    /// memory; the account is the file name without extension. The files go through
+    /// the same validation and storage as `validate_and_store_files`, and `on_progress` is
+    /// called after each file
+    pub fn import_from_paths(
+        paths: &[String],
+        policy: ValidationPolicy,
+        mode: ImportMode,
+        on_progress: impl FnMut(&DealImportProgress),
+    ) -> Result<DealImportResult, AppError> {
+        let sources = paths
+            .iter()
+            .map(|path| {
+                let path = Path::new(path);
+                let filename = path
+                    .file_name()
+                    .map(|name| name.to_string_lossy().to_string())
+                    .unwrap_or_default();
+                (filename, DealFileSource::Path(path))
+            })
+            .collect();
+        Self::import_files(sources, &policy, mode, on_progress)
+    }
+
+    /// Validates and stores each file in turn, reporting progress after each one
+    fn import_files(
+        files: Vec<(String, DealFileSource)>,
+        policy: &ValidationPolicy,
+        mode: ImportMode,
+        mut on_progress: impl FnMut(&DealImportProgress),
     ) -> Result<DealImportResult, AppError> {
         if files.is_empty() {
             return Ok(DealImportResult::error(
@@ -34,11 +114,17 @@ impl DealService {
         // Ensure cache directory exists
         cache_dir::ensure_cache_dir(&deals_dir)?;
 
-        // Create temp directory for validation
+        // Create a temp directory for validation, owned by this import only
+        // (imports run concurrently, e.g. an upload alongside an import from paths)
         let temp_dir = deals_dir
             .parent()
             .ok_or_else(|| AppError::new("Invalid deals cache directory path"))?
-            .join("temp");
+            .join("temp")
+            .join(format!(
+                "deals-import-{}-{}",
+                std::process::id(),
+                IMPORT_COUNTER.fetch_add(1, Ordering::Relaxed)
+            ));
         cache_dir::ensure_cache_dir(&temp_dir)?;
 
         let total_files = files.len();
@@ -47,8 +133,8 @@ impl DealService {
         let mut error_count = 0;
 
         // Process each file
-        for (filename, file_data) in files {
-            let file_result = Self::process_file(&deals_dir, &temp_dir, &filename, &file_data);
+        for (filename, source) in files {
+            let file_result = Self::process_file(&deals_dir, &temp_dir, &filename, source, policy, mode);
             
             match &file_result {
                 FileImportResult { success: true, .. } => {
@@ -58,6 +144,13 @@ impl DealService {
                     error_count += 1;
                 }
             }
+
+            on_progress(&DealImportProgress {
+                processed: file_results.len() + 1,
+                total: total_files,
+                filename: file_result.filename.clone(),
+                success: file_result.success,
+            });
             
             file_results.push(file_result);
         }
@@ -93,77 +186,558 @@ impl DealService {
     }
 
     /// Processes a single file: validates and stores it
+    /// Uploaded data is written to the import's temp directory; files on disk are read in place
     fn process_file(
         deals_dir: &Path,
         temp_dir: &Path,
         filename: &str,
-        file_data: &[u8],
+        source: DealFileSource,
+        policy: &ValidationPolicy,
+        mode: ImportMode,
     ) -> FileImportResult {
-        // Validate filename (sanitize)
-        if filename.is_empty() {
-            return FileImportResult::error(filename.to_string(), "Filename cannot be empty");
-        }
-
-        // Ensure filename ends with .parquet
-        let sanitized_filename = if filename.ends_with(".parquet") {
-            filename.to_string()
-        } else {
-            format!("{}.parquet", filename)
+        // The file name (without .parquet) is the account; reject names that would leave the cache
+        let account = match AccountId::parse(filename) {
+            Ok(account) => account,
+            Err(e) => return FileImportResult::from_error(filename, e.into()),
         };
 
-        // Write file to temp location for validation
-        let temp_file_path = temp_dir.join(&sanitized_filename);
-        
-        // Write file data to temp location
-        if let Err(e) = fs::write(&temp_file_path, file_data) {
-            return FileImportResult::error(
-                filename.to_string(),
-                format!("Failed to write file to temp location: {}", e),
-            );
+        match source {
+            DealFileSource::Data(file_data) => {
+                let temp_file_path = temp_dir.join(account.file_name());
+                if let Err(e) = fs::write(&temp_file_path, file_data) {
+                    return FileImportResult::error(
+                        filename.to_string(),
+                        format!("Failed to write file to temp location: {}", e),
+                    );
+                }
+                let result = Self::import_file(deals_dir, &temp_file_path, &account, filename, policy, mode);
+                let _ = fs::remove_file(&temp_file_path);
+                result
+            }
+            DealFileSource::Path(path) if !path.is_file() => {
+                FileImportResult::error(filename.to_string(), format!("File not found: {}", path.display()))
+            }
+            DealFileSource::Path(path) => Self::import_file(deals_dir, path, &account, filename, policy, mode),
+        }
+    }
+
+    /// Validates the Parquet file at `path` and stores it as the deals of `account`
+    fn import_file(
+        deals_dir: &Path,
+        path: &Path,
+        account: &AccountId,
+        filename: &str,
+        policy: &ValidationPolicy,
+        mode: ImportMode,
+    ) -> FileImportResult {
+        if policy.mode == ValidationMode::Coerce {
+            return Self::store_normalized_file(path, account, filename, policy, mode);
         }
 
-        // Validate schema
-        if let Err(e) = ParquetValidator::validate_deals_schema(&temp_file_path) {
-            // Clean up temp file
-            let _ = fs::remove_file(&temp_file_path);
-            return FileImportResult::error(filename.to_string(), e.message);
+        // Validate schema, collecting every problem
+        let df = match ParquetValidator::read_deals_frame(path) {
+            Ok(df) => df,
+            Err(e) => return FileImportResult::invalid(filename.to_string(), ValidationReport::unreadable(e.message)),
+        };
+        let report = ParquetValidator::check_frame(&df, false);
+        if !report.is_valid() {
+            return FileImportResult::invalid(filename.to_string(), report);
         }
 
-        // File is valid, move to deals directory
-        let target_path = deals_dir.join(&sanitized_filename);
+        // Semantic checks; error findings reject the file
+        let semantic = match SemanticValidator::check(&df, &policy.semantic) {
+            Ok(semantic) => semantic,
+            Err(e) => return FileImportResult::from_error(filename, e).with_validation(report),
+        };
+        if semantic.has_errors() {
+            return FileImportResult::error(filename.to_string(), semantic.error_summary())
+                .with_validation(report)
+                .with_semantic(semantic);
+        }
 
-        // If file already exists, we could either overwrite or skip
-        // For now, we'll overwrite (copy will replace if exists)
-        if let Err(e) = fs::copy(&temp_file_path, &target_path) {
-            let _ = fs::remove_file(&temp_file_path);
-            return FileImportResult::error(
-                filename.to_string(),
-                format!("Failed to copy file to cache directory: {}", e),
-            );
+        // Append and upsert merge the file into the stored deals
+        if mode != ImportMode::Replace {
+            return match Self::store_deals_frame(account, df, mode) {
+                Ok((path, merge)) => FileImportResult::success(filename.to_string(), Self::stored_message(&path, merge))
+                    .with_validation(report)
+                    .with_semantic(semantic)
+                    .with_merge(merge),
+                Err(e) => FileImportResult::from_error(filename, e),
+            };
         }
 
-        // Clean up temp file
-        let _ = fs::remove_file(&temp_file_path);
+        // File is valid, copy it as is to the deals directory (replacing the stored deals of the account)
+        let target_path = deals_dir.join(account.file_name());
+        if let Err(e) = Self::copy_deals_file(account, path, &target_path) {
+            return FileImportResult::from_error(filename, e);
+        }
 
         FileImportResult::success(
             filename.to_string(),
             format!("Successfully imported to {}", target_path.display()),
         )
+        .with_validation(report)
+        .with_semantic(semantic)
+    }
+
+    /// Copies a validated file over the deals of an account, replacing the file atomically
+    fn copy_deals_file(account: &AccountId, source: &Path, target_path: &Path) -> Result<(), AppError> {
+        Self::with_account_lock(account, || {
+            let temp_path = target_path.with_extension("parquet.tmp");
+            fs::copy(source, &temp_path)
+                .map_err(|e| AppError::new(format!("Failed to copy file to cache directory: {}", e)))?;
+            fs::rename(&temp_path, target_path).map_err(|e| {
+                let _ = fs::remove_file(&temp_path);
+                AppError::new(format!("Failed to replace deals file: {}", e))
+            })
+        })
+    }
+
+    /// Runs `action` holding the lock that serializes the writes to the deals file of an account
+    /// Held across the read-modify-write of append and upsert imports so concurrent imports
+    /// into the same account do not lose rows, and by anything else replacing or deleting the file
+    pub fn with_account_lock<T>(
+        account: &AccountId,
+        action: impl FnOnce() -> Result<T, AppError>,
+    ) -> Result<T, AppError> {
+        let lock = Self::account_lock(account)?;
+        let _guard = lock
+            .lock()
+            .map_err(|_| AppError::new(format!("Deals lock of account {} is poisoned", account)))?;
+        action()
+    }
+
+    fn account_lock(account: &AccountId) -> Result<Arc<Mutex<()>>, AppError> {
+        let mut locks = ACCOUNT_LOCKS
+            .lock()
+            .map_err(|_| AppError::new("Deals lock table is poisoned"))?;
+        Ok(locks.entry(account.to_string()).or_default().clone())
+    }
+
+    /// Stores a validated deals frame as the deals of an account
+    /// Replace overwrites the stored deals; append and upsert merge the frame into them on
+    /// `ticket` (an account without stored deals gets every deal added)
+    /// Returns the written path and, when merged, the merge statistics
+    fn store_deals_frame(
+        account: &AccountId,
+        mut df: DataFrame,
+        mode: ImportMode,
+    ) -> Result<(PathBuf, Option<MergeStats>), AppError> {
+        Self::with_account_lock(account, || {
+            let merge = match mode {
+                ImportMode::Replace => return Ok((Self::write_deals_frame(account, &mut df)?, None)),
+                ImportMode::Append => DealMerger::append,
+                ImportMode::Upsert => DealMerger::upsert,
+            };
+
+            let stored_path = account.deals_path()?;
+            let stored = if stored_path.exists() {
+                ParquetValidator::read_deals_frame(&stored_path)?
+            } else {
+                df.clear()
+            };
+
+            let (mut merged, stats) = merge(&stored, &df)?;
+            let path = Self::write_deals_frame(account, &mut merged)?;
+            Ok((path, Some(stats)))
+        })
+    }
+
+    /// Success message of a stored file, with the merge statistics when merged
+    fn stored_message(path: &Path, merge: Option<MergeStats>) -> String {
+        match merge {
+            Some(stats) => format!(
+                "Successfully imported to {} ({} added, {} updated, {} unchanged)",
+                path.display(),
+                stats.rows_added,
+                stats.rows_updated,
+                stats.rows_unchanged
+            ),
+            None => format!("Successfully imported to {}", path.display()),
+        }
+    }
+
+    /// Imports MT5 "Report History" statements (HTML or XLSX) into the deals cache
+    /// The Deals section of each report is mapped to the deals schema and stored as
+    /// `{login}.parquet` (login from the report header, otherwise the file name)
+    /// A file that cannot be read fails on its own; unmappable rows are skipped with a warning
+    /// Mapped deals go through the semantic checks; error findings reject the report
+    /// The import mode decides whether a report replaces the stored deals or is merged into them
+    pub fn import_mt5_statements(
+        files: Vec<(String, Vec<u8>)>,
+        config: &SemanticConfig,
+        mode: ImportMode,
+    ) -> Result<Vec<StatementImportResult>, AppError> {
+        if files.is_empty() {
+            return Err(AppError::new("No files provided"));
+        }
+
+        Ok(files
+            .into_iter()
+            .map(|(filename, data)| Self::import_mt5_statement(&filename, &data, config, mode))
+            .collect())
+    }
+
+    /// Imports a single MT5 statement
+    fn import_mt5_statement(
+        filename: &str,
+        data: &[u8],
+        config: &SemanticConfig,
+        mode: ImportMode,
+    ) -> StatementImportResult {
+        let mut result = StatementImportResult {
+            filename: filename.to_string(),
+            success: false,
+            message: String::new(),
+            code: None,
+            account: None,
+            deals_imported: 0,
+            rows_skipped: 0,
+            warnings: Vec::new(),
+            semantic: None,
+            merge: None,
+        };
+
+        let parsed = match Mt5StatementParser::parse(filename, data) {
+            Ok(parsed) => parsed,
+            Err(e) => {
+                result.fail(e);
+                return result;
+            }
+        };
+        result.rows_skipped = parsed.rows_skipped;
+        result.warnings = parsed.warnings;
+
+        let account = parsed.account.unwrap_or_else(|| Self::account_from_filename(filename));
+        Self::store_converted_deals(result, &account, &parsed.deals, config, mode)
+    }
+
+    /// Stores converted deals as the deals of `account` and completes the import result
+    /// The semantic checks run on the mapped deals first; error findings reject the file
+    /// The import mode decides whether they replace the stored deals or are merged into them
+    fn store_converted_deals(
+        mut result: StatementImportResult,
+        account: &str,
+        deals: &[Deal],
+        config: &SemanticConfig,
+        mode: ImportMode,
+    ) -> StatementImportResult {
+        let account = match AccountId::parse(account) {
+            Ok(account) => account,
+            Err(e) => {
+                result.fail(e.into());
+                return result;
+            }
+        };
+        if deals.is_empty() {
+            result.message = "No deal could be mapped from the file".to_string();
+            return result;
+        }
+
+        let df = match Self::deals_to_dataframe(deals) {
+            Ok(df) => df,
+            Err(e) => {
+                result.fail(e);
+                return result;
+            }
+        };
+        let semantic = match SemanticValidator::check(&df, config) {
+            Ok(semantic) => semantic,
+            Err(e) => {
+                result.fail(e);
+                return result;
+            }
+        };
+        if semantic.has_errors() {
+            result.message = semantic.error_summary();
+            result.semantic = Some(semantic);
+            return result;
+        }
+        result.semantic = Some(semantic);
+
+        match Self::store_deals_frame(&account, df, mode) {
+            Ok((path, merge)) => {
+                result.success = true;
+                result.deals_imported = deals.len();
+                result.message = format!(
+                    "{} ({} deal(s) mapped, {} row(s) skipped)",
+                    Self::stored_message(&path, merge),
+                    deals.len(),
+                    result.rows_skipped
+                );
+                result.account = Some(account.into_string());
+                result.merge = merge;
+            }
+            Err(e) => result.fail(e),
+        }
+
+        result
+    }
+
+    /// Account name of an imported file: its name without extension
+    fn account_from_filename(filename: &str) -> String {
+        Path::new(filename)
+            .file_stem()
+            .map(|stem| stem.to_string_lossy().to_string())
+            .unwrap_or_default()
+    }
+
+    /// Saves (or replaces) a column-mapping profile for CSV/JSON imports
+    pub fn save_import_profile(profile: ImportProfile) -> Result<ImportProfile, AppError> {
+        profile.validate()?;
+        ImportProfileStore::update(|store| {
+            store.profiles.insert(profile.name.clone(), profile.clone());
+            profile
+        })
+    }
+
+    /// Lists the saved import profiles, sorted by name
+    pub fn list_import_profiles() -> Result<Vec<ImportProfile>, AppError> {
+        Ok(ImportProfileStore::load()?.profiles.into_values().collect())
+    }
+
+    /// Deletes a saved import profile
+    pub fn delete_import_profile(name: &str) -> Result<(), AppError> {
+        let removed = ImportProfileStore::update(|store| store.profiles.remove(name))?;
+        removed
+            .map(|_| ())
+            .ok_or_else(|| AppError::new(format!("Import profile '{}' not found", name)))
+    }
+
+    /// Maps a CSV/JSON file with a profile without storing anything
+    /// Returns the first `rows` mapped deals and the rows that could not be mapped
+    pub fn preview_import(
+        data: &[u8],
+        profile: &ImportProfile,
+        rows: Option<usize>,
+    ) -> Result<ImportPreview, AppError> {
+        let rows = rows.unwrap_or(DEFAULT_PREVIEW_ROWS);
+        if rows > MAX_PREVIEW_ROWS {
+            return Err(AppError::new(format!(
+                "Preview cannot exceed {} rows",
+                MAX_PREVIEW_ROWS
+            )));
+        }
+
+        let mapped = ProfileMapper::new(profile)?.map(data)?;
+        let error_count = mapped.errors.len();
+
+        Ok(ImportPreview {
+            source_columns: mapped.source_columns,
+            unmapped_fields: mapped.unmapped_fields,
+            total_rows: mapped.total_rows,
+            valid_rows: mapped.deals.len(),
+            deals: mapped.deals.into_iter().take(rows).collect(),
+            errors: mapped.errors.into_iter().take(MAX_PREVIEW_ERRORS).collect(),
+            error_count,
+        })
+    }
+
+    /// Imports a CSV/JSON file mapped with a profile as `{account}.parquet`
+    /// The account defaults to the file name; rows that cannot be mapped are skipped with a warning
+    /// Mapped deals go through the semantic checks; error findings reject the file
+    /// The import mode decides whether the file replaces the stored deals or is merged into them
+    pub fn import_with_profile(
+        filename: &str,
+        data: &[u8],
+        profile: &ImportProfile,
+        account: Option<String>,
+        config: &SemanticConfig,
+        mode: ImportMode,
+    ) -> Result<StatementImportResult, AppError> {
+        let mapped = ProfileMapper::new(profile)?.map(data)?;
+
+        let result = StatementImportResult {
+            filename: filename.to_string(),
+            success: false,
+            message: String::new(),
+            code: None,
+            account: None,
+            deals_imported: 0,
+            rows_skipped: mapped.errors.len(),
+            warnings: mapped.errors,
+            semantic: None,
+            merge: None,
+        };
+        let account = account.unwrap_or_else(|| Self::account_from_filename(filename));
+
+        Ok(Self::store_converted_deals(result, &account, &mapped.deals, config, mode))
+    }
+
+    /// Normalizes an imported file to the deals schema (coerce mode) and stores the result
+    /// The result lists the coercions applied, or every problem that prevented normalization
+    fn store_normalized_file(
+        path: &Path,
+        account: &AccountId,
+        filename: &str,
+        policy: &ValidationPolicy,
+        mode: ImportMode,
+    ) -> FileImportResult {
+        let df = match ParquetValidator::read_deals_frame(path) {
+            Ok(df) => df,
+            Err(e) => return FileImportResult::invalid(filename.to_string(), ValidationReport::unreadable(e.message)),
+        };
+
+        let (normalized, coercions) = match ParquetValidator::normalize_deals(&df, policy) {
+            Ok(normalized) => normalized,
+            Err(report) => return FileImportResult::invalid(filename.to_string(), report),
+        };
+        let report = ParquetValidator::check_frame(&normalized, policy.extra_columns == ExtraColumnsPolicy::Keep);
+
+        let semantic = match SemanticValidator::check(&normalized, &policy.semantic) {
+            Ok(semantic) => semantic,
+            Err(e) => return FileImportResult::from_error(filename, e).with_validation(report),
+        };
+        if semantic.has_errors() {
+            return FileImportResult::error(filename.to_string(), semantic.error_summary())
+                .with_coercions(coercions)
+                .with_validation(report)
+                .with_semantic(semantic);
+        }
+
+        match Self::store_deals_frame(account, normalized, mode) {
+            Ok((path, merge)) => FileImportResult::success(
+                filename.to_string(),
+                format!("{}, {} coercion(s) applied", Self::stored_message(&path, merge), coercions.len()),
+            )
+            .with_coercions(coercions)
+            .with_validation(report)
+            .with_semantic(semantic)
+            .with_merge(merge),
+            Err(e) => FileImportResult::from_error(filename, e),
+        }
+    }
+
+    /// Writes deals to `{account}.parquet` in the deals cache directory using the `deals_schema` layout
+    /// Replaces an existing file of the same account; returns the written path
+    pub fn write_deals(account: &str, deals: &[Deal]) -> Result<PathBuf, AppError> {
+        let account = AccountId::parse(account)?;
+        let df = Self::deals_to_dataframe(deals)?;
+        Ok(Self::store_deals_frame(&account, df, ImportMode::Replace)?.0)
+    }
+
+    /// Writes a deals frame to `{account}.parquet`, replacing the file atomically
+    /// Callers hold the account lock
+    fn write_deals_frame(account: &AccountId, df: &mut DataFrame) -> Result<PathBuf, AppError> {
+        let deals_dir = cache_dir::get_deals_cache_dir()?;
+        cache_dir::ensure_cache_dir(&deals_dir)?;
+
+        let file_path = deals_dir.join(account.file_name());
+        let temp_path = file_path.with_extension("parquet.tmp");
+        let file = fs::File::create(&temp_path)
+            .map_err(|e| AppError::new(format!("Failed to create deals file: {}", e)))?;
+
+        ParquetWriter::new(file)
+            .with_compression(ParquetCompression::Zstd(None))
+            .with_statistics(true)
+            .finish(df)
+            .map_err(|e| {
+                let _ = fs::remove_file(&temp_path);
+                AppError::new(format!("Failed to write deals file: {}", e))
+            })?;
+
+        fs::rename(&temp_path, &file_path).map_err(|e| {
+            let _ = fs::remove_file(&temp_path);
+            AppError::new(format!("Failed to replace deals file: {}", e))
+        })?;
+
+        Ok(file_path)
+    }
+
+    /// Builds a DataFrame with the `deals_schema` columns (in schema order) from deal records
+    pub fn deals_to_dataframe(deals: &[Deal]) -> Result<DataFrame, AppError> {
+        let int_column = |name: &str, value: fn(&Deal) -> i64| {
+            Series::new(name, deals.iter().map(value).collect::<Vec<i64>>())
+        };
+        let float_column = |name: &str, value: fn(&Deal) -> f64| {
+            Series::new(name, deals.iter().map(value).collect::<Vec<f64>>())
+        };
+        let string_column = |name: &str, value: fn(&Deal) -> &str| {
+            Series::new(name, deals.iter().map(value).collect::<Vec<&str>>())
+        };
+
+        DataFrame::new(vec![
+            int_column("ticket", |d| d.ticket),
+            int_column("order", |d| d.order),
+            int_column("time", |d| d.time),
+            int_column("time_msc", |d| d.time_msc),
+            int_column("type", |d| d.r#type),
+            int_column("entry", |d| d.entry),
+            int_column("magic", |d| d.magic),
+            int_column("position_id", |d| d.position_id),
+            int_column("reason", |d| d.reason),
+            float_column("volume", |d| d.volume),
+            float_column("price", |d| d.price),
+            float_column("commission", |d| d.commission),
+            float_column("swap", |d| d.swap),
+            float_column("profit", |d| d.profit),
+            float_column("fee", |d| d.fee),
+            string_column("symbol", |d| d.symbol.as_str()),
+            string_column("comment", |d| d.comment.as_str()),
+            string_column("external_id", |d| d.external_id.as_str()),
+        ])
+        .map_err(|e| AppError::new(format!("Failed to build deals table: {}", e)))
+    }
+
+    /// Runs the semantic checks on stored accounts (all imported accounts if none are given;
+    /// backtest accounts are checked when named)
+    /// Accounts are given by id or registered name
+    /// Returns one report per account, in the order requested
+    pub fn validate_stored_deals(
+        accounts: Option<Vec<String>>,
+        config: &SemanticConfig,
+    ) -> Result<Vec<AccountSemanticReport>, AppError> {
+        let accounts = match accounts {
+            Some(accounts) => accounts
+                .iter()
+                .map(|account| AccountService::resolve_one(account))
+                .collect::<Result<Vec<_>, _>>()?,
+            None => AccountService::resolve(None)?,
+        };
+
+        Ok(accounts
+            .into_iter()
+            .map(|account| Self::check_stored_account(account, config))
+            .collect())
+    }
+
+    /// Runs the semantic checks on the deals of one stored account
+    /// An unreadable file or schema mismatch is reported in the account's entry
+    fn check_stored_account(account: ResolvedAccount, config: &SemanticConfig) -> AccountSemanticReport {
+        let mut result = AccountSemanticReport {
+            account: account.id,
+            report: None,
+            validation: None,
+            error: None,
+        };
+
+        let df = match ParquetValidator::read_deals_frame(&account.deals_path) {
+            Ok(df) => df,
+            Err(e) => {
+                result.error = Some(e.message);
+                return result;
+            }
+        };
+        // Stored files may carry extra columns kept by a coerce import
+        let schema = ParquetValidator::check_frame(&df, true);
+        if !schema.is_valid() {
+            result.error = Some(format!("Deals do not match the schema: {}", schema.summary()));
+            result.validation = Some(schema);
+            return result;
+        }
+
+        match SemanticValidator::check(&df, config) {
+            Ok(report) => result.report = Some(report),
+            Err(e) => result.error = Some(e.message),
+        }
+        result
     }
 
     /// Reads deals from a single Parquet file
     /// Returns a vector of Deal records
     pub fn read_deals_from_file(filename: &str) -> Result<Vec<Deal>, AppError> {
-        let deals_dir = cache_dir::get_deals_cache_dir()?;
-        
-        // Ensure filename ends with .parquet
-        let filename = if filename.ends_with(".parquet") {
-            filename.to_string()
-        } else {
-            format!("{}.parquet", filename)
-        };
-        
-        let file_path = deals_dir.join(&filename);
+        // The filename (with or without .parquet) must name a file inside the deals cache
+        let file_path = AccountId::parse(filename)?.deals_path()?;
         
         if !file_path.exists() {
             return Err(AppError::new(format!(
@@ -315,44 +889,19 @@ impl DealService {
         Ok(deals)
     }
 
-    /// Reads all deals from all Parquet files in the deals cache directory
+    /// Reads all deals of the imported accounts (backtest accounts are left out)
     /// Returns a combined vector of all Deal records
     pub fn read_all_deals() -> Result<Vec<Deal>, AppError> {
-        let deals_dir = cache_dir::get_deals_cache_dir()?;
-        
-        // If directory doesn't exist, return empty vector
-        if !deals_dir.exists() {
-            return Ok(Vec::new());
-        }
-
         let mut all_deals = Vec::new();
 
-        // Read directory entries
-        let entries = fs::read_dir(&deals_dir)
-            .map_err(|e| AppError::new(format!("Failed to read deals directory: {}", e)))?;
-
-        for entry in entries {
-            let entry = entry
-                .map_err(|e| AppError::new(format!("Failed to read directory entry: {}", e)))?;
-            
-            let path = entry.path();
-            
-            // Only process .parquet files
-            if path.is_file() {
-                if let Some(file_name) = path.file_name() {
-                    let file_name_str = file_name.to_string_lossy();
-                    if file_name_str.ends_with(".parquet") {
-                        // Read deals from this file
-                        match Self::read_deals_from_file(&file_name_str) {
-                            Ok(mut deals) => {
-                                all_deals.append(&mut deals);
-                            }
-                            Err(e) => {
-                                // Log error but continue processing other files
-                                eprintln!("Warning: Failed to read file {}: {}", file_name_str, e.message);
-                            }
-                        }
-                    }
+        for account in AccountService::resolve(None)? {
+            match Self::read_deals_from_file(&account.id) {
+                Ok(mut deals) => {
+                    all_deals.append(&mut deals);
+                }
+                Err(e) => {
+                    // Log error but continue processing other files
+                    eprintln!("Warning: Failed to read deals of account {}: {}", account.id, e.message);
                 }
             }
         }
@@ -0,0 +1,677 @@
+//! Parser for MT5 "Report History" statements (HTML or XLSX export of the terminal)
+//!
+//! Only the Deals section is read. Columns are located by their header names, so reports
+//! with or without the newer `Fee` column are both accepted. The report has no position
+//! column: entries take the position of their order (MT5 uses the opening order ticket as
+//! position identifier) and exits are matched to the open position they close.
+
+use crate::features::deals::model::{
+    Deal, RowWarning, DEAL_ENTRY_IN, DEAL_ENTRY_INOUT, DEAL_ENTRY_OUT, DEAL_ENTRY_OUT_BY,
+    DEAL_REASON_CLIENT, DEAL_REASON_SL, DEAL_REASON_SO, DEAL_REASON_TP, DEAL_TYPE_BUY,
+    DEAL_TYPE_SELL, VOLUME_EPSILON,
+};
+use crate::shared::error::AppError;
+use calamine::{Data, Reader, Xlsx};
+use chrono::NaiveDateTime;
+use std::collections::{HashMap, HashSet};
+use std::io::Cursor;
+
+/// MT5 deal types (`ENUM_DEAL_TYPE`) by their report spelling with non-letters removed
+const DEAL_TYPES: &[(&str, i64)] = &[
+    ("buy", 0),
+    ("sell", 1),
+    ("balance", 2),
+    ("credit", 3),
+    ("charge", 4),
+    ("correction", 5),
+    ("bonus", 6),
+    ("commission", 7),
+    ("dailycommission", 8),
+    ("commissiondaily", 8),
+    ("monthlycommission", 9),
+    ("commissionmonthly", 9),
+    ("dailyagentcommission", 10),
+    ("agentcommissiondaily", 10),
+    ("monthlyagentcommission", 11),
+    ("agentcommissionmonthly", 11),
+    ("interest", 12),
+    ("interestrate", 12),
+    ("canceledbuy", 13),
+    ("buycanceled", 13),
+    ("canceledsell", 14),
+    ("sellcanceled", 14),
+    ("dividend", 15),
+    ("frankeddividend", 16),
+    ("dividendfranked", 16),
+    ("tax", 17),
+];
+
+/// Days between 1899-12-30 (Excel serial 0) and 1970-01-01
+const EXCEL_UNIX_EPOCH_DAYS: f64 = 25_569.0;
+
+/// Time formats used by MT5 reports
+const TIME_FORMATS: &[&str] = &["%Y.%m.%d %H:%M:%S%.f", "%Y.%m.%d %H:%M", "%Y-%m-%d %H:%M:%S%.f"];
+
+/// Largest `colspan` honoured in HTML reports; wider cells are truncated to it
+const MAX_COLSPAN: usize = 1_000;
+
+/// Deals read from a statement
+pub struct ParsedStatement {
+    /// Login from the report header, if present
+    pub account: Option<String>,
+    pub deals: Vec<Deal>,
+    pub rows_skipped: usize,
+    pub warnings: Vec<RowWarning>,
+}
+
+/// Column positions of the Deals table
+struct DealColumns {
+    time: usize,
+    deal: usize,
+    symbol: Option<usize>,
+    r#type: usize,
+    direction: Option<usize>,
+    volume: Option<usize>,
+    price: Option<usize>,
+    order: Option<usize>,
+    commission: Option<usize>,
+    fee: Option<usize>,
+    swap: Option<usize>,
+    profit: Option<usize>,
+    comment: Option<usize>,
+}
+
+/// Open position tracked while assigning position ids to exits
+struct OpenPosition {
+    position_id: i64,
+    /// Deal type of the entry (buy or sell)
+    side: i64,
+    volume: f64,
+}
+
+/// Parser for MT5 history statements
+pub struct Mt5StatementParser;
+
+impl Mt5StatementParser {
+    /// Parses an `.html`/`.htm` or `.xlsx` statement into deals
+    pub fn parse(filename: &str, data: &[u8]) -> Result<ParsedStatement, AppError> {
+        let extension = filename
+            .rsplit_once('.')
+            .map(|(_, extension)| extension.to_ascii_lowercase())
+            .unwrap_or_default();
+
+        let rows = match extension.as_str() {
+            "html" | "htm" => Self::html_rows(data),
+            "xlsx" => Self::xlsx_rows(data)?,
+            _ => {
+                return Err(AppError::new(format!(
+                    "Unsupported statement format '{}'. Expected an .html or .xlsx report",
+                    filename
+                )))
+            }
+        };
+
+        Self::parse_rows(&rows)
+    }
+
+    /// Reads the cell texts of every table row of an HTML report
+    /// MT5 writes reports as UTF-16; colspans are expanded so columns line up with the header
+    fn html_rows(data: &[u8]) -> Vec<Vec<String>> {
+        let html = Self::decode_text(data);
+        // ASCII lowercasing keeps byte offsets, so tags are searched in `lower` and read from `html`
+        let lower = html.to_ascii_lowercase();
+
+        let mut rows = Vec::new();
+        let mut position = 0;
+        while let Some(start) = Self::find_tag(&lower, "tr", position) {
+            let end = [lower[start + 3..].find("</tr"), lower[start + 3..].find("<tr")]
+                .into_iter()
+                .flatten()
+                .min()
+                .map(|offset| start + 3 + offset)
+                .unwrap_or(lower.len());
+
+            let mut cells = Vec::new();
+            let mut cell_position = start + 3;
+            loop {
+                let next = [Self::find_tag(&lower, "td", cell_position), Self::find_tag(&lower, "th", cell_position)]
+                    .into_iter()
+                    .flatten()
+                    .min()
+                    .filter(|cell| *cell < end);
+                let Some(cell_start) = next else { break };
+
+                let tag_end = lower[cell_start..].find('>').map(|i| cell_start + i + 1).unwrap_or(end).min(end);
+                let content_end = ["</td", "</th", "<td", "<th"]
+                    .iter()
+                    .filter_map(|tag| lower[tag_end..end].find(tag))
+                    .min()
+                    .map(|offset| tag_end + offset)
+                    .unwrap_or(end);
+
+                let colspan = Self::attribute(&lower[cell_start..tag_end], "colspan")
+                    .and_then(|value| value.parse::<usize>().ok())
+                    .unwrap_or(1)
+                    .clamp(1, MAX_COLSPAN);
+                cells.push(Self::cell_text(&html[tag_end..content_end]));
+                cells.extend(std::iter::repeat_n(String::new(), colspan - 1));
+                cell_position = content_end;
+            }
+
+            rows.push(cells);
+            position = end;
+        }
+
+        rows
+    }
+
+    /// Reads the cells of the first worksheet of an XLSX report as text
+    fn xlsx_rows(data: &[u8]) -> Result<Vec<Vec<String>>, AppError> {
+        let mut workbook = Xlsx::new(Cursor::new(data))
+            .map_err(|e| AppError::new(format!("Failed to open XLSX report: {}", e)))?;
+        let range = workbook
+            .worksheet_range_at(0)
+            .ok_or_else(|| AppError::new("XLSX report has no worksheet"))?
+            .map_err(|e| AppError::new(format!("Failed to read XLSX worksheet: {}", e)))?;
+
+        Ok(range
+            .rows()
+            .map(|row| {
+                row.iter()
+                    .map(|cell| match cell {
+                        Data::String(text) | Data::DateTimeIso(text) => text.trim().to_string(),
+                        Data::Int(value) => value.to_string(),
+                        Data::Float(value) if value.fract() == 0.0 && value.abs() < 1e15 => {
+                            format!("{}", *value as i64)
+                        }
+                        Data::Float(value) => value.to_string(),
+                        Data::DateTime(value) => Self::excel_serial_text(value.as_f64()),
+                        Data::Bool(value) => value.to_string(),
+                        _ => String::new(),
+                    })
+                    .collect()
+            })
+            .collect())
+    }
+
+    /// Formats an Excel serial date (days since 1899-12-30) in the report time format
+    fn excel_serial_text(serial: f64) -> String {
+        let seconds = ((serial - EXCEL_UNIX_EPOCH_DAYS) * 86_400.0).round() as i64;
+        chrono::DateTime::from_timestamp(seconds, 0)
+            .map(|time| time.format("%Y.%m.%d %H:%M:%S").to_string())
+            .unwrap_or_default()
+    }
+
+    /// Locates the Deals section and maps its rows
+    fn parse_rows(rows: &[Vec<String>]) -> Result<ParsedStatement, AppError> {
+        let account = Self::account_login(rows);
+
+        let section = rows
+            .iter()
+            .position(|row| Self::single_text(row).is_some_and(|text| text.eq_ignore_ascii_case("deals")))
+            .ok_or_else(|| AppError::new("No Deals section found in the report"))?;
+        let header_index = rows[section + 1..]
+            .iter()
+            .position(|row| !Self::is_blank(row))
+            .map(|offset| section + 1 + offset)
+            .ok_or_else(|| AppError::new("Deals section has no header row"))?;
+        let columns = Self::deal_columns(&rows[header_index])?;
+
+        let mut parsed = ParsedStatement {
+            account,
+            deals: Vec::new(),
+            rows_skipped: 0,
+            warnings: Vec::new(),
+        };
+        // Source row of every mapped deal, for warnings raised after mapping
+        let mut deal_rows = Vec::new();
+
+        for (index, row) in rows.iter().enumerate().skip(header_index + 1) {
+            // The section ends at a spacer row or at the title of the next section
+            if Self::is_blank(row) || Self::single_text(row).is_some_and(|text| text.parse::<f64>().is_err()) {
+                break;
+            }
+            let cell = |column: Option<usize>| column.and_then(|i| row.get(i)).map(|s| s.as_str()).unwrap_or("");
+            // Totals row: amounts without a deal ticket
+            if cell(Some(columns.deal)).is_empty() {
+                continue;
+            }
+
+            match Self::map_deal(row, &columns) {
+                Ok(deal) => {
+                    parsed.deals.push(deal);
+                    deal_rows.push(index + 1);
+                }
+                Err(message) => {
+                    parsed.rows_skipped += 1;
+                    parsed.warnings.push(RowWarning { row: index + 1, message });
+                }
+            }
+        }
+
+        if parsed.deals.is_empty() && parsed.rows_skipped == 0 {
+            return Err(AppError::new("Deals section of the report is empty"));
+        }
+
+        Self::assign_positions(&mut parsed, &deal_rows);
+        Ok(parsed)
+    }
+
+    /// Finds the columns of the Deals header row by name
+    fn deal_columns(header: &[String]) -> Result<DealColumns, AppError> {
+        let find = |name: &str| {
+            header
+                .iter()
+                .position(|cell| Self::letters(cell) == name)
+        };
+        let require = |name: &str| {
+            find(name).ok_or_else(|| {
+                AppError::new(format!("Deals section has no '{}' column", name))
+            })
+        };
+
+        Ok(DealColumns {
+            time: require("time")?,
+            deal: require("deal")?,
+            symbol: find("symbol"),
+            r#type: require("type")?,
+            direction: find("direction"),
+            volume: find("volume"),
+            price: find("price"),
+            order: find("order"),
+            commission: find("commission"),
+            fee: find("fee"),
+            swap: find("swap"),
+            profit: find("profit"),
+            comment: find("comment"),
+        })
+    }
+
+    /// Maps one Deals row; the error describes why the row was skipped
+    fn map_deal(row: &[String], columns: &DealColumns) -> Result<Deal, String> {
+        let cell = |column: Option<usize>| column.and_then(|i| row.get(i)).map(|s| s.as_str()).unwrap_or("");
+        let number = |column: Option<usize>, name: &str| -> Result<f64, String> {
+            let text: String = cell(column)
+                .split('/')
+                .next()
+                .unwrap_or_default()
+                .chars()
+                .filter(|c| !c.is_whitespace())
+                .collect();
+            if text.is_empty() {
+                return Ok(0.0);
+            }
+            text.parse::<f64>()
+                .map_err(|_| format!("Invalid {} '{}'", name, cell(column)))
+        };
+
+        let ticket_text = cell(Some(columns.deal));
+        let ticket = ticket_text
+            .parse::<i64>()
+            .map_err(|_| format!("Invalid deal ticket '{}'", ticket_text))?;
+
+        let time_text = cell(Some(columns.time));
+        let time = TIME_FORMATS
+            .iter()
+            .find_map(|format| NaiveDateTime::parse_from_str(time_text, format).ok())
+            .map(|time| time.and_utc())
+            .ok_or_else(|| format!("Deal {}: invalid time '{}'", ticket, time_text))?;
+
+        let type_text = cell(Some(columns.r#type));
+        let r#type = DEAL_TYPES
+            .iter()
+            .find(|(name, _)| *name == Self::letters(type_text))
+            .map(|(_, code)| *code)
+            .ok_or_else(|| format!("Deal {}: unknown type '{}'", ticket, type_text))?;
+
+        let direction_text = cell(columns.direction);
+        let entry = match Self::letters(direction_text).as_str() {
+            "" | "in" => DEAL_ENTRY_IN,
+            "out" => DEAL_ENTRY_OUT,
+            "inout" => DEAL_ENTRY_INOUT,
+            "outby" => DEAL_ENTRY_OUT_BY,
+            _ => return Err(format!("Deal {}: unknown direction '{}'", ticket, direction_text)),
+        };
+
+        let order_text = cell(columns.order);
+        let order = if order_text.is_empty() {
+            0
+        } else {
+            order_text
+                .parse::<i64>()
+                .map_err(|_| format!("Deal {}: invalid order '{}'", ticket, order_text))?
+        };
+
+        let comment = cell(columns.comment).to_string();
+        let lower_comment = comment.to_ascii_lowercase();
+        let reason = if lower_comment.contains("[sl") {
+            DEAL_REASON_SL
+        } else if lower_comment.contains("[tp") {
+            DEAL_REASON_TP
+        } else if lower_comment.contains("[so") || lower_comment.starts_with("so:") {
+            DEAL_REASON_SO
+        } else {
+            DEAL_REASON_CLIENT
+        };
+
+        let with_ticket = |error: String| format!("Deal {}: {}", ticket, error);
+        Ok(Deal {
+            ticket,
+            order,
+            time: time.timestamp(),
+            time_msc: time.timestamp_millis(),
+            r#type,
+            entry,
+            magic: 0,
+            position_id: 0,
+            reason,
+            volume: number(columns.volume, "volume").map_err(with_ticket)?,
+            price: number(columns.price, "price").map_err(with_ticket)?,
+            commission: number(columns.commission, "commission").map_err(with_ticket)?,
+            swap: number(columns.swap, "swap").map_err(with_ticket)?,
+            profit: number(columns.profit, "profit").map_err(with_ticket)?,
+            fee: number(columns.fee, "fee").map_err(with_ticket)?,
+            symbol: cell(columns.symbol).to_string(),
+            comment,
+            external_id: String::new(),
+        })
+    }
+
+    /// Sets `position_id` of buy/sell deals by replaying them in time order
+    /// Entries open a position identified by their order; exits close the oldest open
+    /// position of the opposite side on the same symbol, preferring one of equal volume
+    fn assign_positions(parsed: &mut ParsedStatement, deal_rows: &[usize]) {
+        let mut open: HashMap<String, Vec<OpenPosition>> = HashMap::new();
+        let mut order: Vec<usize> = (0..parsed.deals.len()).collect();
+        order.sort_by_key(|&i| (parsed.deals[i].time_msc, parsed.deals[i].ticket));
+        let mut warnings = Vec::new();
+
+        for i in order {
+            let deal = &mut parsed.deals[i];
+            if deal.r#type != DEAL_TYPE_BUY && deal.r#type != DEAL_TYPE_SELL {
+                continue;
+            }
+            let positions = open.entry(deal.symbol.clone()).or_default();
+            let own_id = if deal.order != 0 { deal.order } else { deal.ticket };
+
+            if deal.entry == DEAL_ENTRY_IN {
+                deal.position_id = own_id;
+                positions.push(OpenPosition { position_id: own_id, side: deal.r#type, volume: deal.volume });
+                continue;
+            }
+
+            let candidates: Vec<usize> = positions
+                .iter()
+                .enumerate()
+                .filter(|(_, position)| position.side != deal.r#type)
+                .map(|(index, _)| index)
+                .collect();
+            let chosen = candidates
+                .iter()
+                .copied()
+                .find(|&index| (positions[index].volume - deal.volume).abs() <= VOLUME_EPSILON)
+                .or_else(|| candidates.first().copied());
+
+            let Some(index) = chosen else {
+                deal.position_id = own_id;
+                warnings.push(RowWarning {
+                    row: deal_rows[i],
+                    message: format!(
+                        "Deal {}: no open {} position to close; position set to {}",
+                        deal.ticket, deal.symbol, own_id
+                    ),
+                });
+                if deal.entry == DEAL_ENTRY_INOUT {
+                    positions.push(OpenPosition { position_id: own_id, side: deal.r#type, volume: deal.volume });
+                }
+                continue;
+            };
+
+            let distinct_ids = candidates
+                .iter()
+                .map(|&index| positions[index].position_id)
+                .collect::<HashSet<_>>()
+                .len();
+            if distinct_ids > 1 {
+                warnings.push(RowWarning {
+                    row: deal_rows[i],
+                    message: format!(
+                        "Deal {}: {} open {} positions could be closed; assigned to position {}",
+                        deal.ticket, distinct_ids, deal.symbol, positions[index].position_id
+                    ),
+                });
+            }
+
+            let position = &mut positions[index];
+            deal.position_id = position.position_id;
+            if deal.entry == DEAL_ENTRY_INOUT {
+                // Netting reversal: the remainder stays open in the other direction under the same id
+                position.volume = deal.volume - position.volume;
+                position.side = deal.r#type;
+                if position.volume <= VOLUME_EPSILON {
+                    positions.remove(index);
+                }
+            } else {
+                position.volume -= deal.volume;
+                if position.volume <= VOLUME_EPSILON {
+                    positions.remove(index);
+                }
+            }
+        }
+
+        parsed.warnings.extend(warnings);
+        parsed.warnings.sort_by_key(|warning| warning.row);
+    }
+
+    /// Login from the "Account:" header row (e.g. "12345678 (USD, Broker-Server, real, Hedge)")
+    fn account_login(rows: &[Vec<String>]) -> Option<String> {
+        rows.iter().find_map(|row| {
+            let label = row.iter().position(|cell| cell.to_ascii_lowercase().starts_with("account:"))?;
+            let inline = row[label]["account:".len()..].trim().to_string();
+            let value = if inline.is_empty() {
+                row[label + 1..].iter().find(|cell| !cell.is_empty())?.clone()
+            } else {
+                inline
+            };
+            let login: String = value.chars().take_while(|c| c.is_ascii_digit()).collect();
+            (!login.is_empty()).then_some(login)
+        })
+    }
+
+    /// Decodes report bytes: UTF-16 (with or without BOM, as written by MT5) or UTF-8
+    fn decode_text(data: &[u8]) -> String {
+        let utf16 = |bytes: &[u8], little_endian: bool| {
+            let units: Vec<u16> = bytes
+                .chunks_exact(2)
+                .map(|pair| if little_endian { u16::from_le_bytes([pair[0], pair[1]]) } else { u16::from_be_bytes([pair[0], pair[1]]) })
+                .collect();
+            String::from_utf16_lossy(&units)
+        };
+
+        match data {
+            [0xFF, 0xFE, rest @ ..] => utf16(rest, true),
+            [0xFE, 0xFF, rest @ ..] => utf16(rest, false),
+            [0xEF, 0xBB, 0xBF, rest @ ..] => String::from_utf8_lossy(rest).into_owned(),
+            [b'<', 0, ..] => utf16(data, true),
+            _ => String::from_utf8_lossy(data).into_owned(),
+        }
+    }
+
+    /// Position of the next `<tag` (followed by whitespace or `>`) at or after `from`
+    fn find_tag(lower: &str, tag: &str, from: usize) -> Option<usize> {
+        let pattern = format!("<{}", tag);
+        let mut position = from;
+        while let Some(offset) = lower.get(position..)?.find(&pattern) {
+            let start = position + offset;
+            let next = lower[start + pattern.len()..].chars().next();
+            if next.is_some_and(|c| c.is_whitespace() || c == '>' || c == '/') {
+                return Some(start);
+            }
+            position = start + pattern.len();
+        }
+        None
+    }
+
+    /// Value of an attribute inside an opening tag (quoted or not)
+    fn attribute(tag: &str, name: &str) -> Option<String> {
+        let start = tag.find(&format!("{}=", name))? + name.len() + 1;
+        let value = tag[start..].trim_start_matches(['"', '\'']);
+        Some(value.chars().take_while(|c| c.is_ascii_alphanumeric()).collect())
+    }
+
+    /// Text of a cell: tags removed, entities decoded and whitespace collapsed
+    fn cell_text(content: &str) -> String {
+        let mut text = String::with_capacity(content.len());
+        let mut in_tag = false;
+        for c in content.chars() {
+            match c {
+                '<' => in_tag = true,
+                '>' if in_tag => {
+                    in_tag = false;
+                    text.push(' ');
+                }
+                _ if !in_tag => text.push(c),
+                _ => {}
+            }
+        }
+
+        let text = text
+            .replace("&nbsp;", " ")
+            .replace("&lt;", "<")
+            .replace("&gt;", ">")
+            .replace("&quot;", "\"")
+            .replace("&#39;", "'")
+            .replace("&amp;", "&");
+        text.split_whitespace().collect::<Vec<_>>().join(" ")
+    }
+
+    /// Lowercase letters of a header or enum value ("In/Out" -> "inout")
+    fn letters(text: &str) -> String {
+        text.chars()
+            .filter(|c| c.is_alphabetic())
+            .flat_map(|c| c.to_lowercase())
+            .collect()
+    }
+
+    /// The only non-empty cell of a row, if there is exactly one
+    fn single_text(row: &[String]) -> Option<&str> {
+        let mut filled = row.iter().filter(|cell| !cell.is_empty());
+        let first = filled.next()?;
+        filled.next().is_none().then_some(first.as_str())
+    }
+
+    fn is_blank(row: &[String]) -> bool {
+        row.iter().all(|cell| cell.is_empty())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// (time, deal, type, direction, volume, price, order, profit)
+    type Row<'a> = (&'a str, i64, &'a str, &'a str, f64, f64, i64, f64);
+
+    /// HTML report laid out like the MT5 export, with a title row and a spanning header
+    fn report(rows: &[Row]) -> String {
+        let mut html = String::from(
+            "<html><body><table>\n\
+             <tr><th colspan=\"14\"><b>Trade History Report</b></th></tr>\n\
+             <tr><td colspan=\"3\">Account:</td><td colspan=\"11\"><b>12345678 (USD, Demo-Server, demo, Hedge)</b></td></tr>\n\
+             <tr><th colspan=\"14\"><div><b>Deals</b></div></th></tr>\n\
+             <tr><td>Time</td><td>Deal</td><td>Symbol</td><td>Type</td><td>Direction</td><td>Volume</td><td>Price</td>\
+             <td>Order</td><td>Commission</td><td>Fee</td><td>Swap</td><td>Profit</td><td>Balance</td><td>Comment</td></tr>\n",
+        );
+        for (time, deal, r#type, direction, volume, price, order, profit) in rows {
+            html.push_str(&format!(
+                "<tr><td>{}</td><td>{}</td><td>EURUSD</td><td>{}</td><td>{}</td><td>{}</td><td>{}</td>\
+                 <td>{}</td><td>-1.00</td><td>0.00</td><td>0.00</td><td>{}</td><td>10 000.00</td><td></td></tr>\n",
+                time, deal, r#type, direction, volume, price, order, profit
+            ));
+        }
+        html.push_str("<tr><td colspan=\"14\"></td></tr>\n</table></body></html>");
+        html
+    }
+
+    fn parse_html(rows: &[Row]) -> ParsedStatement {
+        Mt5StatementParser::parse("ReportHistory-12345678.html", report(rows).as_bytes()).unwrap()
+    }
+
+    fn position_ids(parsed: &ParsedStatement) -> Vec<(i64, i64)> {
+        parsed.deals.iter().map(|deal| (deal.ticket, deal.position_id)).collect()
+    }
+
+    #[test]
+    fn reads_a_utf16_html_report() {
+        let html = report(&[
+            ("2024.01.15 10:00:00", 1001, "buy", "in", 1.0, 1.1, 2001, 0.0),
+            ("2024.01.15 11:30:00", 1002, "sell", "out", 1.0, 1.105, 2002, 50.0),
+        ]);
+        let mut data = vec![0xFF, 0xFE];
+        data.extend(html.encode_utf16().flat_map(|unit| unit.to_le_bytes()));
+
+        let parsed = Mt5StatementParser::parse("ReportHistory-12345678.html", &data).unwrap();
+        assert_eq!(parsed.account.as_deref(), Some("12345678"));
+        assert_eq!(parsed.rows_skipped, 0);
+        assert!(parsed.warnings.is_empty());
+        assert_eq!(parsed.deals.len(), 2);
+
+        let exit = &parsed.deals[1];
+        assert_eq!((exit.r#type, exit.entry), (DEAL_TYPE_SELL, DEAL_ENTRY_OUT));
+        assert_eq!(exit.time, 1_705_318_200);
+        assert_eq!(exit.time_msc, 1_705_318_200_000);
+        assert_eq!((exit.volume, exit.price, exit.commission, exit.profit), (1.0, 1.105, -1.0, 50.0));
+        assert_eq!(exit.symbol, "EURUSD");
+        assert_eq!(position_ids(&parsed), vec![(1001, 2001), (1002, 2001)]);
+    }
+
+    #[test]
+    fn reads_excel_serial_dates() {
+        assert_eq!(Mt5StatementParser::excel_serial_text(45_306.5), "2024.01.15 12:00:00");
+        assert_eq!(Mt5StatementParser::excel_serial_text(EXCEL_UNIX_EPOCH_DAYS), "1970.01.01 00:00:00");
+    }
+
+    #[test]
+    fn closes_the_position_of_equal_volume() {
+        let parsed = parse_html(&[
+            ("2024.01.15 10:00:00", 1001, "buy", "in", 1.0, 1.1, 2001, 0.0),
+            ("2024.01.15 10:05:00", 1002, "buy", "in", 0.5, 1.1, 2002, 0.0),
+            ("2024.01.15 10:10:00", 1003, "sell", "out", 0.5, 1.1, 2003, 5.0),
+            ("2024.01.15 10:15:00", 1004, "sell", "out", 0.4, 1.1, 2004, 4.0),
+            ("2024.01.15 10:20:00", 1005, "sell", "out", 0.6, 1.1, 2005, 6.0),
+        ]);
+
+        assert_eq!(
+            position_ids(&parsed),
+            vec![(1001, 2001), (1002, 2002), (1003, 2002), (1004, 2001), (1005, 2001)]
+        );
+    }
+
+    #[test]
+    fn warns_about_ambiguous_exits() {
+        let parsed = parse_html(&[
+            ("2024.01.15 10:00:00", 1001, "buy", "in", 1.0, 1.1, 2001, 0.0),
+            ("2024.01.15 10:05:00", 1002, "buy", "in", 2.0, 1.1, 2002, 0.0),
+            ("2024.01.15 10:10:00", 1003, "sell", "out", 0.5, 1.1, 2003, 5.0),
+        ]);
+
+        // Neither volume matches, so the oldest position is closed
+        assert_eq!(parsed.deals[2].position_id, 2001);
+        assert_eq!(parsed.warnings.len(), 1);
+        assert_eq!(parsed.warnings[0].row, 7);
+        assert!(parsed.warnings[0].message.contains("2 open EURUSD positions"));
+    }
+
+    #[test]
+    fn keeps_the_position_id_through_a_reversal() {
+        let parsed = parse_html(&[
+            ("2024.01.15 10:00:00", 1001, "buy", "in", 1.0, 1.1, 2001, 0.0),
+            ("2024.01.15 10:10:00", 1002, "sell", "in/out", 1.5, 1.1, 2002, 10.0),
+            ("2024.01.15 10:20:00", 1003, "buy", "out", 0.5, 1.1, 2003, 2.0),
+        ]);
+
+        assert_eq!(parsed.deals[1].entry, DEAL_ENTRY_INOUT);
+        assert_eq!(position_ids(&parsed), vec![(1001, 2001), (1002, 2001), (1003, 2001)]);
+        assert!(parsed.warnings.is_empty());
+    }
+}
@@ -1,21 +1,22 @@
 //! Parquet validator for deals schema
 
 use crate::shared::error::AppError;
-use crate::features::deals::model::deals_schema;
+use crate::features::deals::model::{
+    deals_schema, CoercionKind, ExtraColumnsPolicy, SchemaCoercion, ValidationIssue,
+    ValidationIssueKind, ValidationPolicy, ValidationReport,
+};
 use polars::prelude::*;
 use std::path::Path;
 
+/// Row indices reported per issue
+const MAX_SAMPLE_ROWS: usize = 5;
+
 /// Service for validating deals Parquet files
 pub struct ParquetValidator;
 
 impl ParquetValidator {
-    /// Validates that a Parquet file matches the exact deals schema
-    /// Checks:
-    /// - File is valid Parquet format (not corrupted)
-    /// - All required columns exist with correct names
-    /// - All columns have correct data types
-    /// - All columns are non-nullable (no null values)
-    pub fn validate_deals_schema(file_path: &Path) -> Result<(), AppError> {
+    /// Reads a Parquet file, failing if it does not exist or is not valid Parquet
+    pub fn read_deals_frame(file_path: &Path) -> Result<DataFrame, AppError> {
         // First, check if file exists
         if !file_path.exists() {
             return Err(AppError::new(format!(
@@ -44,78 +45,450 @@ impl ParquetValidator {
         .collect()
         .map_err(|e| AppError::new(format!("Failed to read parquet file: {}", e)))?;
 
-        // Get the schema
+        Ok(df)
+    }
+
+    /// Checks a frame against the exact deals schema, collecting every problem
+    /// Checks:
+    /// - All required columns exist with correct names
+    /// - No other columns exist, unless `allow_extra_columns`
+    /// - All columns have correct data types
+    /// - All columns are non-nullable (no null values)
+    pub fn check_frame(df: &DataFrame, allow_extra_columns: bool) -> ValidationReport {
         let schema = df.schema();
+        let mut issues = Vec::new();
 
-        // Check all required columns exist
+        // Missing columns, in schema order
         for &required_col in deals_schema::REQUIRED_COLUMNS {
             if !schema.contains(required_col) {
-                return Err(AppError::new(format!(
-                    "Missing required column: {}",
-                    required_col
-                )));
+                let mut issue = ValidationIssue::new(
+                    ValidationIssueKind::MissingColumn,
+                    Some(required_col),
+                    format!("Missing required column: {}", required_col),
+                );
+                issue.expected = deals_schema::get_column_type(required_col).map(|dtype| format!("{:?}", dtype));
+                issues.push(issue);
             }
         }
 
-        // Check for extra columns (strict schema validation)
-        let actual_columns: std::collections::HashSet<_> = schema
-            .iter_names()
-            .map(|s| s.as_str())
-            .collect();
-        let required_columns: std::collections::HashSet<_> = deals_schema::REQUIRED_COLUMNS
-            .iter()
-            .copied()
-            .collect();
-
-        for actual_col in &actual_columns {
-            if !required_columns.contains(actual_col) {
-                return Err(AppError::new(format!(
-                    "Unexpected column found: {}. Schema must match exactly.",
-                    actual_col
-                )));
+        // Extra columns (strict schema validation), in file order
+        if !allow_extra_columns {
+            for actual_col in schema.iter_names() {
+                if !deals_schema::REQUIRED_COLUMNS.contains(&actual_col.as_str()) {
+                    let mut issue = ValidationIssue::new(
+                        ValidationIssueKind::UnexpectedColumn,
+                        Some(actual_col),
+                        format!("Unexpected column found: {}. Schema must match exactly.", actual_col),
+                    );
+                    issue.actual = schema.get(actual_col).map(|dtype| format!("{:?}", dtype));
+                    issues.push(issue);
+                }
             }
         }
 
-        // Validate column types and nullability
+        // Column types and nullability of the columns that exist
         for &col_name in deals_schema::REQUIRED_COLUMNS {
-            // Get the field from schema - we already know it exists from earlier check
-            // Use get_field which returns Option<Field>
-            let field_opt = schema.get_field(col_name);
-            let field = field_opt
-                .ok_or_else(|| AppError::new(format!("Column {} not found in schema", col_name)))?;
-
-            // Check data type
-            let expected_type = deals_schema::get_column_type(col_name)
-                .ok_or_else(|| AppError::new(format!("Unknown column type for: {}", col_name)))?;
-
-            let actual_type = field.data_type();
+            let (Ok(series), Some(expected_type)) = (df.column(col_name), deals_schema::get_column_type(col_name))
+            else {
+                continue;
+            };
+            let actual_type = series.dtype();
 
             // Polars uses different representations, so we need to match carefully
             // Int64 maps to Int64
             // Float64 maps to Float64
             // String maps to String
             if !Self::types_match(&expected_type, actual_type) {
-                return Err(AppError::new(format!(
-                    "Column '{}' has incorrect type. Expected {:?}, found {:?}",
-                    col_name, expected_type, actual_type
-                )));
+                let mut issue = ValidationIssue::new(
+                    ValidationIssueKind::WrongType,
+                    Some(col_name),
+                    format!(
+                        "Column '{}' has incorrect type. Expected {:?}, found {:?}",
+                        col_name, expected_type, actual_type
+                    ),
+                );
+                issue.expected = Some(format!("{:?}", expected_type));
+                issue.actual = Some(format!("{:?}", actual_type));
+                issues.push(issue);
+            }
+
+            // Null values (non-nullable requirement)
+            if let Some(issue) = Self::null_issue(series, "All columns must be non-nullable.") {
+                issues.push(issue);
             }
+        }
 
-            // Check for null values (non-nullable requirement)
-            let null_count = df
-                .column(col_name)
-                .map_err(|e| AppError::new(format!("Failed to get column '{}': {}", col_name, e)))?
-                .null_count();
+        ValidationReport {
+            row_count: df.height(),
+            issues,
+        }
+    }
 
-            if null_count > 0 {
-                return Err(AppError::new(format!(
-                    "Column '{}' contains {} null value(s). All columns must be non-nullable.",
-                    col_name, null_count
-                )));
+    /// Normalizes a frame to the deals schema for the coerce validation mode
+    /// - Compatible types are converted: smaller integers and Float32 are upcast, floats holding
+    ///   whole numbers become integers, datetimes become Unix seconds (`time`) or milliseconds
+    ///   (`time_msc`) and numbers become strings
+    /// - Nulls (and NaN) are filled with the column default where one exists (zero amounts and
+    ///   identifiers, empty strings); `time_msc` is derived from `time`
+    /// - Columns outside the schema are dropped or kept after the schema columns
+    ///
+    /// Returns the normalized frame and every change applied, or a report of every column
+    /// that could not be normalized
+    pub fn normalize_deals(
+        df: &DataFrame,
+        policy: &ValidationPolicy,
+    ) -> Result<(DataFrame, Vec<SchemaCoercion>), ValidationReport> {
+        let height = df.height();
+        let mut coercions = Vec::new();
+        let mut issues = Vec::new();
+        let mut columns: Vec<Series> = Vec::with_capacity(df.width());
+
+        for &name in deals_schema::REQUIRED_COLUMNS {
+            let Some(expected) = deals_schema::get_column_type(name) else {
+                continue;
+            };
+
+            let (series, added) = match df.column(name) {
+                Ok(series) => match Self::coerce_column(series, &expected, &mut coercions) {
+                    Ok(series) => (series, false),
+                    Err(issue) => {
+                        issues.push(*issue);
+                        continue;
+                    }
+                },
+                Err(_) if name == "time_msc" || Self::column_default(name).is_some() => {
+                    coercions.push(SchemaCoercion {
+                        column: name.to_string(),
+                        kind: CoercionKind::AddColumn,
+                        detail: if name == "time_msc" {
+                            "Missing column derived from time".to_string()
+                        } else {
+                            "Missing column filled with its default".to_string()
+                        },
+                        rows_affected: height,
+                    });
+                    (Series::full_null(name, height, &expected), true)
+                }
+                Err(_) => {
+                    let mut issue = ValidationIssue::new(
+                        ValidationIssueKind::MissingColumn,
+                        Some(name),
+                        format!("Missing required column: {}", name),
+                    );
+                    issue.expected = Some(format!("{:?}", expected));
+                    issues.push(issue);
+                    continue;
+                }
+            };
+
+            let null_count = series.null_count();
+            match Self::fill_nulls(series, &columns) {
+                Ok(series) => columns.push(series),
+                Err(issue) => {
+                    issues.push(*issue);
+                    continue;
+                }
             }
+            if null_count > 0 && !added {
+                coercions.push(SchemaCoercion {
+                    column: name.to_string(),
+                    kind: CoercionKind::FillNull,
+                    detail: if name == "time_msc" {
+                        "Null values derived from time".to_string()
+                    } else {
+                        "Null values replaced with the column default".to_string()
+                    },
+                    rows_affected: null_count,
+                });
+            }
+        }
+
+        for series in df.get_columns() {
+            let name = series.name();
+            if deals_schema::REQUIRED_COLUMNS.contains(&name) {
+                continue;
+            }
+            let kind = match policy.extra_columns {
+                ExtraColumnsPolicy::Drop => CoercionKind::DropColumn,
+                ExtraColumnsPolicy::Keep => CoercionKind::KeepColumn,
+            };
+            if kind == CoercionKind::KeepColumn {
+                columns.push(series.clone());
+            }
+            coercions.push(SchemaCoercion {
+                column: name.to_string(),
+                kind,
+                detail: "Column is not part of the deals schema".to_string(),
+                rows_affected: height,
+            });
+        }
+
+        if !issues.is_empty() {
+            return Err(ValidationReport { row_count: height, issues });
+        }
+
+        let normalized = DataFrame::new(columns).map_err(|e| ValidationReport {
+            row_count: height,
+            issues: vec![ValidationIssue::new(
+                ValidationIssueKind::UnconvertibleValues,
+                None,
+                format!("Failed to build normalized deals table: {}", e),
+            )],
+        })?;
+
+        // Sanity check of the result; extra columns are expected when kept
+        let check = Self::check_frame(&normalized, policy.extra_columns == ExtraColumnsPolicy::Keep);
+        if !check.is_valid() {
+            return Err(check);
+        }
+
+        Ok((normalized, coercions))
+    }
+
+    /// Default of a column whose nulls may be filled in coerce mode
+    /// Identifying columns (ticket, time, type, entry, volume, price) have none
+    fn column_default(name: &str) -> Option<AnyValue<'static>> {
+        match name {
+            "order" | "magic" | "position_id" | "reason" => Some(AnyValue::Int64(0)),
+            "commission" | "swap" | "profit" | "fee" => Some(AnyValue::Float64(0.0)),
+            "symbol" | "comment" | "external_id" => Some(AnyValue::StringOwned("".into())),
+            _ => None,
         }
+    }
+
+    /// Converts a column to its schema type; nulls are preserved
+    fn coerce_column(
+        series: &Series,
+        expected: &DataType,
+        coercions: &mut Vec<SchemaCoercion>,
+    ) -> Result<Series, Box<ValidationIssue>> {
+        let name = series.name();
+        let actual = series.dtype();
+
+        let issue = |kind: ValidationIssueKind, message: String| {
+            let mut issue = ValidationIssue::new(kind, Some(name), message);
+            issue.expected = Some(format!("{:?}", expected));
+            issue.actual = Some(format!("{:?}", actual));
+            Box::new(issue)
+        };
+        let cast_error = |e: PolarsError| {
+            issue(
+                ValidationIssueKind::UnconvertibleValues,
+                format!(
+                    "Column '{}' cannot be converted from {:?} to {:?}: {}",
+                    name, actual, expected, e
+                ),
+            )
+        };
+
+        if Self::types_match(expected, actual) {
+            return match expected {
+                DataType::Float64 => Self::nan_to_null(series).map_err(cast_error),
+                _ => Ok(series.clone()),
+            };
+        }
+
+        let converted = match (expected, actual) {
+            (DataType::Int64, DataType::Datetime(unit, _)) => {
+                let per_second = match unit {
+                    TimeUnit::Nanoseconds => 1_000_000_000,
+                    TimeUnit::Microseconds => 1_000_000,
+                    TimeUnit::Milliseconds => 1_000,
+                };
+                // time_msc keeps milliseconds, every other integer column gets seconds
+                let divisor = if name == "time_msc" { per_second / 1_000 } else { per_second };
+                let raw = series.cast(&DataType::Int64).map_err(cast_error)?;
+                let values: Vec<Option<i64>> = raw
+                    .i64()
+                    .map_err(cast_error)?
+                    .into_iter()
+                    .map(|value| value.map(|value| value.div_euclid(divisor)))
+                    .collect();
+                Series::new(name, values)
+            }
+            (DataType::Int64, DataType::Float32 | DataType::Float64) => {
+                let floats = series.cast(&DataType::Float64).map_err(cast_error)?;
+                let floats = floats.f64().map_err(cast_error)?;
+                let not_whole = |value: Option<f64>| {
+                    value.is_some_and(|value| !value.is_nan() && (value.fract() != 0.0 || value.abs() >= 9.2e18))
+                };
+
+                let invalid = floats.into_iter().filter(|value| not_whole(*value)).count();
+                if invalid > 0 {
+                    let mut issue = issue(
+                        ValidationIssueKind::UnconvertibleValues,
+                        format!(
+                            "Column '{}' has {} value(s) that are not whole numbers",
+                            name, invalid
+                        ),
+                    );
+                    issue.invalid_count = Some(invalid);
+                    issue.sample_rows = Self::sample_rows(floats.into_iter().map(not_whole));
+                    return Err(issue);
+                }
+
+                let values: Vec<Option<i64>> = floats
+                    .into_iter()
+                    .map(|value| value.filter(|value| !value.is_nan()).map(|value| value as i64))
+                    .collect();
+                Series::new(name, values)
+            }
+            (DataType::Float64, DataType::Float32) => {
+                Self::nan_to_null(&series.cast(&DataType::Float64).map_err(cast_error)?).map_err(cast_error)?
+            }
+            (DataType::Int64 | DataType::Float64, actual) if actual.is_integer() || *actual == DataType::String => {
+                Self::strict_cast(series, expected).map_err(|mut failed| {
+                    failed.expected = Some(format!("{:?}", expected));
+                    failed.actual = Some(format!("{:?}", actual));
+                    failed
+                })?
+            }
+            (DataType::String, actual) if actual.is_numeric() => {
+                series.cast(expected).map_err(cast_error)?
+            }
+            _ => {
+                return Err(issue(
+                    ValidationIssueKind::WrongType,
+                    format!(
+                        "Column '{}' has type {:?} which cannot be converted to {:?}",
+                        name, actual, expected
+                    ),
+                ))
+            }
+        };
 
-        Ok(())
+        coercions.push(SchemaCoercion {
+            column: name.to_string(),
+            kind: CoercionKind::Cast,
+            detail: format!("Converted from {:?} to {:?}", actual, expected),
+            rows_affected: series.len(),
+        });
+        Ok(converted)
+    }
+
+    /// Casts a column, reporting the values that cannot be represented in the target type
+    fn strict_cast(series: &Series, expected: &DataType) -> Result<Series, Box<ValidationIssue>> {
+        let name = series.name();
+        let failed = |message: String| {
+            Box::new(ValidationIssue::new(ValidationIssueKind::UnconvertibleValues, Some(name), message))
+        };
+
+        let cast = series
+            .cast(expected)
+            .map_err(|e| failed(format!("Column '{}' cannot be converted to {:?}: {}", name, expected, e)))?;
+
+        // A non-strict cast turns unconvertible values into nulls
+        let lost: Vec<bool> = series
+            .is_not_null()
+            .into_iter()
+            .zip(&cast.is_null())
+            .map(|(present, missing)| present.unwrap_or(false) && missing.unwrap_or(false))
+            .collect();
+        let invalid = lost.iter().filter(|lost| **lost).count();
+        if invalid > 0 {
+            let mut issue = failed(format!(
+                "Column '{}' has {} value(s) that cannot be converted to {:?}",
+                name, invalid, expected
+            ));
+            issue.invalid_count = Some(invalid);
+            issue.sample_rows = Self::sample_rows(lost.into_iter());
+            return Err(issue);
+        }
+
+        Ok(cast)
+    }
+
+    /// Turns NaN (pandas' missing value) into null so it is filled like one
+    fn nan_to_null(series: &Series) -> PolarsResult<Series> {
+        let floats = series.f64()?;
+        if !floats.into_iter().flatten().any(f64::is_nan) {
+            return Ok(series.clone());
+        }
+        let values: Vec<Option<f64>> = floats
+            .into_iter()
+            .map(|value| value.filter(|value| !value.is_nan()))
+            .collect();
+        Ok(Series::new(series.name(), values))
+    }
+
+    /// Fills the nulls of a coerced column with its default
+    /// `time_msc` is derived from the already normalized `time` column
+    fn fill_nulls(series: Series, normalized: &[Series]) -> Result<Series, Box<ValidationIssue>> {
+        if series.null_count() == 0 {
+            return Ok(series);
+        }
+        let name = series.name().to_string();
+        let failed = |e: PolarsError| {
+            Box::new(ValidationIssue::new(
+                ValidationIssueKind::NullValues,
+                Some(&name),
+                format!("Failed to fill null values of '{}': {}", name, e),
+            ))
+        };
+
+        if name == "time_msc" {
+            // Without a usable time column (reported on its own) time_msc cannot be derived
+            let Some(time) = normalized.iter().find(|column| column.name() == "time") else {
+                return Err(Box::new(ValidationIssue::new(
+                    ValidationIssueKind::NullValues,
+                    Some(&name),
+                    "Column 'time_msc' cannot be derived without a valid 'time' column",
+                )));
+            };
+            let derived = (time * 1000).i64().cloned().map_err(failed)?;
+            let values: Vec<Option<i64>> = series
+                .i64()
+                .map_err(failed)?
+                .into_iter()
+                .zip(&derived)
+                .map(|(value, derived)| value.or(derived))
+                .collect();
+            return Ok(Series::new(&name, values));
+        }
+
+        let Some(default) = Self::column_default(&name) else {
+            let hint = "The column has no default to fill them with.";
+            let issue = Self::null_issue(&series, hint)
+                .unwrap_or_else(|| ValidationIssue::new(ValidationIssueKind::NullValues, Some(&name), hint));
+            return Err(Box::new(issue));
+        };
+        let fill = Series::from_any_values(&name, &[default], true)
+            .map_err(failed)?
+            .new_from_index(0, series.len());
+        let mask = series.is_not_null();
+        series.zip_with(&mask, &fill).map_err(failed)
+    }
+
+    /// Null-values issue of a column, if it has nulls
+    fn null_issue(series: &Series, hint: &str) -> Option<ValidationIssue> {
+        let null_count = series.null_count();
+        if null_count == 0 {
+            return None;
+        }
+
+        let mut issue = ValidationIssue::new(
+            ValidationIssueKind::NullValues,
+            Some(series.name()),
+            format!(
+                "Column '{}' contains {} null value(s). {}",
+                series.name(),
+                null_count,
+                hint
+            ),
+        );
+        issue.null_count = Some(null_count);
+        issue.sample_rows = Self::sample_rows(series.is_null().into_iter().map(|null| null.unwrap_or(false)));
+        Some(issue)
+    }
+
+    /// First row indices where `flags` is set
+    fn sample_rows(flags: impl Iterator<Item = bool>) -> Vec<usize> {
+        flags
+            .enumerate()
+            .filter(|(_, flag)| *flag)
+            .map(|(index, _)| index)
+            .take(MAX_SAMPLE_ROWS)
+            .collect()
     }
 
     /// Helper function to check if types match
@@ -130,3 +503,137 @@ impl ParquetValidator {
     }
 }
 
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::features::deals::model::ValidationMode;
+
+    /// Two deals as pandas writes them: Int32 and Float32 columns, nullable integers,
+    /// NaN amounts, no `time_msc` and no `external_id`
+    fn pandas_columns() -> Vec<Series> {
+        vec![
+            Series::new("ticket", &[1_i64, 2]),
+            Series::new("order", &[10_i32, 11]),
+            Series::new("time", &[1_700_000_000_i64, 1_700_000_060]),
+            Series::new("type", &[0_i32, 1]),
+            Series::new("entry", &[0_i32, 1]),
+            Series::new("magic", &[Some(7_i64), None]),
+            Series::new("position_id", &[10.0_f64, f64::NAN]),
+            Series::new("reason", &[0_i8, 0]),
+            Series::new("volume", &[0.5_f32, 0.25]),
+            Series::new("price", &[1.1_f64, 1.2]),
+            Series::new("commission", &[-1.0_f64, f64::NAN]),
+            Series::new("swap", &[0.0_f64, 0.0]),
+            Series::new("profit", &[0.0_f64, 50.0]),
+            Series::new("fee", &[0.0_f32, 0.0]),
+            Series::new("symbol", &["EURUSD", "EURUSD"]),
+            Series::new("comment", &[Some("entry"), None]),
+        ]
+    }
+
+    fn policy(extra_columns: ExtraColumnsPolicy) -> ValidationPolicy {
+        ValidationPolicy {
+            mode: ValidationMode::Coerce,
+            extra_columns,
+            ..Default::default()
+        }
+    }
+
+    fn coercion<'a>(coercions: &'a [SchemaCoercion], column: &str) -> &'a SchemaCoercion {
+        coercions.iter().find(|coercion| coercion.column == column).unwrap()
+    }
+
+    fn i64_values(df: &DataFrame, column: &str) -> Vec<Option<i64>> {
+        df.column(column).unwrap().i64().unwrap().into_iter().collect()
+    }
+
+    fn f64_values(df: &DataFrame, column: &str) -> Vec<Option<f64>> {
+        df.column(column).unwrap().f64().unwrap().into_iter().collect()
+    }
+
+    #[test]
+    fn normalizes_pandas_types() {
+        let df = DataFrame::new(pandas_columns()).unwrap();
+        let (normalized, coercions) =
+            ParquetValidator::normalize_deals(&df, &policy(ExtraColumnsPolicy::Drop)).unwrap();
+
+        assert!(ParquetValidator::check_frame(&normalized, false).is_valid());
+        let names: Vec<&str> = normalized.get_column_names();
+        assert_eq!(names, deals_schema::REQUIRED_COLUMNS.to_vec());
+
+        assert_eq!(i64_values(&normalized, "order"), vec![Some(10), Some(11)]);
+        assert_eq!(i64_values(&normalized, "type"), vec![Some(0), Some(1)]);
+        assert_eq!(f64_values(&normalized, "volume"), vec![Some(0.5), Some(0.25)]);
+        assert_eq!(coercion(&coercions, "order").kind, CoercionKind::Cast);
+        assert_eq!(coercion(&coercions, "volume").kind, CoercionKind::Cast);
+    }
+
+    #[test]
+    fn fills_nullable_columns_and_derives_time_msc() {
+        let df = DataFrame::new(pandas_columns()).unwrap();
+        let (normalized, coercions) =
+            ParquetValidator::normalize_deals(&df, &policy(ExtraColumnsPolicy::Drop)).unwrap();
+
+        assert_eq!(i64_values(&normalized, "magic"), vec![Some(7), Some(0)]);
+        assert_eq!(i64_values(&normalized, "position_id"), vec![Some(10), Some(0)]);
+        assert_eq!(f64_values(&normalized, "commission"), vec![Some(-1.0), Some(0.0)]);
+        assert_eq!(
+            i64_values(&normalized, "time_msc"),
+            vec![Some(1_700_000_000_000), Some(1_700_000_060_000)]
+        );
+        let comments: Vec<Option<&str>> = normalized.column("comment").unwrap().str().unwrap().into_iter().collect();
+        assert_eq!(comments, vec![Some("entry"), Some("")]);
+
+        let magic = coercion(&coercions, "magic");
+        assert_eq!((magic.kind, magic.rows_affected), (CoercionKind::FillNull, 1));
+        assert_eq!(coercion(&coercions, "commission").kind, CoercionKind::FillNull);
+        assert_eq!(coercion(&coercions, "time_msc").kind, CoercionKind::AddColumn);
+        assert_eq!(coercion(&coercions, "external_id").kind, CoercionKind::AddColumn);
+    }
+
+    #[test]
+    fn drops_or_keeps_extra_columns() {
+        let mut columns = pandas_columns();
+        columns.push(Series::new("strategy", &["breakout", "breakout"]));
+        let df = DataFrame::new(columns).unwrap();
+
+        let (dropped, coercions) = ParquetValidator::normalize_deals(&df, &policy(ExtraColumnsPolicy::Drop)).unwrap();
+        assert!(dropped.column("strategy").is_err());
+        assert_eq!(coercion(&coercions, "strategy").kind, CoercionKind::DropColumn);
+
+        let (kept, coercions) = ParquetValidator::normalize_deals(&df, &policy(ExtraColumnsPolicy::Keep)).unwrap();
+        assert_eq!(kept.get_column_names().last(), Some(&"strategy"));
+        assert_eq!(kept.width(), deals_schema::REQUIRED_COLUMNS.len() + 1);
+        assert_eq!(coercion(&coercions, "strategy").kind, CoercionKind::KeepColumn);
+    }
+
+    #[test]
+    fn rejects_floats_that_are_not_whole_numbers() {
+        let mut columns = pandas_columns();
+        columns[0] = Series::new("ticket", &[1.0_f64, 2.5]);
+        columns[2] = Series::new("time", &[1.7e9_f64, 9.3e18]);
+        let df = DataFrame::new(columns).unwrap();
+
+        let report = ParquetValidator::normalize_deals(&df, &policy(ExtraColumnsPolicy::Drop)).unwrap_err();
+        let columns: Vec<Option<&str>> = report.issues.iter().map(|issue| issue.column.as_deref()).collect();
+        assert_eq!(columns, vec![Some("ticket"), Some("time"), Some("time_msc")]);
+
+        let ticket = &report.issues[0];
+        assert_eq!(ticket.kind, ValidationIssueKind::UnconvertibleValues);
+        assert_eq!((ticket.invalid_count, ticket.sample_rows.clone()), (Some(1), vec![1]));
+        assert_eq!(report.issues[1].invalid_count, Some(1));
+    }
+
+    #[test]
+    fn rejects_nulls_in_columns_without_a_default() {
+        let mut columns = pandas_columns();
+        columns[3] = Series::new("type", &[Some(0_i64), None]);
+        let df = DataFrame::new(columns).unwrap();
+
+        let report = ParquetValidator::normalize_deals(&df, &policy(ExtraColumnsPolicy::Drop)).unwrap_err();
+        assert_eq!(report.issues.len(), 1);
+        let issue = &report.issues[0];
+        assert_eq!((issue.kind, issue.column.as_deref()), (ValidationIssueKind::NullValues, Some("type")));
+        assert_eq!((issue.null_count, issue.sample_rows.clone()), (Some(1), vec![1]));
+    }
+}
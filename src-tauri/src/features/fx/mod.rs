@@ -0,0 +1,2 @@
+/// FX conversion feature module
+pub mod service;
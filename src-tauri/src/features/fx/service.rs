@@ -0,0 +1,387 @@
+//! Currency conversion with historical daily closes of FX pairs from the asset cache
+
+use crate::features::asset::model::OHLCData;
+use crate::features::asset::service::AssetService;
+use crate::features::accounts::registry::AccountRegistry;
+use crate::shared::error::AppError;
+use polars::prelude::*;
+use std::collections::{BTreeMap, HashMap, VecDeque};
+
+/// Timeframe whose closes are used as conversion rates
+const RATE_TIMEFRAME: &str = "1D";
+/// Currency preferred as the intermediate leg of cross rates
+const PIVOT_CURRENCY: &str = "USD";
+/// How far from a cached close (before the first one, or after the close used) a rate may
+/// still be taken from it
+const MAX_RATE_GAP_SECONDS: i64 = 7 * 86_400;
+/// Deal columns holding amounts in the account currency
+const AMOUNT_COLUMNS: [&str; 4] = ["profit", "commission", "swap", "fee"];
+/// Temporary column holding the per-deal rate
+const RATE_COLUMN: &str = "__fx_rate";
+
+/// One conversion step through a cached pair
+#[derive(Debug, Clone, PartialEq)]
+struct Leg {
+    symbol: String,
+    /// True when the pair is quoted the other way round (divide by its close)
+    inverted: bool,
+}
+
+/// Converts amounts to a target currency at the daily close of the conversion day
+/// Direct pairs (`EURUSD`), inverted pairs (`USDJPY` for JPY -> USD) and cross rates
+/// through other cached pairs (`GBP -> USD -> JPY`) are supported
+pub struct CurrencyConverter {
+    target: String,
+    /// Cached six-letter FX symbols by (base, quote)
+    pairs: BTreeMap<(String, String), String>,
+    accounts: AccountRegistry,
+    routes: HashMap<String, Vec<Leg>>,
+    closes: HashMap<String, Vec<OHLCData>>,
+}
+
+impl CurrencyConverter {
+    /// Creates a converter to `target` (three-letter ISO code, e.g. "USD")
+    pub fn new(target: &str) -> Result<Self, AppError> {
+        let target = Self::normalize_currency(target)?;
+
+        let pairs = AssetService::list_symbols()?
+            .into_iter()
+            .filter(|symbol| symbol.len() == 6 && symbol.chars().all(|c| c.is_ascii_alphabetic()))
+            .map(|symbol| {
+                let upper = symbol.to_ascii_uppercase();
+                ((upper[..3].to_string(), upper[3..].to_string()), symbol)
+            })
+            .collect();
+
+        Ok(Self {
+            target,
+            pairs,
+            accounts: AccountRegistry::load()?,
+            routes: HashMap::new(),
+            closes: HashMap::new(),
+        })
+    }
+
+    /// Validates and uppercases a three-letter currency code
+    pub fn normalize_currency(currency: &str) -> Result<String, AppError> {
+        let currency = currency.trim().to_ascii_uppercase();
+        if currency.len() != 3 || !currency.chars().all(|c| c.is_ascii_alphabetic()) {
+            return Err(AppError::new(format!(
+                "Invalid currency '{}'. Expected a three-letter ISO code (e.g. \"USD\")",
+                currency
+            )));
+        }
+        Ok(currency)
+    }
+
+    /// Currency of an account, as set with `set_account_currency` or `update_account`
+    pub fn account_currency(&self, account: &str) -> Result<String, AppError> {
+        let account = account.strip_suffix(".parquet").unwrap_or(account);
+        self.accounts.get(account).currency.ok_or_else(|| {
+            AppError::new(format!(
+                "Currency of account {} is not set. Set it with set_account_currency or update_account before converting to {}",
+                account, self.target
+            ))
+        })
+    }
+
+    /// Rate converting one unit of `from` to the target currency at `time` (UTC seconds)
+    pub fn rate(&mut self, from: &str, time: i64) -> Result<f64, AppError> {
+        let from = Self::normalize_currency(from)?;
+        if from == self.target {
+            return Ok(1.0);
+        }
+
+        let route = match self.routes.get(&from) {
+            Some(route) => route.clone(),
+            None => {
+                let route = Self::find_route(&self.pairs, &from, &self.target)?;
+                self.routes.insert(from.clone(), route.clone());
+                route
+            }
+        };
+
+        let mut rate = 1.0;
+        for leg in &route {
+            let close = self.close_at(&leg.symbol, time)?;
+            rate = if leg.inverted { rate / close } else { rate * close };
+        }
+        Ok(rate)
+    }
+
+    /// Converts an amount in `from` to the target currency at `time` (UTC seconds)
+    pub fn convert(&mut self, amount: f64, from: &str, time: i64) -> Result<f64, AppError> {
+        Ok(amount * self.rate(from, time)?)
+    }
+
+    /// Converts the amount columns (profit, commission, swap, fee) of an account's deals
+    /// Deal times are shifted to UTC with the account's timezone offset before rates are looked up
+    pub fn convert_deals_frame(&mut self, df: DataFrame, account: &str) -> Result<DataFrame, AppError> {
+        let currency = self.account_currency(account)?;
+        if currency == self.target || df.height() == 0 {
+            return Ok(df);
+        }
+
+        let account = account.strip_suffix(".parquet").unwrap_or(account);
+        let offset_seconds = self.accounts.get(account).timezone_offset_minutes * 60;
+
+        let times = df
+            .column("time")
+            .and_then(|column| column.cast(&DataType::Int64))
+            .map_err(|e| AppError::new(format!("Failed to read 'time' column: {}", e)))?;
+        let times = times
+            .i64()
+            .map_err(|e| AppError::new(format!("Failed to cast 'time' column: {}", e)))?;
+
+        // Rates only change once per day
+        let mut rates_by_day: HashMap<i64, f64> = HashMap::new();
+        let mut rates = Vec::with_capacity(df.height());
+        for time in times.into_iter() {
+            let utc = time.unwrap_or_default() - offset_seconds;
+            let day = utc.div_euclid(86_400);
+            let rate = match rates_by_day.get(&day) {
+                Some(rate) => *rate,
+                None => {
+                    let rate = self.rate(&currency, utc)?;
+                    rates_by_day.insert(day, rate);
+                    rate
+                }
+            };
+            rates.push(rate);
+        }
+
+        let present: Vec<Expr> = AMOUNT_COLUMNS
+            .iter()
+            .filter(|name| df.get_column_index(name).is_some())
+            .map(|name| (col(name).cast(DataType::Float64) * col(RATE_COLUMN)).alias(name))
+            .collect();
+
+        let mut df = df;
+        df.with_column(Series::new(RATE_COLUMN, rates))
+            .map_err(|e| AppError::new(format!("Failed to add conversion rates: {}", e)))?;
+
+        df.lazy()
+            .with_columns(present)
+            .drop([RATE_COLUMN])
+            .collect()
+            .map_err(|e| AppError::new(format!("Failed to convert {} amounts: {}", account, e)))
+    }
+
+    /// Shortest chain of `pairs` from `from` to `target`
+    /// Routes through the pivot currency are preferred among equally short ones
+    fn find_route(
+        pairs: &BTreeMap<(String, String), String>,
+        from: &str,
+        target: &str,
+    ) -> Result<Vec<Leg>, AppError> {
+        let mut edges: BTreeMap<&str, Vec<(&str, Leg)>> = BTreeMap::new();
+        for ((base, quote), symbol) in pairs {
+            edges.entry(base).or_default().push((
+                quote,
+                Leg { symbol: symbol.clone(), inverted: false },
+            ));
+            edges.entry(quote).or_default().push((
+                base,
+                Leg { symbol: symbol.clone(), inverted: true },
+            ));
+        }
+        for neighbours in edges.values_mut() {
+            neighbours.sort_by_key(|(currency, _)| *currency != PIVOT_CURRENCY);
+        }
+
+        let mut previous: HashMap<&str, (&str, Leg)> = HashMap::new();
+        let mut queue = VecDeque::from([from]);
+        while let Some(currency) = queue.pop_front() {
+            if currency == target {
+                break;
+            }
+            for (next, leg) in edges.get(currency).into_iter().flatten() {
+                if *next != from && !previous.contains_key(next) {
+                    previous.insert(next, (currency, leg.clone()));
+                    queue.push_back(next);
+                }
+            }
+        }
+
+        let mut route = Vec::new();
+        let mut currency = target;
+        while currency != from {
+            let (prev, leg) = previous.get(currency).ok_or_else(|| {
+                AppError::new(format!(
+                    "No cached FX pairs convert {} to {}. Pull daily bars of a pair such as {}{} or {}{}",
+                    from, target, from, target, target, from
+                ))
+            })?;
+            route.push(leg.clone());
+            currency = prev;
+        }
+        route.reverse();
+        Ok(route)
+    }
+
+    /// Close of the daily bar of `symbol` containing `time`, loading the closes on first use
+    fn close_at(&mut self, symbol: &str, time: i64) -> Result<f64, AppError> {
+        if !self.closes.contains_key(symbol) {
+            let bars = AssetService::retrieve_asset_ochl(symbol, RATE_TIMEFRAME, None).map_err(|e| {
+                AppError::new(format!(
+                    "Failed to load {} closes of {} for currency conversion: {}",
+                    RATE_TIMEFRAME, symbol, e.message
+                ))
+            })?;
+            self.closes.insert(symbol.to_string(), bars);
+        }
+        Self::close_in(&self.closes[symbol], symbol, time)
+    }
+
+    /// Close of the bar of `bars` containing `time`
+    /// Before the first bar, the first close is used if it is close enough; the last close at
+    /// or before `time` must be at most `MAX_RATE_GAP_SECONDS` old
+    fn close_in(bars: &[OHLCData], symbol: &str, time: i64) -> Result<f64, AppError> {
+        let index = bars.partition_point(|bar| bar.time <= time);
+        let bar = match index.checked_sub(1) {
+            Some(i) => &bars[i],
+            None => bars
+                .first()
+                .filter(|bar| bar.time - time <= MAX_RATE_GAP_SECONDS)
+                .ok_or_else(|| {
+                    AppError::new(format!(
+                        "No {} close of {} at or before {} for currency conversion",
+                        RATE_TIMEFRAME, symbol, time
+                    ))
+                })?,
+        };
+        if time - bar.time > MAX_RATE_GAP_SECONDS {
+            return Err(AppError::new(format!(
+                "The last {} close of {} before {} is from {}, more than {} days earlier. Pull newer {} data for currency conversion",
+                RATE_TIMEFRAME,
+                symbol,
+                time,
+                bar.time,
+                MAX_RATE_GAP_SECONDS / 86_400,
+                symbol
+            )));
+        }
+
+        if !(bar.close.is_finite() && bar.close > 0.0) {
+            return Err(AppError::new(format!(
+                "Invalid {} close {} of {} at {}",
+                RATE_TIMEFRAME, bar.close, symbol, bar.time
+            )));
+        }
+        Ok(bar.close)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::features::accounts::model::AccountMetadata;
+
+    const DAY: i64 = 86_400;
+
+    fn pairs(symbols: &[&str]) -> BTreeMap<(String, String), String> {
+        symbols
+            .iter()
+            .map(|symbol| ((symbol[..3].to_string(), symbol[3..].to_string()), symbol.to_string()))
+            .collect()
+    }
+
+    fn leg(symbol: &str, inverted: bool) -> Leg {
+        Leg { symbol: symbol.to_string(), inverted }
+    }
+
+    fn bar(time: i64, close: f64) -> OHLCData {
+        OHLCData { time, open: close, high: close, low: close, close }
+    }
+
+    #[test]
+    fn finds_direct_and_inverted_routes() {
+        let pairs = pairs(&["EURUSD", "USDJPY"]);
+        assert_eq!(CurrencyConverter::find_route(&pairs, "EUR", "USD").unwrap(), vec![leg("EURUSD", false)]);
+        assert_eq!(CurrencyConverter::find_route(&pairs, "JPY", "USD").unwrap(), vec![leg("USDJPY", true)]);
+    }
+
+    #[test]
+    fn finds_cross_routes_through_the_pivot_currency() {
+        let pairs = pairs(&["EURJPY", "GBPEUR", "GBPUSD", "USDJPY"]);
+        assert_eq!(
+            CurrencyConverter::find_route(&pairs, "GBP", "JPY").unwrap(),
+            vec![leg("GBPUSD", false), leg("USDJPY", false)]
+        );
+        assert_eq!(
+            CurrencyConverter::find_route(&pairs, "JPY", "GBP").unwrap(),
+            vec![leg("USDJPY", true), leg("GBPUSD", true)]
+        );
+    }
+
+    #[test]
+    fn reports_missing_routes() {
+        let pairs = pairs(&["EURUSD", "AUDNZD"]);
+        assert!(CurrencyConverter::find_route(&pairs, "AUD", "USD").is_err());
+        assert!(CurrencyConverter::find_route(&pairs, "CHF", "USD").is_err());
+    }
+
+    #[test]
+    fn takes_the_close_at_or_before_the_time() {
+        let bars = [bar(10 * DAY, 1.1), bar(11 * DAY, 1.2)];
+        assert_eq!(CurrencyConverter::close_in(&bars, "EURUSD", 10 * DAY + 3_600).unwrap(), 1.1);
+        assert_eq!(CurrencyConverter::close_in(&bars, "EURUSD", 11 * DAY).unwrap(), 1.2);
+    }
+
+    #[test]
+    fn uses_the_first_close_shortly_before_the_first_bar() {
+        let bars = [bar(10 * DAY, 1.1), bar(11 * DAY, 1.2)];
+        assert_eq!(CurrencyConverter::close_in(&bars, "EURUSD", 10 * DAY - 3 * DAY).unwrap(), 1.1);
+        assert!(CurrencyConverter::close_in(&bars, "EURUSD", 10 * DAY - MAX_RATE_GAP_SECONDS - 1).is_err());
+    }
+
+    #[test]
+    fn rejects_stale_closes() {
+        let bars = [bar(10 * DAY, 1.1), bar(11 * DAY, 1.2)];
+        assert_eq!(CurrencyConverter::close_in(&bars, "EURUSD", 11 * DAY + MAX_RATE_GAP_SECONDS).unwrap(), 1.2);
+        assert!(CurrencyConverter::close_in(&bars, "EURUSD", 11 * DAY + MAX_RATE_GAP_SECONDS + 1).is_err());
+        assert!(CurrencyConverter::close_in(&[], "EURUSD", 11 * DAY).is_err());
+    }
+
+    #[test]
+    fn converts_deal_amounts_at_the_close_of_the_utc_day() {
+        let mut accounts = AccountRegistry::default();
+        accounts.accounts.insert(
+            "demo".to_string(),
+            AccountMetadata {
+                currency: Some("JPY".to_string()),
+                timezone_offset_minutes: 120,
+                ..Default::default()
+            },
+        );
+        let mut converter = CurrencyConverter {
+            target: "USD".to_string(),
+            pairs: pairs(&["USDJPY"]),
+            accounts,
+            routes: HashMap::new(),
+            closes: HashMap::from([(
+                "USDJPY".to_string(),
+                vec![bar(10 * DAY, 100.0), bar(11 * DAY, 200.0)],
+            )]),
+        };
+
+        // 01:00 server time on day 11 is 23:00 UTC on day 10
+        let df = df!(
+            "time" => [11 * DAY + 3_600, 11 * DAY + 7_200],
+            "profit" => [1_000.0, 1_000.0],
+            "commission" => [-100.0, -100.0],
+            "swap" => [0.0, 0.0],
+            "fee" => [0.0, -200.0],
+        )
+        .unwrap();
+        let converted = converter.convert_deals_frame(df, "demo").unwrap();
+
+        let column = |name: &str| -> Vec<Option<f64>> {
+            converted.column(name).unwrap().f64().unwrap().into_iter().collect()
+        };
+        assert_eq!(column("profit"), vec![Some(10.0), Some(5.0)]);
+        assert_eq!(column("commission"), vec![Some(-1.0), Some(-0.5)]);
+        assert_eq!(column("fee"), vec![Some(0.0), Some(-1.0)]);
+        assert!(converted.column(RATE_COLUMN).is_err());
+    }
+}
@@ -1,10 +1,11 @@
 //! Greet command handlers
 
 use crate::features::greet::service::GreetService;
+use crate::shared::error::AppError;
 
 /// Tauri command: greet
 /// Returns a greeting message
 #[tauri::command]
 pub fn greet(name: String) -> Result<String, String> {
-    GreetService::create_greeting(&name).map_err(|e| e.message)
+    GreetService::create_greeting(&name).map_err(AppError::into_command_error)
 }
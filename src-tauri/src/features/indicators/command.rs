@@ -0,0 +1,27 @@
+//! Indicator command handlers
+
+use crate::features::asset::model::TimeRange;
+use crate::features::indicators::model::{IndicatorResult, IndicatorSpec};
+use crate::features::indicators::service::IndicatorService;
+use crate::shared::error::AppError;
+
+/// Tauri command: compute_indicators
+/// Computes technical indicators over the cached bars of a symbol
+/// 
+/// # Arguments
+/// * `symbol` - The symbol name (e.g., "EURUSD")
+/// * `timeframe` - The timeframe (e.g., "1M")
+/// * `range` - Optional inclusive time range in Unix seconds (`{ start, end }`, either bound optional)
+/// * `specs` - Indicators to compute (e.g., `{ "kind": "sma", "period": 20 }`)
+/// 
+/// Returns the bar timestamps and one series per indicator, aligned with the bars
+#[tauri::command]
+pub fn compute_indicators(
+    symbol: String,
+    timeframe: String,
+    range: Option<TimeRange>,
+    specs: Vec<IndicatorSpec>,
+) -> Result<IndicatorResult, String> {
+    IndicatorService::compute_indicators(&symbol, &timeframe, range, &specs)
+        .map_err(AppError::into_command_error)
+}
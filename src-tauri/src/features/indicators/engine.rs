@@ -0,0 +1,334 @@
+//! Indicator engine - builds Polars expressions for each indicator
+//!
+//! Every indicator is expressed over the OHLCV frame produced by
+//! `ParquetReaderService::scan_ohlcv` (`time`, `open`, `high`, `low`, `close`, `volume`),
+//! so all requested indicators are evaluated in a single pass over the bars.
+
+use crate::shared::error::AppError;
+use crate::shared::utils::timeframe;
+use crate::features::indicators::model::{IndicatorKind, PriceSource, VwapAnchor};
+use polars::prelude::*;
+use std::sync::Arc;
+
+/// Recursive (EMA / Wilder) indicators are warmed up over this many periods,
+/// after which the influence of the seed value is below 1%
+const RECURSIVE_WARMUP_PERIODS: usize = 5;
+
+/// Longest accepted period; keeps rolling windows and the warmup history bounded
+const MAX_PERIOD: usize = 10_000;
+
+/// Builds indicator expressions, default identifiers and warmup requirements
+pub struct IndicatorEngine;
+
+impl IndicatorEngine {
+    /// Validates the parameters of an indicator
+    pub fn validate(kind: &IndicatorKind) -> Result<(), AppError> {
+        let check_period = |name: &str, period: usize| {
+            if period == 0 {
+                Err(AppError::new(format!("Indicator parameter '{}' must be at least 1", name)))
+            } else if period > MAX_PERIOD {
+                Err(AppError::new(format!(
+                    "Indicator parameter '{}' must be at most {}",
+                    name, MAX_PERIOD
+                )))
+            } else {
+                Ok(())
+            }
+        };
+
+        match kind {
+            IndicatorKind::Sma { period, .. }
+            | IndicatorKind::Ema { period, .. }
+            | IndicatorKind::Wma { period, .. }
+            | IndicatorKind::Rsi { period, .. }
+            | IndicatorKind::Atr { period }
+            | IndicatorKind::Adx { period }
+            | IndicatorKind::Donchian { period } => check_period("period", *period),
+            IndicatorKind::Macd { fast, slow, signal, .. } => {
+                check_period("fast", *fast)?;
+                check_period("slow", *slow)?;
+                check_period("signal", *signal)?;
+                if fast >= slow {
+                    return Err(AppError::new("MACD 'fast' period must be shorter than 'slow'"));
+                }
+                Ok(())
+            }
+            IndicatorKind::Bollinger { period, std_dev, .. } => {
+                check_period("period", *period)?;
+                if !std_dev.is_finite() || *std_dev <= 0.0 {
+                    return Err(AppError::new("Bollinger 'std_dev' must be a positive number"));
+                }
+                Ok(())
+            }
+            IndicatorKind::Stochastic { k_period, k_smoothing, d_period } => {
+                check_period("k_period", *k_period)?;
+                check_period("k_smoothing", *k_smoothing)?;
+                check_period("d_period", *d_period)
+            }
+            IndicatorKind::Vwap { .. } => Ok(()),
+        }
+    }
+
+    /// Identifier used when the spec does not provide one (e.g. "sma_20", "macd_12_26_9")
+    pub fn default_id(kind: &IndicatorKind) -> String {
+        match kind {
+            IndicatorKind::Sma { period, .. } => format!("sma_{}", period),
+            IndicatorKind::Ema { period, .. } => format!("ema_{}", period),
+            IndicatorKind::Wma { period, .. } => format!("wma_{}", period),
+            IndicatorKind::Rsi { period, .. } => format!("rsi_{}", period),
+            IndicatorKind::Macd { fast, slow, signal, .. } => {
+                format!("macd_{}_{}_{}", fast, slow, signal)
+            }
+            IndicatorKind::Bollinger { period, std_dev, .. } => {
+                format!("bollinger_{}_{}", period, std_dev)
+            }
+            IndicatorKind::Atr { period } => format!("atr_{}", period),
+            IndicatorKind::Stochastic { k_period, k_smoothing, d_period } => {
+                format!("stochastic_{}_{}_{}", k_period, k_smoothing, d_period)
+            }
+            IndicatorKind::Adx { period } => format!("adx_{}", period),
+            IndicatorKind::Vwap { anchor } => match anchor {
+                VwapAnchor::Day => "vwap_day".to_string(),
+                VwapAnchor::Week => "vwap_week".to_string(),
+            },
+            IndicatorKind::Donchian { period } => format!("donchian_{}", period),
+        }
+    }
+
+    /// Number of bars of history needed before the first output bar
+    pub fn warmup_bars(kind: &IndicatorKind, timeframe_seconds: i64) -> usize {
+        match kind {
+            IndicatorKind::Sma { period, .. }
+            | IndicatorKind::Wma { period, .. }
+            | IndicatorKind::Bollinger { period, .. }
+            | IndicatorKind::Donchian { period } => *period,
+            IndicatorKind::Ema { period, .. }
+            | IndicatorKind::Rsi { period, .. }
+            | IndicatorKind::Atr { period } => period * RECURSIVE_WARMUP_PERIODS,
+            IndicatorKind::Macd { slow, signal, .. } => (slow + signal) * RECURSIVE_WARMUP_PERIODS,
+            // DX is smoothed twice (directional movement, then ADX itself)
+            IndicatorKind::Adx { period } => period * 2 * RECURSIVE_WARMUP_PERIODS,
+            IndicatorKind::Stochastic { k_period, k_smoothing, d_period } => {
+                k_period + k_smoothing + d_period
+            }
+            IndicatorKind::Vwap { anchor } => {
+                let session_seconds = Self::session_seconds(*anchor);
+                (session_seconds / timeframe_seconds.max(1)) as usize + 1
+            }
+        }
+    }
+
+    /// Whether the indicator needs a volume column
+    pub fn needs_volume(kind: &IndicatorKind) -> bool {
+        matches!(kind, IndicatorKind::Vwap { .. })
+    }
+
+    /// Output lines of an indicator as (line name, expression)
+    pub fn expressions(kind: &IndicatorKind) -> Vec<(&'static str, Expr)> {
+        match kind {
+            IndicatorKind::Sma { period, source } => {
+                vec![("value", Self::sma(Self::source(*source), *period))]
+            }
+            IndicatorKind::Ema { period, source } => {
+                vec![("value", Self::ema(Self::source(*source), *period))]
+            }
+            IndicatorKind::Wma { period, source } => {
+                vec![("value", Self::wma(Self::source(*source), *period))]
+            }
+            IndicatorKind::Rsi { period, source } => {
+                vec![("value", Self::rsi(Self::source(*source), *period))]
+            }
+            IndicatorKind::Macd { fast, slow, signal, source } => {
+                let price = Self::source(*source);
+                let macd = Self::ema(price.clone(), *fast) - Self::ema(price, *slow);
+                let signal_line = Self::ema(macd.clone(), *signal);
+                vec![
+                    ("macd", macd.clone()),
+                    ("signal", signal_line.clone()),
+                    ("histogram", macd - signal_line),
+                ]
+            }
+            IndicatorKind::Bollinger { period, std_dev, source } => {
+                let price = Self::source(*source);
+                let middle = Self::sma(price.clone(), *period);
+                let deviation = Self::rolling_std(price, *period) * lit(*std_dev);
+                vec![
+                    ("middle", middle.clone()),
+                    ("upper", middle.clone() + deviation.clone()),
+                    ("lower", middle - deviation),
+                ]
+            }
+            IndicatorKind::Atr { period } => vec![("value", Self::wilder(Self::true_range(), *period))],
+            IndicatorKind::Stochastic { k_period, k_smoothing, d_period } => {
+                let lowest = col("low").rolling_min(Self::window(*k_period));
+                let highest = col("high").rolling_max(Self::window(*k_period));
+                let raw_k = Self::safe_div(
+                    (col("close") - lowest.clone()) * lit(100.0),
+                    highest - lowest,
+                );
+                let k = if *k_smoothing > 1 {
+                    Self::sma(raw_k, *k_smoothing)
+                } else {
+                    raw_k
+                };
+                let d = Self::sma(k.clone(), *d_period);
+                vec![("k", k), ("d", d)]
+            }
+            IndicatorKind::Adx { period } => {
+                let up_move = col("high") - col("high").shift(lit(1));
+                let down_move = col("low").shift(lit(1)) - col("low");
+                let plus_dm = when(up_move.clone().gt(down_move.clone()).and(up_move.clone().gt(lit(0.0))))
+                    .then(up_move.clone())
+                    .otherwise(lit(0.0));
+                let minus_dm = when(down_move.clone().gt(up_move).and(down_move.clone().gt(lit(0.0))))
+                    .then(down_move)
+                    .otherwise(lit(0.0));
+
+                let atr = Self::wilder(Self::true_range(), *period);
+                let plus_di = Self::safe_div(Self::wilder(plus_dm, *period) * lit(100.0), atr.clone());
+                let minus_di = Self::safe_div(Self::wilder(minus_dm, *period) * lit(100.0), atr);
+                let dx = Self::safe_div(
+                    (plus_di.clone() - minus_di.clone()).abs() * lit(100.0),
+                    plus_di.clone() + minus_di.clone(),
+                );
+                vec![
+                    ("adx", Self::wilder(dx, *period)),
+                    ("plus_di", plus_di),
+                    ("minus_di", minus_di),
+                ]
+            }
+            IndicatorKind::Vwap { anchor } => {
+                let session = (col("time") - lit(Self::session_offset(*anchor)))
+                    .floor_div(lit(Self::session_seconds(*anchor)));
+                let typical = Self::source(PriceSource::Hlc3);
+                let volume = col("volume").fill_null(lit(0.0));
+                let cum_pv = (typical * volume.clone()).cum_sum(false).over([session.clone()]);
+                let cum_volume = volume.cum_sum(false).over([session]);
+                vec![("value", Self::safe_div(cum_pv, cum_volume))]
+            }
+            IndicatorKind::Donchian { period } => {
+                let upper = col("high").rolling_max(Self::window(*period));
+                let lower = col("low").rolling_min(Self::window(*period));
+                vec![
+                    ("upper", upper.clone()),
+                    ("middle", (upper + lower.clone()) / lit(2.0)),
+                    ("lower", lower),
+                ]
+            }
+        }
+    }
+
+    /// Price expression of a source
+    fn source(source: PriceSource) -> Expr {
+        match source {
+            PriceSource::Open => col("open"),
+            PriceSource::High => col("high"),
+            PriceSource::Low => col("low"),
+            PriceSource::Close => col("close"),
+            PriceSource::Hl2 => (col("high") + col("low")) / lit(2.0),
+            PriceSource::Hlc3 => (col("high") + col("low") + col("close")) / lit(3.0),
+            PriceSource::Ohlc4 => (col("open") + col("high") + col("low") + col("close")) / lit(4.0),
+        }
+    }
+
+    /// Fixed window requiring a full window before producing values
+    fn window(period: usize) -> RollingOptionsFixedWindow {
+        RollingOptionsFixedWindow {
+            window_size: period,
+            min_periods: period,
+            ..Default::default()
+        }
+    }
+
+    fn sma(expr: Expr, period: usize) -> Expr {
+        expr.rolling_mean(Self::window(period))
+    }
+
+    /// Weighted mean with weights 1..=period, the most recent bar weighing the most
+    fn wma(expr: Expr, period: usize) -> Expr {
+        expr.rolling_mean(RollingOptionsFixedWindow {
+            weights: Some((1..=period).map(|w| w as f64).collect()),
+            ..Self::window(period)
+        })
+    }
+
+    /// Population standard deviation over a fixed window
+    fn rolling_std(expr: Expr, period: usize) -> Expr {
+        expr.rolling_std(RollingOptionsFixedWindow {
+            fn_params: Some(Arc::new(RollingVarParams { ddof: 0 })),
+            ..Self::window(period)
+        })
+    }
+
+    fn ema(expr: Expr, period: usize) -> Expr {
+        Self::exponential(expr, 2.0 / (period as f64 + 1.0), period)
+    }
+
+    /// Wilder's smoothing (RMA), an EMA with alpha = 1 / period
+    fn wilder(expr: Expr, period: usize) -> Expr {
+        Self::exponential(expr, 1.0 / period as f64, period)
+    }
+
+    fn exponential(expr: Expr, alpha: f64, min_periods: usize) -> Expr {
+        expr.ewm_mean(EWMOptions {
+            alpha,
+            adjust: false,
+            bias: false,
+            min_periods,
+            ignore_nulls: true,
+        })
+    }
+
+    fn rsi(price: Expr, period: usize) -> Expr {
+        let change = price.clone() - price.shift(lit(1));
+        // The first bar has no change and stays out of the averages
+        let gain = when(change.clone().gt(lit(0.0)))
+            .then(change.clone())
+            .when(change.clone().is_not_null())
+            .then(lit(0.0))
+            .otherwise(lit(NULL));
+        let loss = when(change.clone().lt(lit(0.0)))
+            .then(lit(0.0) - change.clone())
+            .when(change.is_not_null())
+            .then(lit(0.0))
+            .otherwise(lit(NULL));
+
+        let avg_gain = Self::wilder(gain, period);
+        let avg_loss = Self::wilder(loss, period);
+        when(avg_loss.clone().eq(lit(0.0)))
+            .then(lit(100.0))
+            .otherwise(lit(100.0) - lit(100.0) / (lit(1.0) + avg_gain / avg_loss))
+    }
+
+    /// max(high - low, |high - previous close|, |low - previous close|)
+    /// The first bar has no previous close, so its range is high - low
+    pub fn true_range() -> Expr {
+        let previous_close = col("close").shift(lit(1));
+        let high_close = (col("high") - previous_close.clone()).abs().fill_null(lit(0.0));
+        let low_close = (col("low") - previous_close).abs().fill_null(lit(0.0));
+        Self::max(Self::max(col("high") - col("low"), high_close), low_close)
+    }
+
+    fn max(a: Expr, b: Expr) -> Expr {
+        when(a.clone().gt_eq(b.clone())).then(a).otherwise(b)
+    }
+
+    /// Division that yields null instead of NaN / infinity when the denominator is zero
+    fn safe_div(numerator: Expr, denominator: Expr) -> Expr {
+        when(denominator.clone().eq(lit(0.0)))
+            .then(lit(NULL).cast(DataType::Float64))
+            .otherwise(numerator / denominator)
+    }
+
+    fn session_seconds(anchor: VwapAnchor) -> i64 {
+        match anchor {
+            VwapAnchor::Day => 86_400,
+            VwapAnchor::Week => 7 * 86_400,
+        }
+    }
+
+    /// Weekly sessions start on Monday, like weekly bars
+    fn session_offset(anchor: VwapAnchor) -> i64 {
+        timeframe::bucket_alignment_offset(Self::session_seconds(anchor))
+    }
+}
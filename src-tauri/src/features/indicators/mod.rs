@@ -0,0 +1,7 @@
+/// Indicators feature module
+pub mod command;
+pub mod service;
+pub mod model;
+pub mod engine;
+
+pub use command::compute_indicators;
@@ -0,0 +1,133 @@
+//! Indicator data models
+
+use serde::{Deserialize, Serialize};
+
+/// Price input of single-series indicators
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum PriceSource {
+    Open,
+    High,
+    Low,
+    #[default]
+    Close,
+    /// (high + low) / 2
+    Hl2,
+    /// (high + low + close) / 3
+    Hlc3,
+    /// (open + high + low + close) / 4
+    Ohlc4,
+}
+
+/// Period after which a VWAP restarts accumulating
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum VwapAnchor {
+    /// Daily session starting at 00:00 UTC
+    #[default]
+    Day,
+    /// Weekly session starting on Monday 00:00 UTC
+    Week,
+}
+
+/// Indicator type and parameters
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum IndicatorKind {
+    /// Simple moving average
+    Sma {
+        period: usize,
+        #[serde(default)]
+        source: PriceSource,
+    },
+    /// Exponential moving average (alpha = 2 / (period + 1))
+    Ema {
+        period: usize,
+        #[serde(default)]
+        source: PriceSource,
+    },
+    /// Linearly weighted moving average
+    Wma {
+        period: usize,
+        #[serde(default)]
+        source: PriceSource,
+    },
+    /// Relative strength index with Wilder smoothing
+    Rsi {
+        period: usize,
+        #[serde(default)]
+        source: PriceSource,
+    },
+    /// MACD line, signal line and histogram
+    Macd {
+        fast: usize,
+        slow: usize,
+        signal: usize,
+        #[serde(default)]
+        source: PriceSource,
+    },
+    /// Bollinger Bands (SMA middle band, population standard deviation)
+    Bollinger {
+        period: usize,
+        std_dev: f64,
+        #[serde(default)]
+        source: PriceSource,
+    },
+    /// Average true range with Wilder smoothing
+    Atr { period: usize },
+    /// Stochastic oscillator (%K smoothed by `k_smoothing`, %D = SMA of %K)
+    Stochastic {
+        k_period: usize,
+        #[serde(default = "IndicatorKind::default_smoothing")]
+        k_smoothing: usize,
+        d_period: usize,
+    },
+    /// Average directional index with +DI / -DI
+    Adx { period: usize },
+    /// Volume weighted average price of the typical price, reset every session
+    Vwap {
+        #[serde(default)]
+        anchor: VwapAnchor,
+    },
+    /// Donchian channel (highest high / lowest low over `period` bars)
+    Donchian { period: usize },
+}
+
+impl IndicatorKind {
+    fn default_smoothing() -> usize {
+        1
+    }
+}
+
+/// A requested indicator
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IndicatorSpec {
+    /// Caller-chosen identifier echoed in the result (defaults to e.g. "sma_20")
+    #[serde(default)]
+    pub id: Option<String>,
+    #[serde(flatten)]
+    pub kind: IndicatorKind,
+}
+
+/// One output line of an indicator (e.g. the MACD signal line)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IndicatorLine {
+    pub name: String,
+    /// One value per bar, aligned with `IndicatorResult::time`; null during warmup
+    pub values: Vec<Option<f64>>,
+}
+
+/// Output of a single indicator
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IndicatorSeries {
+    pub id: String,
+    pub lines: Vec<IndicatorLine>,
+}
+
+/// Result of `compute_indicators`: indicator values aligned with the bars of the range
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IndicatorResult {
+    /// Bar timestamps (Unix seconds), identical to the bars returned by `retrieve_asset_ochl`
+    pub time: Vec<i64>,
+    pub indicators: Vec<IndicatorSeries>,
+}
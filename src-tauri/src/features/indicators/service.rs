@@ -0,0 +1,142 @@
+//! Indicator service - computes technical indicators over cached OHLC data
+
+use crate::shared::error::AppError;
+use crate::shared::utils::timeframe;
+use crate::features::asset::cache_manager::CacheManagerService;
+use crate::features::asset::model::TimeRange;
+use crate::features::asset::parquet_reader::ParquetReaderService;
+use crate::features::asset::service::AssetService;
+use crate::features::indicators::engine::IndicatorEngine;
+use crate::features::indicators::model::{
+    IndicatorLine, IndicatorResult, IndicatorSeries, IndicatorSpec,
+};
+use polars::prelude::*;
+use std::collections::HashSet;
+
+/// Extra calendar time loaded before the warmup bars to bridge weekends and holidays
+const WARMUP_GAP_ALLOWANCE_SECONDS: i64 = 7 * 86_400;
+
+/// A resolved indicator: its output id and the frame columns of its lines
+struct PlannedIndicator {
+    id: String,
+    /// (line name, frame column name)
+    lines: Vec<(String, String)>,
+}
+
+/// Service for computing indicators
+pub struct IndicatorService;
+
+impl IndicatorService {
+    /// Computes indicators for the bars of a symbol and timeframe inside `range`
+    /// History before `range.start` is loaded to warm the indicators up, so the first
+    /// returned bars already carry settled values. The output is aligned with the bars
+    /// that `retrieve_asset_ochl` returns for the same range.
+    pub fn compute_indicators(
+        symbol: &str,
+        timeframe: &str,
+        range: Option<TimeRange>,
+        specs: &[IndicatorSpec],
+    ) -> Result<IndicatorResult, AppError> {
+        let timeframe_seconds = timeframe::parse_timeframe_seconds(timeframe)?;
+        let range = range.unwrap_or_default();
+
+        // Validate specs and plan the output columns
+        let mut ids = HashSet::new();
+        let mut planned = Vec::with_capacity(specs.len());
+        let mut expressions = Vec::new();
+        let mut warmup_bars = 0;
+        let mut needs_volume = false;
+
+        for (index, spec) in specs.iter().enumerate() {
+            IndicatorEngine::validate(&spec.kind)?;
+
+            let id = spec
+                .id
+                .clone()
+                .unwrap_or_else(|| IndicatorEngine::default_id(&spec.kind));
+            if !ids.insert(id.clone()) {
+                return Err(AppError::new(format!("Duplicate indicator id: {}", id)));
+            }
+
+            let mut lines = Vec::new();
+            for (line, expr) in IndicatorEngine::expressions(&spec.kind) {
+                let column = format!("{}:{}", index, line);
+                expressions.push(expr.alias(&column));
+                lines.push((line.to_string(), column));
+            }
+
+            warmup_bars = warmup_bars.max(IndicatorEngine::warmup_bars(&spec.kind, timeframe_seconds));
+            needs_volume |= IndicatorEngine::needs_volume(&spec.kind);
+            planned.push(PlannedIndicator { id, lines });
+        }
+
+        // Load the range plus enough earlier history for the warmup
+        let load_start = range.start.map(|start| {
+            let warmup_seconds = (warmup_bars as i64).saturating_mul(timeframe_seconds).saturating_mul(2);
+            start
+                .saturating_sub(warmup_seconds)
+                .saturating_sub(WARMUP_GAP_ALLOWANCE_SECONDS)
+        });
+        let load_range = TimeRange {
+            start: load_start,
+            end: range.end,
+        };
+        let files = AssetService::dataset_files(symbol, timeframe, &load_range)?;
+
+        let mut lazy = ParquetReaderService::scan_ohlcv(&files, load_range.start, load_range.end)?
+            .with_columns(expressions);
+        if let Some(start) = range.start {
+            lazy = lazy.filter(col("time").gt_eq(lit(start)));
+        }
+        let df = lazy
+            .collect()
+            .map_err(|e| AppError::new(format!("Failed to compute indicators: {}", e)))?;
+
+        if needs_volume {
+            let volume = df
+                .column("volume")
+                .map_err(|e| AppError::new(format!("Failed to get 'volume' column: {}", e)))?;
+            if df.height() > 0 && volume.null_count() == df.height() {
+                return Err(AppError::new(format!(
+                    "VWAP requires volume, but {} {} has no volume column",
+                    symbol, timeframe
+                )));
+            }
+        }
+
+        // Extract columns
+        let time_col = df
+            .column("time")
+            .map_err(|e| AppError::new(format!("Failed to get 'time' column: {}", e)))?
+            .i64()
+            .map_err(|e| AppError::new(format!("Failed to cast 'time' column to i64: {}", e)))?;
+        let time: Vec<i64> = time_col.into_iter().map(|t| t.unwrap_or(0)).collect();
+
+        let mut indicators = Vec::with_capacity(planned.len());
+        for indicator in planned {
+            let mut lines = Vec::with_capacity(indicator.lines.len());
+            for (name, column) in indicator.lines {
+                let values = df
+                    .column(&column)
+                    .map_err(|e| AppError::new(format!("Failed to get '{}' column: {}", column, e)))?
+                    .cast(&DataType::Float64)
+                    .map_err(|e| AppError::new(format!("Failed to cast '{}' column to f64: {}", column, e)))?;
+                let values = values
+                    .f64()
+                    .map_err(|e| AppError::new(format!("Failed to cast '{}' column to f64: {}", column, e)))?
+                    .into_iter()
+                    .map(|value| value.filter(|v| v.is_finite()))
+                    .collect();
+                lines.push(IndicatorLine { name, values });
+            }
+            indicators.push(IndicatorSeries {
+                id: indicator.id,
+                lines,
+            });
+        }
+
+        CacheManagerService::record_access_best_effort(symbol, timeframe);
+
+        Ok(IndicatorResult { time, indicators })
+    }
+}
@@ -0,0 +1,88 @@
+//! ATR by Hour insight implementation
+//! Average true range of intraday bars grouped by hour of day
+
+mod params;
+mod result;
+mod query;
+
+use crate::features::insights::insight_trait::Insight;
+use crate::shared::error::AppError;
+use params::AtrByHourParams;
+use query::execute_query;
+use serde_json::{json, Value};
+
+/// Insight that averages the true range of intraday bars by hour of day
+pub struct AtrByHourInsight;
+
+impl AtrByHourInsight {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl Default for AtrByHourInsight {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Insight for AtrByHourInsight {
+    fn identifier(&self) -> &'static str {
+        "assets.atr_by_hour"
+    }
+
+    fn name(&self) -> &'static str {
+        "ATR by Hour"
+    }
+
+    fn description(&self) -> &'static str {
+        "Average true range by hour of day"
+    }
+
+    fn parameter_schema(&self) -> Value {
+        // Generate JSON Schema from the params struct
+        let schema = schemars::schema_for!(AtrByHourParams);
+        serde_json::to_value(schema).unwrap_or_else(|_| {
+            // Fallback schema if serialization fails
+            json!({
+                "type": "object",
+                "properties": {
+                    "symbol": {
+                        "type": "string",
+                        "description": "Symbol name (e.g. \"EURUSD\")"
+                    },
+                    "timeframe": {
+                        "type": "string",
+                        "description": "Intraday timeframe of the bars, one hour or less"
+                    }
+                },
+                "required": ["symbol", "timeframe"]
+            })
+        })
+    }
+
+    fn validate_parameters(&self, params: &Value) -> Result<(), AppError> {
+        // Try to deserialize to validate structure
+        let _: AtrByHourParams = serde_json::from_value(params.clone())
+            .map_err(|e| AppError::new(format!("Invalid parameters: {}", e)))?;
+
+        Ok(())
+    }
+
+    fn execute(&self, params: Value) -> Result<Value, AppError> {
+        // Deserialize parameters
+        let params: AtrByHourParams = serde_json::from_value(params)
+            .map_err(|e| AppError::new(format!("Failed to parse parameters: {}", e)))?;
+
+        // Execute the query
+        let results = execute_query(&params)?;
+
+        // Serialize results to JSON
+        let json_results: Vec<Value> = results
+            .into_iter()
+            .map(|r| serde_json::to_value(r).unwrap_or_else(|_| json!({})))
+            .collect();
+
+        Ok(Value::Array(json_results))
+    }
+}
@@ -0,0 +1,23 @@
+//! Parameters for atr_by_hour insight
+
+use crate::features::asset::model::TimeRange;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+/// Parameters for the atr_by_hour insight
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct AtrByHourParams {
+    /// Symbol name (e.g. "EURUSD")
+    pub symbol: String,
+
+    /// Intraday timeframe of the bars, one hour or less (e.g. "1M", "15M", "1H")
+    pub timeframe: String,
+
+    /// Optional time range of the bars (Unix seconds)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub range: Option<TimeRange>,
+
+    /// Optional offset from UTC in minutes used to compute the hour of day (e.g. 120 for UTC+2)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub utc_offset_minutes: Option<i64>,
+}
@@ -0,0 +1,73 @@
+//! Polars query logic for atr_by_hour insight
+
+use crate::features::indicators::engine::IndicatorEngine;
+use crate::features::insights::assets::atr_by_hour::params::AtrByHourParams;
+use crate::features::insights::assets::atr_by_hour::result::AtrByHourResult;
+use crate::features::insights::assets::scan_bars;
+use crate::shared::error::AppError;
+use crate::shared::utils::timeframe;
+use polars::prelude::*;
+
+/// Executes the atr_by_hour query
+pub fn execute_query(params: &AtrByHourParams) -> Result<Vec<AtrByHourResult>, AppError> {
+    let timeframe_seconds = timeframe::parse_timeframe_seconds(&params.timeframe)?;
+    if timeframe_seconds > 3_600 {
+        return Err(AppError::new(format!(
+            "ATR by hour needs intraday bars of one hour or less, got {}",
+            params.timeframe
+        )));
+    }
+
+    let offset_seconds = params.utc_offset_minutes.unwrap_or(0) * 60;
+
+    let result = scan_bars(&params.symbol, &params.timeframe, params.range)?
+        .with_columns([
+            IndicatorEngine::true_range().alias("true_range"),
+            ((col("time") + lit(offset_seconds)).floor_div(lit(3_600i64)) % lit(24i64)).alias("hour"),
+        ])
+        .group_by([col("hour")])
+        .agg([
+            col("true_range").mean().alias("avg_true_range"),
+            (col("true_range") / col("close") * lit(100.0)).mean().alias("avg_true_range_pct"),
+            col("true_range").count().alias("bar_count"),
+        ])
+        .sort(["hour"], SortMultipleOptions::default())
+        .collect()
+        .map_err(|e| AppError::new(format!("Failed to execute query: {}", e)))?;
+
+    // Convert to result structs
+    let hour_col = result
+        .column("hour")
+        .map_err(|e| AppError::new(format!("Failed to get 'hour' column: {}", e)))?
+        .i64()
+        .map_err(|e| AppError::new(format!("Failed to cast 'hour' column: {}", e)))?;
+    let atr_col = result
+        .column("avg_true_range")
+        .map_err(|e| AppError::new(format!("Failed to get 'avg_true_range' column: {}", e)))?
+        .f64()
+        .map_err(|e| AppError::new(format!("Failed to cast 'avg_true_range' column: {}", e)))?;
+    let atr_pct_col = result
+        .column("avg_true_range_pct")
+        .map_err(|e| AppError::new(format!("Failed to get 'avg_true_range_pct' column: {}", e)))?
+        .f64()
+        .map_err(|e| AppError::new(format!("Failed to cast 'avg_true_range_pct' column: {}", e)))?;
+    let count_col = result
+        .column("bar_count")
+        .map_err(|e| AppError::new(format!("Failed to get 'bar_count' column: {}", e)))?
+        .u32()
+        .map_err(|e| AppError::new(format!("Failed to cast 'bar_count' column: {}", e)))?;
+
+    let num_rows = result.height();
+    let mut results = Vec::with_capacity(num_rows);
+
+    for i in 0..num_rows {
+        results.push(AtrByHourResult {
+            hour: hour_col.get(i).unwrap_or(0),
+            avg_true_range: atr_col.get(i).unwrap_or(0.0),
+            avg_true_range_pct: atr_pct_col.get(i).unwrap_or(0.0),
+            bar_count: count_col.get(i).unwrap_or(0) as i64,
+        });
+    }
+
+    Ok(results)
+}
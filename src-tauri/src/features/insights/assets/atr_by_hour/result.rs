@@ -0,0 +1,16 @@
+//! Result structure for atr_by_hour insight
+
+use serde::{Deserialize, Serialize};
+
+/// Result row for atr_by_hour insight
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AtrByHourResult {
+    /// Hour of day (0-23)
+    pub hour: i64,
+    /// Average true range of the bars starting in this hour
+    pub avg_true_range: f64,
+    /// Average true range as a percentage of the close
+    pub avg_true_range_pct: f64,
+    /// Number of bars in this hour
+    pub bar_count: i64,
+}
@@ -0,0 +1,89 @@
+//! Correlation Matrix insight implementation
+//! Pairwise Pearson correlation of close returns across symbols
+
+mod params;
+mod result;
+mod query;
+
+use crate::features::insights::insight_trait::Insight;
+use crate::shared::error::AppError;
+use params::CorrelationMatrixParams;
+use query::execute_query;
+use serde_json::{json, Value};
+
+/// Insight that correlates the close returns of several symbols
+pub struct CorrelationMatrixInsight;
+
+impl CorrelationMatrixInsight {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl Default for CorrelationMatrixInsight {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Insight for CorrelationMatrixInsight {
+    fn identifier(&self) -> &'static str {
+        "assets.correlation_matrix"
+    }
+
+    fn name(&self) -> &'static str {
+        "Correlation Matrix"
+    }
+
+    fn description(&self) -> &'static str {
+        "Pairwise correlation of close returns across symbols"
+    }
+
+    fn parameter_schema(&self) -> Value {
+        // Generate JSON Schema from the params struct
+        let schema = schemars::schema_for!(CorrelationMatrixParams);
+        serde_json::to_value(schema).unwrap_or_else(|_| {
+            // Fallback schema if serialization fails
+            json!({
+                "type": "object",
+                "properties": {
+                    "symbols": {
+                        "type": "array",
+                        "items": { "type": "string" },
+                        "description": "Symbols to correlate (at least two)"
+                    },
+                    "timeframe": {
+                        "type": "string",
+                        "description": "Timeframe of the bars (e.g. \"1H\", \"1D\")"
+                    }
+                },
+                "required": ["symbols", "timeframe"]
+            })
+        })
+    }
+
+    fn validate_parameters(&self, params: &Value) -> Result<(), AppError> {
+        // Try to deserialize to validate structure
+        let _: CorrelationMatrixParams = serde_json::from_value(params.clone())
+            .map_err(|e| AppError::new(format!("Invalid parameters: {}", e)))?;
+
+        Ok(())
+    }
+
+    fn execute(&self, params: Value) -> Result<Value, AppError> {
+        // Deserialize parameters
+        let params: CorrelationMatrixParams = serde_json::from_value(params)
+            .map_err(|e| AppError::new(format!("Failed to parse parameters: {}", e)))?;
+
+        // Execute the query
+        let results = execute_query(&params)?;
+
+        // Serialize results to JSON
+        let json_results: Vec<Value> = results
+            .into_iter()
+            .map(|r| serde_json::to_value(r).unwrap_or_else(|_| json!({})))
+            .collect();
+
+        Ok(Value::Array(json_results))
+    }
+}
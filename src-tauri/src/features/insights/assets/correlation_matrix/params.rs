@@ -0,0 +1,19 @@
+//! Parameters for correlation_matrix insight
+
+use crate::features::asset::model::TimeRange;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+/// Parameters for the correlation_matrix insight
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct CorrelationMatrixParams {
+    /// Symbols to correlate (at least two, e.g. ["EURUSD", "GBPUSD"])
+    pub symbols: Vec<String>,
+
+    /// Timeframe of the bars (e.g. "1H", "1D")
+    pub timeframe: String,
+
+    /// Optional time range of the bars (Unix seconds)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub range: Option<TimeRange>,
+}
@@ -0,0 +1,61 @@
+//! Query logic for correlation_matrix insight
+
+use crate::features::asset::model::{FillPolicy, OhlcField};
+use crate::features::asset::service::AssetService;
+use crate::features::insights::assets::correlation_matrix::params::CorrelationMatrixParams;
+use crate::features::insights::assets::correlation_matrix::result::CorrelationMatrixResult;
+use crate::shared::error::AppError;
+use crate::shared::utils::statistics;
+
+/// Executes the correlation_matrix query
+pub fn execute_query(params: &CorrelationMatrixParams) -> Result<Vec<CorrelationMatrixResult>, AppError> {
+    if params.symbols.len() < 2 {
+        return Err(AppError::new("At least two symbols are required"));
+    }
+
+    // Only timestamps where every symbol has a bar, so returns line up pairwise
+    let aligned = AssetService::retrieve_aligned_series(
+        &params.symbols,
+        &params.timeframe,
+        params.range,
+        OhlcField::Close,
+        FillPolicy::DropIncomplete,
+    )?;
+
+    let returns: Vec<Vec<f64>> = aligned
+        .series
+        .iter()
+        .map(|series| {
+            series
+                .values
+                .windows(2)
+                .map(|pair| match (pair[0], pair[1]) {
+                    (Some(prev), Some(next)) if prev != 0.0 => next / prev - 1.0,
+                    _ => f64::NAN,
+                })
+                .collect()
+        })
+        .collect();
+
+    let mut results = Vec::with_capacity(aligned.series.len() * aligned.series.len());
+    for (i, series_a) in aligned.series.iter().enumerate() {
+        for (j, series_b) in aligned.series.iter().enumerate() {
+            // Keep only pairs where both returns are defined
+            let (x, y): (Vec<f64>, Vec<f64>) = returns[i]
+                .iter()
+                .zip(&returns[j])
+                .filter(|(a, b)| a.is_finite() && b.is_finite())
+                .map(|(a, b)| (*a, *b))
+                .unzip();
+
+            results.push(CorrelationMatrixResult {
+                symbol_a: series_a.symbol.clone(),
+                symbol_b: series_b.symbol.clone(),
+                correlation: statistics::pearson(&x, &y),
+                observations: x.len() as i64,
+            });
+        }
+    }
+
+    Ok(results)
+}
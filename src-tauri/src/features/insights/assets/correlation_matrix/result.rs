@@ -0,0 +1,16 @@
+//! Result structure for correlation_matrix insight
+
+use serde::{Deserialize, Serialize};
+
+/// Result row for correlation_matrix insight (one cell of the matrix)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CorrelationMatrixResult {
+    /// Row symbol
+    pub symbol_a: String,
+    /// Column symbol
+    pub symbol_b: String,
+    /// Pearson correlation of close returns (null when undefined, e.g. a flat series)
+    pub correlation: Option<f64>,
+    /// Number of return pairs used
+    pub observations: i64,
+}
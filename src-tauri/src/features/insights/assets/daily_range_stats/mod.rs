@@ -0,0 +1,88 @@
+//! Daily Range Statistics insight implementation
+//! Mean, median, dispersion and percentiles of the daily high-low range
+
+mod params;
+mod result;
+mod query;
+
+use crate::features::insights::insight_trait::Insight;
+use crate::shared::error::AppError;
+use params::DailyRangeStatsParams;
+use query::execute_query;
+use serde_json::{json, Value};
+
+/// Insight that summarizes the daily high-low range of a symbol
+pub struct DailyRangeStatsInsight;
+
+impl DailyRangeStatsInsight {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl Default for DailyRangeStatsInsight {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Insight for DailyRangeStatsInsight {
+    fn identifier(&self) -> &'static str {
+        "assets.daily_range_stats"
+    }
+
+    fn name(&self) -> &'static str {
+        "Daily Range Statistics"
+    }
+
+    fn description(&self) -> &'static str {
+        "Distribution statistics of the daily high-low range"
+    }
+
+    fn parameter_schema(&self) -> Value {
+        // Generate JSON Schema from the params struct
+        let schema = schemars::schema_for!(DailyRangeStatsParams);
+        serde_json::to_value(schema).unwrap_or_else(|_| {
+            // Fallback schema if serialization fails
+            json!({
+                "type": "object",
+                "properties": {
+                    "symbol": {
+                        "type": "string",
+                        "description": "Symbol name (e.g. \"EURUSD\")"
+                    },
+                    "timeframe": {
+                        "type": "string",
+                        "description": "Timeframe of the bars, one day or less"
+                    }
+                },
+                "required": ["symbol", "timeframe"]
+            })
+        })
+    }
+
+    fn validate_parameters(&self, params: &Value) -> Result<(), AppError> {
+        // Try to deserialize to validate structure
+        let _: DailyRangeStatsParams = serde_json::from_value(params.clone())
+            .map_err(|e| AppError::new(format!("Invalid parameters: {}", e)))?;
+
+        Ok(())
+    }
+
+    fn execute(&self, params: Value) -> Result<Value, AppError> {
+        // Deserialize parameters
+        let params: DailyRangeStatsParams = serde_json::from_value(params)
+            .map_err(|e| AppError::new(format!("Failed to parse parameters: {}", e)))?;
+
+        // Execute the query
+        let results = execute_query(&params)?;
+
+        // Serialize results to JSON
+        let json_results: Vec<Value> = results
+            .into_iter()
+            .map(|r| serde_json::to_value(r).unwrap_or_else(|_| json!({})))
+            .collect();
+
+        Ok(Value::Array(json_results))
+    }
+}
@@ -0,0 +1,23 @@
+//! Parameters for daily_range_stats insight
+
+use crate::features::asset::model::TimeRange;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+/// Parameters for the daily_range_stats insight
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct DailyRangeStatsParams {
+    /// Symbol name (e.g. "EURUSD")
+    pub symbol: String,
+
+    /// Timeframe of the bars, one day or less (e.g. "1H", "1D")
+    pub timeframe: String,
+
+    /// Optional time range of the bars (Unix seconds)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub range: Option<TimeRange>,
+
+    /// Optional offset from UTC in minutes used to split the days (e.g. 120 for UTC+2)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub utc_offset_minutes: Option<i64>,
+}
@@ -0,0 +1,76 @@
+//! Polars query logic for daily_range_stats insight
+
+use crate::features::insights::assets::daily_range_stats::params::DailyRangeStatsParams;
+use crate::features::insights::assets::daily_range_stats::result::DailyRangeStatsResult;
+use crate::features::insights::assets::scan_bars;
+use crate::shared::error::AppError;
+use crate::shared::utils::{statistics, timeframe};
+use polars::prelude::*;
+
+const SECONDS_PER_DAY: i64 = 86_400;
+
+/// Executes the daily_range_stats query
+pub fn execute_query(params: &DailyRangeStatsParams) -> Result<Vec<DailyRangeStatsResult>, AppError> {
+    let timeframe_seconds = timeframe::parse_timeframe_seconds(&params.timeframe)?;
+    if timeframe_seconds > SECONDS_PER_DAY {
+        return Err(AppError::new(format!(
+            "Daily range statistics need bars of one day or less, got {}",
+            params.timeframe
+        )));
+    }
+
+    let offset_seconds = params.utc_offset_minutes.unwrap_or(0) * 60;
+
+    // Bars are time-sorted, so first/last within a day are its open/close
+    let days = scan_bars(&params.symbol, &params.timeframe, params.range)?
+        .with_column((col("time") + lit(offset_seconds)).floor_div(lit(SECONDS_PER_DAY)).alias("day"))
+        .group_by_stable([col("day")])
+        .agg([
+            (col("high").max() - col("low").min()).alias("range"),
+            col("open").first().alias("open"),
+        ])
+        .collect()
+        .map_err(|e| AppError::new(format!("Failed to execute query: {}", e)))?;
+
+    let range_col = days
+        .column("range")
+        .map_err(|e| AppError::new(format!("Failed to get 'range' column: {}", e)))?
+        .f64()
+        .map_err(|e| AppError::new(format!("Failed to cast 'range' column: {}", e)))?;
+    let open_col = days
+        .column("open")
+        .map_err(|e| AppError::new(format!("Failed to get 'open' column: {}", e)))?
+        .f64()
+        .map_err(|e| AppError::new(format!("Failed to cast 'open' column: {}", e)))?;
+
+    let mut ranges = Vec::with_capacity(days.height());
+    let mut range_pcts = Vec::with_capacity(days.height());
+    for (range, open) in range_col.into_iter().zip(open_col) {
+        let Some(range) = range else { continue };
+        ranges.push(range);
+        if let Some(open) = open.filter(|open| *open != 0.0) {
+            range_pcts.push(range / open * 100.0);
+        }
+    }
+
+    if ranges.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let sorted_ranges = statistics::sorted(&ranges);
+    let sorted_pcts = statistics::sorted(&range_pcts);
+
+    Ok(vec![DailyRangeStatsResult {
+        days: ranges.len() as i64,
+        mean_range: statistics::mean(&ranges).unwrap_or(0.0),
+        median_range: statistics::median(&sorted_ranges).unwrap_or(0.0),
+        std_range: statistics::std_dev(&ranges).unwrap_or(0.0),
+        min_range: sorted_ranges.first().copied().unwrap_or(0.0),
+        max_range: sorted_ranges.last().copied().unwrap_or(0.0),
+        p25_range: statistics::percentile(&sorted_ranges, 25.0).unwrap_or(0.0),
+        p75_range: statistics::percentile(&sorted_ranges, 75.0).unwrap_or(0.0),
+        p90_range: statistics::percentile(&sorted_ranges, 90.0).unwrap_or(0.0),
+        mean_range_pct: statistics::mean(&range_pcts).unwrap_or(0.0),
+        median_range_pct: statistics::median(&sorted_pcts).unwrap_or(0.0),
+    }])
+}
@@ -0,0 +1,30 @@
+//! Result structure for daily_range_stats insight
+
+use serde::{Deserialize, Serialize};
+
+/// Result row for daily_range_stats insight
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DailyRangeStatsResult {
+    /// Number of days in the sample
+    pub days: i64,
+    /// Mean daily range (high - low)
+    pub mean_range: f64,
+    /// Median daily range
+    pub median_range: f64,
+    /// Sample standard deviation of the daily range
+    pub std_range: f64,
+    /// Smallest daily range
+    pub min_range: f64,
+    /// Largest daily range
+    pub max_range: f64,
+    /// 25th percentile of the daily range
+    pub p25_range: f64,
+    /// 75th percentile of the daily range
+    pub p75_range: f64,
+    /// 90th percentile of the daily range
+    pub p90_range: f64,
+    /// Mean daily range as a percentage of the day's open
+    pub mean_range_pct: f64,
+    /// Median daily range as a percentage of the day's open
+    pub median_range_pct: f64,
+}
@@ -0,0 +1,31 @@
+//! Assets insights module
+//! Insights over cached OHLC data (market context next to trading results)
+
+pub mod atr_by_hour;
+pub mod daily_range_stats;
+pub mod return_distribution;
+pub mod correlation_matrix;
+pub mod return_seasonality;
+
+use crate::features::asset::cache_manager::CacheManagerService;
+use crate::features::asset::model::TimeRange;
+use crate::features::asset::parquet_reader::ParquetReaderService;
+use crate::features::asset::service::AssetService;
+use crate::shared::error::AppError;
+use polars::prelude::*;
+
+/// Scans the cached bars of a symbol as a time-sorted OHLCV frame
+/// (`time`, `open`, `high`, `low`, `close`, `volume`)
+pub fn scan_bars(
+    symbol: &str,
+    timeframe: &str,
+    range: Option<TimeRange>,
+) -> Result<LazyFrame, AppError> {
+    let range = range.unwrap_or_default();
+    let files = AssetService::dataset_files(symbol, timeframe, &range)?;
+    let frame = ParquetReaderService::scan_ohlcv(&files, range.start, range.end)?;
+
+    CacheManagerService::record_access_best_effort(symbol, timeframe);
+
+    Ok(frame)
+}
@@ -0,0 +1,88 @@
+//! Return Distribution insight implementation
+//! Histogram of bar-to-bar close returns
+
+mod params;
+mod result;
+mod query;
+
+use crate::features::insights::insight_trait::Insight;
+use crate::shared::error::AppError;
+use params::ReturnDistributionParams;
+use query::execute_query;
+use serde_json::{json, Value};
+
+/// Insight that bins bar-to-bar close returns into a histogram
+pub struct ReturnDistributionInsight;
+
+impl ReturnDistributionInsight {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl Default for ReturnDistributionInsight {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Insight for ReturnDistributionInsight {
+    fn identifier(&self) -> &'static str {
+        "assets.return_distribution"
+    }
+
+    fn name(&self) -> &'static str {
+        "Return Distribution"
+    }
+
+    fn description(&self) -> &'static str {
+        "Histogram of bar-to-bar close returns"
+    }
+
+    fn parameter_schema(&self) -> Value {
+        // Generate JSON Schema from the params struct
+        let schema = schemars::schema_for!(ReturnDistributionParams);
+        serde_json::to_value(schema).unwrap_or_else(|_| {
+            // Fallback schema if serialization fails
+            json!({
+                "type": "object",
+                "properties": {
+                    "symbol": {
+                        "type": "string",
+                        "description": "Symbol name (e.g. \"EURUSD\")"
+                    },
+                    "timeframe": {
+                        "type": "string",
+                        "description": "Timeframe of the bars (e.g. \"1H\", \"1D\")"
+                    }
+                },
+                "required": ["symbol", "timeframe"]
+            })
+        })
+    }
+
+    fn validate_parameters(&self, params: &Value) -> Result<(), AppError> {
+        // Try to deserialize to validate structure
+        let _: ReturnDistributionParams = serde_json::from_value(params.clone())
+            .map_err(|e| AppError::new(format!("Invalid parameters: {}", e)))?;
+
+        Ok(())
+    }
+
+    fn execute(&self, params: Value) -> Result<Value, AppError> {
+        // Deserialize parameters
+        let params: ReturnDistributionParams = serde_json::from_value(params)
+            .map_err(|e| AppError::new(format!("Failed to parse parameters: {}", e)))?;
+
+        // Execute the query
+        let results = execute_query(&params)?;
+
+        // Serialize results to JSON
+        let json_results: Vec<Value> = results
+            .into_iter()
+            .map(|r| serde_json::to_value(r).unwrap_or_else(|_| json!({})))
+            .collect();
+
+        Ok(Value::Array(json_results))
+    }
+}
@@ -0,0 +1,27 @@
+//! Parameters for return_distribution insight
+
+use crate::features::asset::model::TimeRange;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+/// Parameters for the return_distribution insight
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct ReturnDistributionParams {
+    /// Symbol name (e.g. "EURUSD")
+    pub symbol: String,
+
+    /// Timeframe of the bars (e.g. "1H", "1D")
+    pub timeframe: String,
+
+    /// Optional time range of the bars (Unix seconds)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub range: Option<TimeRange>,
+
+    /// Optional number of histogram bins (2-200, defaults to 20)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub bins: Option<usize>,
+
+    /// Optional flag to use log returns instead of simple returns (defaults to false)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub log_returns: Option<bool>,
+}
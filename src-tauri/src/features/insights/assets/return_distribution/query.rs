@@ -0,0 +1,89 @@
+//! Polars query logic for return_distribution insight
+
+use crate::features::insights::assets::return_distribution::params::ReturnDistributionParams;
+use crate::features::insights::assets::return_distribution::result::ReturnDistributionResult;
+use crate::features::insights::assets::scan_bars;
+use crate::shared::error::AppError;
+use polars::prelude::*;
+
+const DEFAULT_BINS: usize = 20;
+const MAX_BINS: usize = 200;
+
+/// Executes the return_distribution query
+pub fn execute_query(params: &ReturnDistributionParams) -> Result<Vec<ReturnDistributionResult>, AppError> {
+    let bins = params.bins.unwrap_or(DEFAULT_BINS);
+    if !(2..=MAX_BINS).contains(&bins) {
+        return Err(AppError::new(format!(
+            "Bins must be between 2 and {}, got {}",
+            MAX_BINS, bins
+        )));
+    }
+
+    let bars = scan_bars(&params.symbol, &params.timeframe, params.range)?
+        .select([col("close")])
+        .collect()
+        .map_err(|e| AppError::new(format!("Failed to execute query: {}", e)))?;
+
+    let close_col = bars
+        .column("close")
+        .map_err(|e| AppError::new(format!("Failed to get 'close' column: {}", e)))?
+        .f64()
+        .map_err(|e| AppError::new(format!("Failed to cast 'close' column: {}", e)))?;
+
+    let closes: Vec<f64> = close_col.into_iter().flatten().collect();
+    let log_returns = params.log_returns.unwrap_or(false);
+
+    // Returns in percent; pairs with a non-positive close are skipped
+    let returns: Vec<f64> = closes
+        .windows(2)
+        .filter(|pair| pair[0] > 0.0 && pair[1] > 0.0)
+        .map(|pair| {
+            if log_returns {
+                (pair[1] / pair[0]).ln() * 100.0
+            } else {
+                (pair[1] / pair[0] - 1.0) * 100.0
+            }
+        })
+        .filter(|r| r.is_finite())
+        .collect();
+
+    if returns.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let min = returns.iter().copied().fold(f64::INFINITY, f64::min);
+    let max = returns.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+
+    // A flat series collapses into a single bin
+    let width = if max > min { (max - min) / bins as f64 } else { 0.0 };
+    let bins = if width > 0.0 { bins } else { 1 };
+
+    let mut counts = vec![0i64; bins];
+    for value in &returns {
+        let index = if width > 0.0 {
+            (((value - min) / width) as usize).min(bins - 1)
+        } else {
+            0
+        };
+        counts[index] += 1;
+    }
+
+    let total = returns.len() as f64;
+    let mut cumulative = 0i64;
+    let results = counts
+        .into_iter()
+        .enumerate()
+        .map(|(i, count)| {
+            cumulative += count;
+            ReturnDistributionResult {
+                bin_start: min + width * i as f64,
+                bin_end: if i + 1 == bins { max } else { min + width * (i + 1) as f64 },
+                count,
+                frequency: count as f64 / total,
+                cumulative_frequency: cumulative as f64 / total,
+            }
+        })
+        .collect();
+
+    Ok(results)
+}
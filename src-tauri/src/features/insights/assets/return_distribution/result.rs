@@ -0,0 +1,18 @@
+//! Result structure for return_distribution insight
+
+use serde::{Deserialize, Serialize};
+
+/// Result row for return_distribution insight (one histogram bin)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReturnDistributionResult {
+    /// Lower bound of the bin (inclusive), as a percentage return
+    pub bin_start: f64,
+    /// Upper bound of the bin (exclusive, inclusive for the last bin), as a percentage return
+    pub bin_end: f64,
+    /// Number of returns in the bin
+    pub count: i64,
+    /// Share of all returns in the bin (0-1)
+    pub frequency: f64,
+    /// Share of all returns up to and including this bin (0-1)
+    pub cumulative_frequency: f64,
+}
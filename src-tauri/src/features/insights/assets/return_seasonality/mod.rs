@@ -0,0 +1,93 @@
+//! Return Seasonality insight implementation
+//! Return statistics of a symbol grouped by hour of day, weekday or calendar month
+
+mod params;
+mod result;
+mod query;
+
+use crate::features::insights::insight_trait::Insight;
+use crate::shared::error::AppError;
+use params::ReturnSeasonalityParams;
+use query::execute_query;
+use serde_json::{json, Value};
+
+/// Insight that groups period returns by a calendar bucket
+pub struct ReturnSeasonalityInsight;
+
+impl ReturnSeasonalityInsight {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl Default for ReturnSeasonalityInsight {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Insight for ReturnSeasonalityInsight {
+    fn identifier(&self) -> &'static str {
+        "assets.return_seasonality"
+    }
+
+    fn name(&self) -> &'static str {
+        "Return Seasonality"
+    }
+
+    fn description(&self) -> &'static str {
+        "Mean, median, hit rate and volatility of returns by hour of day, weekday or month"
+    }
+
+    fn parameter_schema(&self) -> Value {
+        // Generate JSON Schema from the params struct
+        let schema = schemars::schema_for!(ReturnSeasonalityParams);
+        serde_json::to_value(schema).unwrap_or_else(|_| {
+            // Fallback schema if serialization fails
+            json!({
+                "type": "object",
+                "properties": {
+                    "symbol": {
+                        "type": "string",
+                        "description": "Symbol name (e.g. \"EURUSD\")"
+                    },
+                    "timeframe": {
+                        "type": "string",
+                        "description": "Timeframe of the bars, one day or less"
+                    },
+                    "bucket": {
+                        "type": "string",
+                        "enum": ["hour_of_day", "weekday", "month"],
+                        "description": "Calendar bucket to group the returns by"
+                    }
+                },
+                "required": ["symbol", "timeframe", "bucket"]
+            })
+        })
+    }
+
+    fn validate_parameters(&self, params: &Value) -> Result<(), AppError> {
+        // Try to deserialize to validate structure
+        let _: ReturnSeasonalityParams = serde_json::from_value(params.clone())
+            .map_err(|e| AppError::new(format!("Invalid parameters: {}", e)))?;
+
+        Ok(())
+    }
+
+    fn execute(&self, params: Value) -> Result<Value, AppError> {
+        // Deserialize parameters
+        let params: ReturnSeasonalityParams = serde_json::from_value(params)
+            .map_err(|e| AppError::new(format!("Failed to parse parameters: {}", e)))?;
+
+        // Execute the query
+        let results = execute_query(&params)?;
+
+        // Serialize results to JSON
+        let json_results: Vec<Value> = results
+            .into_iter()
+            .map(|r| serde_json::to_value(r).unwrap_or_else(|_| json!({})))
+            .collect();
+
+        Ok(Value::Array(json_results))
+    }
+}
@@ -0,0 +1,38 @@
+//! Parameters for return_seasonality insight
+
+use crate::features::asset::model::TimeRange;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+/// Calendar bucket the returns are grouped by
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum SeasonalityBucket {
+    /// Hourly returns grouped by hour of day (0-23), needs bars of one hour or less
+    HourOfDay,
+    /// Daily returns grouped by weekday (Monday-Sunday)
+    Weekday,
+    /// Monthly returns grouped by calendar month (January-December)
+    Month,
+}
+
+/// Parameters for the return_seasonality insight
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct ReturnSeasonalityParams {
+    /// Symbol name (e.g. "EURUSD")
+    pub symbol: String,
+
+    /// Timeframe of the bars, one day or less (e.g. "15M", "1H", "1D")
+    pub timeframe: String,
+
+    /// Calendar bucket to group the returns by
+    pub bucket: SeasonalityBucket,
+
+    /// Optional time range of the bars (Unix seconds)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub range: Option<TimeRange>,
+
+    /// Optional offset from UTC in minutes used to split hours, days and months (e.g. 120 for UTC+2)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub utc_offset_minutes: Option<i64>,
+}
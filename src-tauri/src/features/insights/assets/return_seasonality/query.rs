@@ -0,0 +1,162 @@
+//! Polars query logic for return_seasonality insight
+
+use crate::features::insights::assets::return_seasonality::params::{ReturnSeasonalityParams, SeasonalityBucket};
+use crate::features::insights::assets::return_seasonality::result::ReturnSeasonalityResult;
+use crate::features::insights::assets::scan_bars;
+use crate::shared::error::AppError;
+use crate::shared::utils::{statistics, timeframe};
+use chrono::{DateTime, Datelike};
+use polars::prelude::*;
+use std::collections::BTreeMap;
+
+const SECONDS_PER_HOUR: i64 = 3_600;
+const SECONDS_PER_DAY: i64 = 86_400;
+
+/// z-score of the two-sided 95% confidence interval
+const CONFIDENCE_Z: f64 = 1.96;
+
+const WEEKDAY_LABELS: [&str; 7] = ["Mon", "Tue", "Wed", "Thu", "Fri", "Sat", "Sun"];
+const MONTH_LABELS: [&str; 12] = [
+    "Jan", "Feb", "Mar", "Apr", "May", "Jun", "Jul", "Aug", "Sep", "Oct", "Nov", "Dec",
+];
+
+/// One aggregated period (hour, day or month) of the bar series
+struct Period {
+    /// Start of the period in local seconds (UTC shifted by the offset)
+    start: i64,
+    open: f64,
+    close: f64,
+}
+
+/// Executes the return_seasonality query
+pub fn execute_query(params: &ReturnSeasonalityParams) -> Result<Vec<ReturnSeasonalityResult>, AppError> {
+    let timeframe_seconds = timeframe::parse_timeframe_seconds(&params.timeframe)?;
+    let period_seconds = match params.bucket {
+        SeasonalityBucket::HourOfDay => SECONDS_PER_HOUR,
+        SeasonalityBucket::Weekday | SeasonalityBucket::Month => SECONDS_PER_DAY,
+    };
+    if timeframe_seconds > period_seconds {
+        return Err(AppError::new(format!(
+            "Timeframe {} is too coarse for this bucket, use bars of {} or less",
+            params.timeframe,
+            if period_seconds == SECONDS_PER_HOUR { "one hour" } else { "one day" }
+        )));
+    }
+
+    let offset_seconds = params.utc_offset_minutes.unwrap_or(0) * 60;
+
+    // Intraday bars are rolled up into hours or days; bars are time-sorted,
+    // so first/last within a period are its open/close
+    let periods_df = scan_bars(&params.symbol, &params.timeframe, params.range)?
+        .with_column((col("time") + lit(offset_seconds)).floor_div(lit(period_seconds)).alias("period"))
+        .group_by_stable([col("period")])
+        .agg([
+            col("open").first().alias("open"),
+            col("close").last().alias("close"),
+        ])
+        .collect()
+        .map_err(|e| AppError::new(format!("Failed to execute query: {}", e)))?;
+
+    let period_col = periods_df
+        .column("period")
+        .map_err(|e| AppError::new(format!("Failed to get 'period' column: {}", e)))?
+        .i64()
+        .map_err(|e| AppError::new(format!("Failed to cast 'period' column: {}", e)))?;
+    let open_col = periods_df
+        .column("open")
+        .map_err(|e| AppError::new(format!("Failed to get 'open' column: {}", e)))?
+        .f64()
+        .map_err(|e| AppError::new(format!("Failed to cast 'open' column: {}", e)))?;
+    let close_col = periods_df
+        .column("close")
+        .map_err(|e| AppError::new(format!("Failed to get 'close' column: {}", e)))?
+        .f64()
+        .map_err(|e| AppError::new(format!("Failed to cast 'close' column: {}", e)))?;
+
+    let mut periods = Vec::with_capacity(periods_df.height());
+    for i in 0..periods_df.height() {
+        if let (Some(period), Some(open), Some(close)) = (period_col.get(i), open_col.get(i), close_col.get(i)) {
+            periods.push(Period {
+                start: period * period_seconds,
+                open,
+                close,
+            });
+        }
+    }
+
+    if params.bucket == SeasonalityBucket::Month {
+        periods = merge_into_months(periods);
+    }
+
+    // Period return (open to close, in percent) grouped by calendar bucket
+    let mut buckets: BTreeMap<i64, Vec<f64>> = BTreeMap::new();
+    for period in &periods {
+        if period.open <= 0.0 {
+            continue;
+        }
+        let value = (period.close / period.open - 1.0) * 100.0;
+        if value.is_finite() {
+            buckets.entry(bucket_of(params.bucket, period.start)).or_default().push(value);
+        }
+    }
+
+    let results = buckets
+        .into_iter()
+        .map(|(bucket, returns)| {
+            let sorted = statistics::sorted(&returns);
+            let mean = statistics::mean(&returns).unwrap_or(0.0);
+            let volatility = statistics::std_dev(&returns);
+            let margin = volatility.map(|std| CONFIDENCE_Z * std / (returns.len() as f64).sqrt());
+
+            ReturnSeasonalityResult {
+                bucket,
+                label: label_of(params.bucket, bucket),
+                count: returns.len() as i64,
+                mean_return: mean,
+                median_return: statistics::median(&sorted).unwrap_or(0.0),
+                hit_rate: returns.iter().filter(|r| **r > 0.0).count() as f64 / returns.len() as f64,
+                volatility,
+                ci_lower: margin.map(|m| mean - m),
+                ci_upper: margin.map(|m| mean + m),
+            }
+        })
+        .collect();
+
+    Ok(results)
+}
+
+/// Rolls consecutive days of the same calendar month into one period
+fn merge_into_months(days: Vec<Period>) -> Vec<Period> {
+    let mut months: Vec<(i32, u32, Period)> = Vec::new();
+    for day in days {
+        let Some(date) = DateTime::from_timestamp(day.start, 0) else { continue };
+        match months.last_mut() {
+            Some((year, month, period)) if *year == date.year() && *month == date.month() => {
+                period.close = day.close;
+            }
+            _ => months.push((date.year(), date.month(), day)),
+        }
+    }
+    months.into_iter().map(|(_, _, period)| period).collect()
+}
+
+/// Calendar bucket of a period start (local seconds)
+fn bucket_of(bucket: SeasonalityBucket, start: i64) -> i64 {
+    match bucket {
+        SeasonalityBucket::HourOfDay => start.div_euclid(SECONDS_PER_HOUR).rem_euclid(24),
+        // The Unix epoch is a Thursday (weekday 3 with Monday = 0)
+        SeasonalityBucket::Weekday => (start.div_euclid(SECONDS_PER_DAY) + 3).rem_euclid(7),
+        SeasonalityBucket::Month => DateTime::from_timestamp(start, 0)
+            .map(|date| date.month() as i64)
+            .unwrap_or(1),
+    }
+}
+
+/// Human readable label of a bucket number
+fn label_of(bucket: SeasonalityBucket, value: i64) -> String {
+    match bucket {
+        SeasonalityBucket::HourOfDay => format!("{:02}:00", value),
+        SeasonalityBucket::Weekday => WEEKDAY_LABELS[value as usize].to_string(),
+        SeasonalityBucket::Month => MONTH_LABELS[(value - 1) as usize].to_string(),
+    }
+}
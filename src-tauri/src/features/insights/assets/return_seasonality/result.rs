@@ -0,0 +1,26 @@
+//! Result structure for return_seasonality insight
+
+use serde::{Deserialize, Serialize};
+
+/// Result row for return_seasonality insight (one calendar bucket)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReturnSeasonalityResult {
+    /// Bucket number: hour 0-23, weekday 0-6 (Monday = 0) or month 1-12
+    pub bucket: i64,
+    /// Human readable bucket label (e.g. "14:00", "Mon", "Jan")
+    pub label: String,
+    /// Number of periods (hours, days or months) in the bucket
+    pub count: i64,
+    /// Mean period return in percent
+    pub mean_return: f64,
+    /// Median period return in percent
+    pub median_return: f64,
+    /// Share of periods with a positive return (0-1)
+    pub hit_rate: f64,
+    /// Sample standard deviation of the period returns in percent (null below two periods)
+    pub volatility: Option<f64>,
+    /// Lower bound of the 95% confidence interval of the mean (null below two periods)
+    pub ci_lower: Option<f64>,
+    /// Upper bound of the 95% confidence interval of the mean (null below two periods)
+    pub ci_upper: Option<f64>,
+}
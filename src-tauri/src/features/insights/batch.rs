@@ -2,6 +2,7 @@
 
 use crate::features::insights::command::execute_insight;
 use crate::features::insights::model::{BatchInsightItem, BatchInsightRequest, BatchInsightResponse};
+use crate::shared::error::AppError;
 use futures::future;
 
 /// Tauri command: execute_batch_insights
@@ -56,9 +57,12 @@ pub async fn execute_batch_insights(
                             response.columns,
                         )
                     } else {
-                        BatchInsightItem::error(
+                        BatchInsightItem::from_error(
                             insight_id,
-                            response.error.unwrap_or_else(|| "Unknown error".to_string()),
+                            AppError {
+                                message: response.error.unwrap_or_else(|| "Unknown error".to_string()),
+                                code: response.error_code,
+                            },
                         )
                     }
                 }
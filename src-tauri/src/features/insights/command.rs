@@ -36,7 +36,7 @@ pub fn execute_insight(request: InsightRequest) -> Result<InsightResponse, Strin
             let columns = extract_columns(&data);
             InsightResponse::success(data, columns)
         }
-        Err(e) => InsightResponse::error(e.message),
+        Err(e) => InsightResponse::from_error(e),
     };
 
     Ok(result)
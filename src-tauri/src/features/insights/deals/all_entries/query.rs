@@ -3,97 +3,27 @@
 
 use crate::features::deals::model::Deal;
 use crate::features::insights::deals::all_entries::params::AllEntriesParams;
+use crate::features::accounts::service::AccountService;
+use crate::features::insights::deals::load_account_deals;
 use crate::shared::error::AppError;
-use crate::shared::utils::cache_dir;
 use polars::prelude::*;
-use std::fs;
 
 /// Executes the all_entries query
 /// Returns all deals with no filter
 pub fn execute_query(params: &AllEntriesParams) -> Result<Vec<Deal>, AppError> {
-    let deals_dir = cache_dir::get_deals_cache_dir()?;
+    // Accounts are resolved through the account registry (id or registered name)
+    let accounts = AccountService::resolve(params.account_number.as_deref())?;
 
-    // Determine which files to read
-    let files_to_read = if let Some(ref account_number) = params.account_number {
-        // Read specific account file
-        let filename = if account_number.ends_with(".parquet") {
-            account_number.clone()
-        } else {
-            format!("{}.parquet", account_number)
-        };
-        let file_path = deals_dir.join(&filename);
-        if !file_path.exists() {
-            return Err(AppError::new(format!(
-                "Deal file not found: {}",
-                file_path.display()
-            )));
-        }
-        vec![file_path]
-    } else {
-        // Read all parquet files in deals directory
-        let mut files = Vec::new();
-        if deals_dir.exists() {
-            let entries = fs::read_dir(&deals_dir)
-                .map_err(|e| AppError::new(format!("Failed to read deals directory: {}", e)))?;
-            
-            for entry in entries {
-                let entry = entry.map_err(|e| AppError::new(format!("Failed to read directory entry: {}", e)))?;
-                let path = entry.path();
-                if path.is_file() && path.extension().and_then(|s| s.to_str()) == Some("parquet") {
-                    files.push(path);
-                }
-            }
-        }
-        files
-    };
-
-    if files_to_read.is_empty() {
+    if accounts.is_empty() {
         return Ok(Vec::new());
     }
 
-    // Read parquet files with no filter
-    let mut dataframes = Vec::new();
-    for file_path in &files_to_read {
-        let df = LazyFrame::scan_parquet(
-            file_path,
-            ScanArgsParquet {
-                n_rows: None,
-                cache: true,
-                parallel: ParallelStrategy::Auto,
-                rechunk: false,
-                row_index: None,
-                low_memory: false,
-                cloud_options: None,
-                use_statistics: true,
-                hive_options: Default::default(),
-                glob: false,
-            },
-        )
-        .map_err(|e| AppError::new(format!("Failed to scan parquet file {}: {}", file_path.display(), e)))?
-        // No filter - return everything
-        .collect()
-        .map_err(|e| AppError::new(format!("Failed to collect data from {}: {}", file_path.display(), e)))?;
-        
-        dataframes.push(df);
-    }
-
-    // Combine all dataframes
-    let combined = if dataframes.len() == 1 {
-        dataframes.into_iter().next().unwrap()
-    } else {
-        let first = dataframes.remove(0);
-        dataframes
-            .into_iter()
-            .fold(first, |acc, df| {
-                acc.vstack(&df)
-                    .map_err(|e| AppError::new(format!("Failed to combine frames: {}", e)))
-                    .unwrap()
-            })
-    };
+    let combined = load_account_deals(&accounts, params.reporting_currency.as_deref(), None)?;
 
     // Sort by time
     let df = combined
         .sort(["time"], SortMultipleOptions::default())
+        .collect()
         .map_err(|e| AppError::new(format!("Failed to sort data: {}", e)))?;
 
     // Convert to Deal structs (reuse logic from trade_entries)
@@ -48,7 +48,11 @@ impl Insight for BalanceEntriesInsight {
                 "properties": {
                     "account_number": {
                         "type": "string",
-                        "description": "Optional account number (filename without .parquet extension)"
+                        "description": "Optional account number (filename without .parquet extension) or registered account name"
+                    },
+                    "reporting_currency": {
+                        "type": "string",
+                        "description": "Optional currency to report amounts in (e.g. \"USD\"), converted at daily FX closes"
                     }
                 }
             })
@@ -0,0 +1,102 @@
+//! Execution Quality insight implementation
+//! Compares deal prices with the cached reference bar at the fill time
+
+mod params;
+mod result;
+mod query;
+
+use crate::features::insights::insight_trait::Insight;
+use crate::shared::error::AppError;
+use params::ExecutionQualityParams;
+use query::execute_query;
+use serde_json::{json, Value};
+
+/// Insight that checks fill prices against cached OHLC data
+pub struct ExecutionQualityInsight;
+
+impl ExecutionQualityInsight {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl Default for ExecutionQualityInsight {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Insight for ExecutionQualityInsight {
+    fn identifier(&self) -> &'static str {
+        "deals.execution_quality"
+    }
+
+    fn name(&self) -> &'static str {
+        "Execution Quality"
+    }
+
+    fn description(&self) -> &'static str {
+        "Flags fills outside the reference bar range and estimates slippage in points, by symbol, hour, reason or deal"
+    }
+
+    fn parameter_schema(&self) -> Value {
+        // Generate JSON Schema from the params struct
+        let schema = schemars::schema_for!(ExecutionQualityParams);
+        serde_json::to_value(schema).unwrap_or_else(|_| {
+            // Fallback schema if serialization fails
+            json!({
+                "type": "object",
+                "properties": {
+                    "account_number": {
+                        "type": "string",
+                        "description": "Optional account number (filename without .parquet extension) or registered account name"
+                    },
+                    "timeframe": {
+                        "type": "string",
+                        "description": "Optional timeframe of the reference bars (defaults to the finest cached timeframe)"
+                    },
+                    "tolerance_points": {
+                        "type": "number",
+                        "description": "Optional distance in points a fill may lie outside the bar range before it is flagged"
+                    },
+                    "point_sizes": {
+                        "type": "object",
+                        "additionalProperties": { "type": "number" },
+                        "description": "Optional point size per broker symbol"
+                    },
+                    "group_by": {
+                        "type": "string",
+                        "enum": ["symbol", "hour", "reason", "deal"],
+                        "description": "Optional aggregation (defaults to symbol)"
+                    }
+                }
+            })
+        })
+    }
+
+    fn validate_parameters(&self, params: &Value) -> Result<(), AppError> {
+        // Try to deserialize to validate structure
+        let _: ExecutionQualityParams = serde_json::from_value(params.clone())
+            .map_err(|e| AppError::new(format!("Invalid parameters: {}", e)))?;
+        
+        Ok(())
+    }
+
+    fn execute(&self, params: Value) -> Result<Value, AppError> {
+        // Deserialize parameters
+        let params: ExecutionQualityParams = serde_json::from_value(params)
+            .map_err(|e| AppError::new(format!("Failed to parse parameters: {}", e)))?;
+
+        // Execute the query
+        let results = execute_query(&params)?;
+
+        // Serialize results to JSON
+        let json_results: Vec<Value> = results
+            .into_iter()
+            .map(|r| serde_json::to_value(r).unwrap_or_else(|_| json!({})))
+            .collect();
+
+        Ok(Value::Array(json_results))
+    }
+}
+
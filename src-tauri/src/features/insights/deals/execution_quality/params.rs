@@ -0,0 +1,48 @@
+//! Parameters for execution_quality insight
+
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// How the checked deals are aggregated
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ExecutionGrouping {
+    /// One row per broker symbol
+    #[default]
+    Symbol,
+    /// One row per hour of day (UTC) of the fill
+    Hour,
+    /// One row per MT5 deal reason code
+    Reason,
+    /// One row per flagged deal (fills beyond the tolerance) and per deal that could not be checked
+    Deal,
+}
+
+/// Parameters for the execution_quality insight
+#[derive(Debug, Clone, Default, Serialize, Deserialize, JsonSchema)]
+pub struct ExecutionQualityParams {
+    /// Optional account number (filename without .parquet extension) or registered account name
+    /// If not provided, will check the deals of all deal files
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub account_number: Option<String>,
+
+    /// Optional timeframe of the reference bars (e.g. "1M")
+    /// If not provided, the finest cached timeframe of each symbol is used
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub timeframe: Option<String>,
+
+    /// Optional distance in points a fill may lie outside the bar's high-low range before it is flagged
+    /// Bars are usually bid prices while buys fill at the ask, so about one typical spread is a sensible value (defaults to 0)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tolerance_points: Option<f64>,
+
+    /// Optional point size per broker symbol (e.g. {"EURUSD.m": 0.00001})
+    /// Symbols without an entry use a point size inferred from the price precision of their bars
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub point_sizes: Option<BTreeMap<String, f64>>,
+
+    /// Optional aggregation (symbol, hour, reason or deal; defaults to symbol)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub group_by: Option<ExecutionGrouping>,
+}
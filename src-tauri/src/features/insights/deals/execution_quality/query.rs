@@ -0,0 +1,241 @@
+//! Query logic for execution_quality insight
+//!
+//! Every buy/sell deal is compared with the cached bar containing its fill time. Slippage is
+//! the distance of the fill outside the bar's high-low range, signed from the trader's view:
+//! a buy above the high or a sell below the low is adverse (positive), the opposite is favorable.
+
+use crate::features::accounts::service::AccountService;
+use crate::features::deals::model::{Deal, DEAL_TYPE_BUY, DEAL_TYPE_SELL};
+use crate::features::deals::service::DealService;
+use crate::features::insights::deals::execution_quality::params::{ExecutionGrouping, ExecutionQualityParams};
+use crate::features::insights::deals::execution_quality::result::{
+    ExecutionDealResult, ExecutionGroupResult, ExecutionQualityResult,
+};
+use crate::features::insights::deals::price_history::{load_symbol_bars, SymbolBars};
+use crate::shared::error::AppError;
+use crate::shared::utils::timeframe;
+use std::collections::{BTreeMap, BTreeSet};
+
+/// Most decimals considered when inferring a point size
+const MAX_PRICE_DECIMALS: i32 = 8;
+/// Bars sampled when inferring a point size
+const PRECISION_SAMPLE_BARS: usize = 500;
+/// Share of a point a price may be off a whole point (float noise) and still count as precise
+const PRECISION_TOLERANCE: f64 = 0.01;
+
+/// Reference bar of a fill and the resulting slippage
+#[derive(Clone, Copy)]
+struct Reference {
+    bar_time: i64,
+    bar_high: f64,
+    bar_low: f64,
+    point_size: f64,
+    slippage_points: f64,
+}
+
+/// Outcome of checking one deal against its reference bar
+struct DealCheck<'a> {
+    account: &'a str,
+    deal: &'a Deal,
+    time_msc: i64,
+    /// Why the deal could not be checked when no bar covers the fill or no point size is known
+    reference: Result<Reference, String>,
+}
+
+/// Executes the execution_quality query
+pub fn execute_query(params: &ExecutionQualityParams) -> Result<Vec<ExecutionQualityResult>, AppError> {
+    if let Some(ref timeframe) = params.timeframe {
+        timeframe::parse_timeframe_seconds(timeframe)?;
+    }
+    let tolerance = params.tolerance_points.unwrap_or(0.0);
+    if !tolerance.is_finite() || tolerance < 0.0 {
+        return Err(AppError::new("Tolerance cannot be negative"));
+    }
+    if let Some(ref point_sizes) = params.point_sizes {
+        if let Some((symbol, _)) = point_sizes.iter().find(|(_, size)| !(size.is_finite() && **size > 0.0)) {
+            return Err(AppError::new(format!("Point size of {} must be a positive number", symbol)));
+        }
+    }
+
+    let accounts = AccountService::resolve(params.account_number.as_deref())?;
+
+    let mut deals_by_account = Vec::with_capacity(accounts.len());
+    for account in accounts {
+        let offset_msc = account.metadata.timezone_offset_minutes * 60_000;
+        let deals: Vec<Deal> = DealService::read_deals_from_file(&account.id)?
+            .into_iter()
+            .filter(|deal| deal.r#type == DEAL_TYPE_BUY || deal.r#type == DEAL_TYPE_SELL)
+            .collect();
+        deals_by_account.push((account.id, offset_msc, deals));
+    }
+
+    let mut checks = Vec::new();
+    for (account, offset_msc, deals) in &deals_by_account {
+        let mut by_symbol: BTreeMap<&str, Vec<(&Deal, i64)>> = BTreeMap::new();
+        for deal in deals {
+            let time_msc = if deal.time_msc != 0 { deal.time_msc } else { deal.time * 1000 };
+            by_symbol
+                .entry(deal.symbol.as_str())
+                .or_default()
+                .push((deal, time_msc - offset_msc));
+        }
+
+        for (symbol, deals) in by_symbol {
+            // Only the bar of each fill is needed
+            let windows = deals.iter().map(|(_, time_msc)| {
+                let time = time_msc.div_euclid(1000);
+                (time, time)
+            });
+            let history = load_symbol_bars(symbol, params.timeframe.as_deref(), windows);
+
+            let point_size = match params.point_sizes.as_ref().and_then(|sizes| sizes.get(symbol).copied()) {
+                Some(point_size) => Ok(point_size),
+                None => history.as_ref().map_err(Clone::clone).and_then(|history| {
+                    infer_point_size(history).ok_or_else(|| {
+                        format!("No point size for {}. Set it in point_sizes", symbol)
+                    })
+                }),
+            };
+
+            for (deal, time_msc) in deals {
+                let reference = history
+                    .as_ref()
+                    .map_err(Clone::clone)
+                    .and_then(|history| point_size.clone().map(|point_size| (history, point_size)))
+                    .and_then(|(history, point_size)| {
+                        let index = history.bar_at(time_msc.div_euclid(1000)).ok_or_else(|| {
+                            format!("No {} bar at the fill time", history.timeframe)
+                        })?;
+                        let bar = &history.bars[index];
+                        let outside = if deal.price > bar.high {
+                            deal.price - bar.high
+                        } else if deal.price < bar.low {
+                            deal.price - bar.low
+                        } else {
+                            0.0
+                        };
+                        // Paying more on a buy or receiving less on a sell is adverse
+                        let slippage = if deal.r#type == DEAL_TYPE_BUY { outside } else { -outside };
+                        Ok(Reference {
+                            bar_time: bar.time,
+                            bar_high: bar.high,
+                            bar_low: bar.low,
+                            point_size,
+                            slippage_points: slippage / point_size,
+                        })
+                    });
+
+                checks.push(DealCheck {
+                    account,
+                    deal,
+                    time_msc,
+                    reference,
+                });
+            }
+        }
+    }
+
+    let is_flagged = |slippage_points: f64| slippage_points.abs() > tolerance;
+    let grouping = params.group_by.unwrap_or_default();
+
+    if grouping == ExecutionGrouping::Deal {
+        // Unchecked deals are reported too, with the reason, after the flagged ones
+        let mut results: Vec<ExecutionDealResult> = checks
+            .iter()
+            .filter(|check| match &check.reference {
+                Ok(reference) => is_flagged(reference.slippage_points),
+                Err(_) => true,
+            })
+            .map(|check| {
+                let reference = check.reference.as_ref().ok();
+                ExecutionDealResult {
+                    account: check.account.to_string(),
+                    ticket: check.deal.ticket,
+                    position_id: check.deal.position_id,
+                    symbol: check.deal.symbol.clone(),
+                    side: if check.deal.r#type == DEAL_TYPE_BUY { "buy" } else { "sell" }.to_string(),
+                    reason: check.deal.reason,
+                    time_msc: check.time_msc,
+                    price: check.deal.price,
+                    bar_time: reference.map(|reference| reference.bar_time),
+                    bar_high: reference.map(|reference| reference.bar_high),
+                    bar_low: reference.map(|reference| reference.bar_low),
+                    point_size: reference.map(|reference| reference.point_size),
+                    slippage_points: reference.map(|reference| reference.slippage_points),
+                    price_data_missing: reference.is_none(),
+                    missing_reason: check.reference.as_ref().err().cloned(),
+                }
+            })
+            .collect();
+        results.sort_by(|a, b| match (a.slippage_points, b.slippage_points) {
+            (Some(a), Some(b)) => b.abs().total_cmp(&a.abs()),
+            (a, b) => b.is_some().cmp(&a.is_some()),
+        });
+        return Ok(results.into_iter().map(ExecutionQualityResult::Deal).collect());
+    }
+
+    // Groups sorted by number (hour, reason) or by name (symbol)
+    let mut groups: BTreeMap<(i64, String), Vec<&DealCheck>> = BTreeMap::new();
+    for check in &checks {
+        let key = match grouping {
+            ExecutionGrouping::Hour => (check.time_msc.div_euclid(3_600_000).rem_euclid(24), String::new()),
+            ExecutionGrouping::Reason => (check.deal.reason, String::new()),
+            _ => (0, check.deal.symbol.clone()),
+        };
+        groups.entry(key).or_default().push(check);
+    }
+
+    let results = groups
+        .into_iter()
+        .map(|((number, name), checks)| {
+            let slippages: Vec<f64> = checks
+                .iter()
+                .filter_map(|check| check.reference.as_ref().ok().map(|reference| reference.slippage_points))
+                .collect();
+            let missing_reasons: BTreeSet<&String> =
+                checks.iter().filter_map(|check| check.reference.as_ref().err()).collect();
+            let checked = slippages.len();
+            let flagged = slippages.iter().filter(|s| is_flagged(**s)).count();
+
+            ExecutionQualityResult::Group(ExecutionGroupResult {
+                group: if name.is_empty() { number.to_string() } else { name },
+                deal_count: checks.len() as i64,
+                checked_count: checked as i64,
+                missing_count: (checks.len() - checked) as i64,
+                missing_reasons: missing_reasons.into_iter().cloned().collect(),
+                outside_range_count: slippages.iter().filter(|s| **s != 0.0).count() as i64,
+                flagged_count: flagged as i64,
+                flagged_pct: if checked > 0 { flagged as f64 / checked as f64 * 100.0 } else { 0.0 },
+                avg_slippage_points: if checked > 0 { slippages.iter().sum::<f64>() / checked as f64 } else { 0.0 },
+                max_slippage_points: if checked > 0 { slippages.iter().copied().fold(f64::NEG_INFINITY, f64::max) } else { 0.0 },
+                total_adverse_points: slippages.iter().filter(|s| **s > 0.0).sum(),
+            })
+        })
+        .collect();
+
+    Ok(results)
+}
+
+/// Infers the point size from the price precision of the bars (e.g. 0.00001 for 5-decimal quotes)
+fn infer_point_size(history: &SymbolBars) -> Option<f64> {
+    let prices: Vec<f64> = history
+        .bars
+        .iter()
+        .take(PRECISION_SAMPLE_BARS)
+        .flat_map(|bar| [bar.open, bar.high, bar.low, bar.close])
+        .collect();
+    if prices.is_empty() {
+        return None;
+    }
+
+    let decimals = (0..=MAX_PRICE_DECIMALS)
+        .find(|decimals| {
+            let scale = 10f64.powi(*decimals);
+            prices
+                .iter()
+                .all(|price| ((price * scale) - (price * scale).round()).abs() < PRECISION_TOLERANCE)
+        })
+        .unwrap_or(MAX_PRICE_DECIMALS);
+
+    Some(10f64.powi(-decimals))
+}
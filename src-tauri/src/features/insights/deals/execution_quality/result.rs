@@ -0,0 +1,67 @@
+//! Result structures for execution_quality insight
+
+use serde::{Deserialize, Serialize};
+
+/// Result row for execution_quality insight
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum ExecutionQualityResult {
+    Group(ExecutionGroupResult),
+    Deal(ExecutionDealResult),
+}
+
+/// Aggregated execution quality of a symbol, hour or reason code
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExecutionGroupResult {
+    /// Group value: the symbol, the hour of day (0-23) or the reason code
+    pub group: String,
+    /// Buy/sell deals in the group
+    pub deal_count: i64,
+    /// Deals with a reference bar
+    pub checked_count: i64,
+    /// Deals without a reference bar (no cached data or no bar at the fill time)
+    pub missing_count: i64,
+    /// Why deals of the group could not be checked, each reason once
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub missing_reasons: Vec<String>,
+    /// Checked deals filled outside the bar's high-low range
+    pub outside_range_count: i64,
+    /// Checked deals filled beyond the tolerance
+    pub flagged_count: i64,
+    /// Share of checked deals that were flagged, in percent
+    pub flagged_pct: f64,
+    /// Mean slippage in points (positive = worse than the bar range for the trader)
+    pub avg_slippage_points: f64,
+    /// Worst slippage in points
+    pub max_slippage_points: f64,
+    /// Sum of adverse slippage in points
+    pub total_adverse_points: f64,
+}
+
+/// Execution check of a single flagged or unchecked deal
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExecutionDealResult {
+    pub account: String,
+    pub ticket: i64,
+    pub position_id: i64,
+    pub symbol: String,
+    /// "buy" or "sell"
+    pub side: String,
+    pub reason: i64,
+    /// Fill time (UTC, milliseconds)
+    pub time_msc: i64,
+    pub price: f64,
+    /// Open time of the reference bar (UTC, Unix seconds); None when the deal was not checked
+    pub bar_time: Option<i64>,
+    pub bar_high: Option<f64>,
+    pub bar_low: Option<f64>,
+    /// Point size used for the conversion
+    pub point_size: Option<f64>,
+    /// Slippage in points (positive = worse than the bar range for the trader)
+    pub slippage_points: Option<f64>,
+    /// True when no reference bar could be used for the deal
+    pub price_data_missing: bool,
+    /// Why the deal could not be checked
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub missing_reason: Option<String>,
+}
@@ -0,0 +1,92 @@
+//! MAE/MFE insight implementation
+//! Maximum adverse/favorable excursion and exit efficiency of closed positions
+
+mod params;
+mod result;
+mod query;
+
+use crate::features::insights::insight_trait::Insight;
+use crate::shared::error::AppError;
+use params::MaeMfeParams;
+use query::execute_query;
+use serde_json::{json, Value};
+
+/// Insight that measures price excursions of closed positions against cached OHLC data
+pub struct MaeMfeInsight;
+
+impl MaeMfeInsight {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl Default for MaeMfeInsight {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Insight for MaeMfeInsight {
+    fn identifier(&self) -> &'static str {
+        "deals.mae_mfe"
+    }
+
+    fn name(&self) -> &'static str {
+        "MAE / MFE"
+    }
+
+    fn description(&self) -> &'static str {
+        "Maximum adverse and favorable excursion and exit efficiency of every closed position, measured on cached price history"
+    }
+
+    fn parameter_schema(&self) -> Value {
+        // Generate JSON Schema from the params struct
+        let schema = schemars::schema_for!(MaeMfeParams);
+        serde_json::to_value(schema).unwrap_or_else(|_| {
+            // Fallback schema if serialization fails
+            json!({
+                "type": "object",
+                "properties": {
+                    "account_number": {
+                        "type": "string",
+                        "description": "Optional account number (filename without .parquet extension) or registered account name"
+                    },
+                    "timeframe": {
+                        "type": "string",
+                        "description": "Optional timeframe of the price history (defaults to the finest cached timeframe)"
+                    },
+                    "reporting_currency": {
+                        "type": "string",
+                        "description": "Optional currency to report amounts in (e.g. \"USD\"), converted at daily FX closes"
+                    }
+                }
+            })
+        })
+    }
+
+    fn validate_parameters(&self, params: &Value) -> Result<(), AppError> {
+        // Try to deserialize to validate structure
+        let _: MaeMfeParams = serde_json::from_value(params.clone())
+            .map_err(|e| AppError::new(format!("Invalid parameters: {}", e)))?;
+        
+        Ok(())
+    }
+
+    fn execute(&self, params: Value) -> Result<Value, AppError> {
+        // Deserialize parameters
+        let params: MaeMfeParams = serde_json::from_value(params)
+            .map_err(|e| AppError::new(format!("Failed to parse parameters: {}", e)))?;
+
+        // Execute the query
+        let results = execute_query(&params)?;
+
+        // Serialize results to JSON
+        let json_results: Vec<Value> = results
+            .into_iter()
+            .map(|r| serde_json::to_value(r).unwrap_or_else(|_| json!({})))
+            .collect();
+
+        Ok(Value::Array(json_results))
+    }
+}
+
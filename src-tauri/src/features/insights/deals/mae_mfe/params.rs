@@ -0,0 +1,23 @@
+//! Parameters for mae_mfe insight
+
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+/// Parameters for the mae_mfe insight
+#[derive(Debug, Clone, Default, Serialize, Deserialize, JsonSchema)]
+pub struct MaeMfeParams {
+    /// Optional account number (filename without .parquet extension) or registered account name
+    /// If not provided, will analyze the positions of all deal files
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub account_number: Option<String>,
+
+    /// Optional timeframe of the price history (e.g. "1M")
+    /// If not provided, the finest cached timeframe of each symbol is used
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub timeframe: Option<String>,
+
+    /// Optional currency to report amounts in (e.g. "USD"), converted at daily FX closes
+    /// Requires the currency of each account to be set
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reporting_currency: Option<String>,
+}
@@ -0,0 +1,195 @@
+//! Query logic for mae_mfe insight
+//!
+//! Positions are rebuilt from their deals (grouped by `position_id`) and matched to the
+//! bars from the bar containing the first entry up to the last exit. The entry bar's full
+//! range is included, so excursions are measured at bar resolution.
+
+use crate::features::asset::model::OHLCData;
+use crate::features::accounts::service::AccountService;
+use crate::features::deals::model::{Deal, PositionStatus};
+use crate::features::deals::positions::PositionService;
+use crate::features::deals::service::DealService;
+use crate::features::insights::deals::mae_mfe::params::MaeMfeParams;
+use crate::features::insights::deals::mae_mfe::result::MaeMfeResult;
+use crate::features::insights::deals::price_history::load_symbol_bars;
+use crate::features::fx::service::CurrencyConverter;
+use crate::shared::error::AppError;
+use crate::shared::utils::timeframe;
+use std::collections::BTreeMap;
+
+/// A closed position as measured by the insight (times in UTC)
+struct ClosedPosition {
+    position_id: i64,
+    symbol: String,
+    /// 1 for buy positions, -1 for sell positions
+    direction: f64,
+    volume: f64,
+    open_time: i64,
+    close_time: i64,
+    entry_price: f64,
+    exit_price: f64,
+    profit: f64,
+}
+
+/// Executes the mae_mfe query
+pub fn execute_query(params: &MaeMfeParams) -> Result<Vec<MaeMfeResult>, AppError> {
+    // Fail early on an invalid timeframe instead of flagging every position
+    if let Some(ref timeframe) = params.timeframe {
+        timeframe::parse_timeframe_seconds(timeframe)?;
+    }
+
+    let accounts = AccountService::resolve(params.account_number.as_deref())?;
+    let mut converter = params
+        .reporting_currency
+        .as_deref()
+        .map(CurrencyConverter::new)
+        .transpose()?;
+
+    let mut results = Vec::new();
+    for account in accounts {
+        let currency = converter
+            .as_ref()
+            .map(|converter| converter.account_currency(&account.id))
+            .transpose()?;
+        let offset_seconds = account.metadata.timezone_offset_minutes * 60;
+        let deals = DealService::read_deals_from_file(&account.id)?;
+
+        let mut by_symbol: BTreeMap<String, Vec<ClosedPosition>> = BTreeMap::new();
+        for position in closed_positions(&deals, offset_seconds) {
+            by_symbol.entry(position.symbol.clone()).or_default().push(position);
+        }
+
+        for (symbol, positions) in by_symbol {
+            let mut analyzed = analyze_symbol(&account.id, &symbol, positions, params.timeframe.as_deref());
+            // Realized profit is converted at the close of the position
+            if let (Some(converter), Some(currency)) = (converter.as_mut(), currency.as_deref()) {
+                for result in &mut analyzed {
+                    result.profit = converter.convert(result.profit, currency, result.close_time)?;
+                }
+            }
+            results.extend(analyzed);
+        }
+    }
+
+    results.sort_by(|a, b| {
+        a.account
+            .cmp(&b.account)
+            .then(a.open_time.cmp(&b.open_time))
+            .then(a.position_id.cmp(&b.position_id))
+    });
+
+    Ok(results)
+}
+
+/// Keeps the positions whose volume is fully closed
+fn closed_positions(deals: &[Deal], offset_seconds: i64) -> Vec<ClosedPosition> {
+    PositionService::build_positions(deals, offset_seconds)
+        .into_iter()
+        .filter(|position| position.status == PositionStatus::Closed)
+        .filter_map(|position| {
+            Some(ClosedPosition {
+                close_time: position.close_time?,
+                exit_price: position.exit_price?,
+                position_id: position.position_id,
+                symbol: position.symbol,
+                direction: position.direction.sign(),
+                volume: position.entry_volume,
+                open_time: position.open_time,
+                entry_price: position.entry_price,
+                profit: position.gross_profit,
+            })
+        })
+        .collect()
+}
+
+/// Measures the positions of one symbol against its cached bars
+/// Positions without usable price data are flagged instead of failing the insight
+fn analyze_symbol(
+    account: &str,
+    symbol: &str,
+    positions: Vec<ClosedPosition>,
+    timeframe: Option<&str>,
+) -> Vec<MaeMfeResult> {
+    let loaded = load_symbol_bars(
+        symbol,
+        timeframe,
+        positions.iter().map(|position| (position.open_time, position.close_time)),
+    );
+
+    positions
+        .into_iter()
+        .map(|position| {
+            let mut result = MaeMfeResult {
+                account: account.to_string(),
+                position_id: position.position_id,
+                symbol: symbol.to_string(),
+                asset_symbol: None,
+                timeframe: None,
+                side: if position.direction > 0.0 { "buy" } else { "sell" }.to_string(),
+                volume: position.volume,
+                open_time: position.open_time,
+                close_time: position.close_time,
+                entry_price: position.entry_price,
+                exit_price: position.exit_price,
+                profit: position.profit,
+                mae: None,
+                mfe: None,
+                mae_pct: None,
+                mfe_pct: None,
+                exit_efficiency: None,
+                bar_count: 0,
+                price_data_missing: false,
+                missing_reason: None,
+            };
+
+            let history = match &loaded {
+                Ok(history) => history,
+                Err(reason) => {
+                    result.price_data_missing = true;
+                    result.missing_reason = Some(reason.clone());
+                    return result;
+                }
+            };
+            result.asset_symbol = Some(history.asset_symbol.clone());
+            result.timeframe = Some(history.timeframe.clone());
+
+            // Bars from the one containing the entry up to the exit
+            let bars = &history.bars;
+            let start = bars.partition_point(|bar| bar.time <= position.open_time - history.timeframe_seconds);
+            let end = bars.partition_point(|bar| bar.time <= position.close_time);
+            if start >= end {
+                result.price_data_missing = true;
+                result.missing_reason = Some(format!("No {} bars between entry and exit", history.timeframe));
+                return result;
+            }
+
+            apply_excursions(&mut result, &position, &bars[start..end]);
+            result
+        })
+        .collect()
+}
+
+/// Fills MAE, MFE and exit efficiency from the bars the position was open in
+fn apply_excursions(result: &mut MaeMfeResult, position: &ClosedPosition, bars: &[OHLCData]) {
+    let high = bars.iter().map(|bar| bar.high).fold(f64::NEG_INFINITY, f64::max);
+    let low = bars.iter().map(|bar| bar.low).fold(f64::INFINITY, f64::min);
+    let entry = position.entry_price;
+
+    let (adverse, favorable) = if position.direction > 0.0 {
+        (entry - low, high - entry)
+    } else {
+        (high - entry, entry - low)
+    };
+    let mae = adverse.max(0.0);
+    let mfe = favorable.max(0.0);
+    let captured = (position.exit_price - entry) * position.direction;
+
+    result.bar_count = bars.len() as i64;
+    result.mae = Some(mae);
+    result.mfe = Some(mfe);
+    if entry != 0.0 {
+        result.mae_pct = Some(mae / entry * 100.0);
+        result.mfe_pct = Some(mfe / entry * 100.0);
+    }
+    result.exit_efficiency = (mfe > 0.0).then(|| captured / mfe);
+}
@@ -0,0 +1,48 @@
+//! Result structure for mae_mfe insight
+
+use serde::{Deserialize, Serialize};
+
+/// Result row for mae_mfe insight (one closed position)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MaeMfeResult {
+    /// Account (deal file) the position belongs to
+    pub account: String,
+    pub position_id: i64,
+    /// Symbol as recorded by the broker
+    pub symbol: String,
+    /// Cached asset symbol the price history was read from
+    pub asset_symbol: Option<String>,
+    /// Timeframe of the price history
+    pub timeframe: Option<String>,
+    /// "buy" or "sell"
+    pub side: String,
+    /// Total volume opened
+    pub volume: f64,
+    /// First entry time (UTC, Unix seconds)
+    pub open_time: i64,
+    /// Last exit time (UTC, Unix seconds)
+    pub close_time: i64,
+    /// Volume weighted entry price
+    pub entry_price: f64,
+    /// Volume weighted exit price
+    pub exit_price: f64,
+    /// Sum of the position's deal profits
+    pub profit: f64,
+    /// Maximum adverse excursion from the entry price (price units)
+    pub mae: Option<f64>,
+    /// Maximum favorable excursion from the entry price (price units)
+    pub mfe: Option<f64>,
+    /// MAE as a percentage of the entry price
+    pub mae_pct: Option<f64>,
+    /// MFE as a percentage of the entry price
+    pub mfe_pct: Option<f64>,
+    /// Share of the MFE captured at exit (1 = exited at the best price, negative = exited at a loss)
+    pub exit_efficiency: Option<f64>,
+    /// Number of bars between entry and exit
+    pub bar_count: i64,
+    /// True when no price history covers the position
+    pub price_data_missing: bool,
+    /// Why the price history is missing
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub missing_reason: Option<String>,
+}
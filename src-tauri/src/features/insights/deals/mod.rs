@@ -6,4 +6,68 @@ pub mod all_entries;
 pub mod trade_entries_with_balance;
 pub mod balance_entries;
 pub mod total_balance;
+pub mod mae_mfe;
+pub mod positions;
+pub mod execution_quality;
 
+pub mod price_history;
+
+use crate::features::accounts::model::ResolvedAccount;
+use crate::features::deals::model::deals_schema;
+use crate::features::fx::service::CurrencyConverter;
+use crate::shared::error::AppError;
+use polars::prelude::*;
+
+/// Loads the deals of `accounts` (at least one) as a single frame.
+/// `filter` is applied while scanning each file, before it is collected, so it can be pushed
+/// down to the Parquet reader.
+/// Only the schema columns are kept: columns a lenient import kept beyond the schema would
+/// break stacking the files.
+/// With a `reporting_currency`, amounts are converted per account, before accounts in
+/// different currencies are combined.
+pub fn load_account_deals(
+    accounts: &[ResolvedAccount],
+    reporting_currency: Option<&str>,
+    filter: Option<Expr>,
+) -> Result<LazyFrame, AppError> {
+    let mut converter = reporting_currency.map(CurrencyConverter::new).transpose()?;
+
+    let mut frames = Vec::with_capacity(accounts.len());
+    for account in accounts {
+        let file_path = &account.deals_path;
+        let mut scan = LazyFrame::scan_parquet(
+            file_path,
+            ScanArgsParquet {
+                n_rows: None,
+                cache: true,
+                parallel: ParallelStrategy::Auto,
+                rechunk: false,
+                row_index: None,
+                low_memory: false,
+                cloud_options: None,
+                use_statistics: true,
+                hive_options: Default::default(),
+                glob: false,
+            },
+        )
+        .map_err(|e| AppError::new(format!("Failed to scan parquet file {}: {}", file_path.display(), e)))?;
+        if let Some(filter) = filter.clone() {
+            scan = scan.filter(filter);
+        }
+
+        let df = scan
+            .select(deals_schema::REQUIRED_COLUMNS.iter().map(|column| col(column)).collect::<Vec<_>>())
+            .collect()
+            .map_err(|e| AppError::new(format!("Failed to collect data from {}: {}", file_path.display(), e)))?;
+
+        let df = match converter.as_mut() {
+            Some(converter) => converter.convert_deals_frame(df, &account.id)?,
+            None => df,
+        };
+
+        frames.push(df.lazy());
+    }
+
+    concat(frames, UnionArgs::default())
+        .map_err(|e| AppError::new(format!("Failed to combine frames: {}", e)))
+}
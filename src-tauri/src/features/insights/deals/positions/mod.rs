@@ -0,0 +1,97 @@
+//! Positions insight implementation
+//! Positions rebuilt from deals grouped by position_id
+
+mod params;
+mod result;
+mod query;
+
+use crate::features::insights::insight_trait::Insight;
+use crate::shared::error::AppError;
+use params::PositionsParams;
+use query::execute_query;
+use serde_json::{json, Value};
+
+/// Insight that rebuilds open and closed positions from their deals
+pub struct PositionsInsight;
+
+impl PositionsInsight {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl Default for PositionsInsight {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Insight for PositionsInsight {
+    fn identifier(&self) -> &'static str {
+        "deals.positions"
+    }
+
+    fn name(&self) -> &'static str {
+        "Positions"
+    }
+
+    fn description(&self) -> &'static str {
+        "Positions rebuilt from their deals with direction, open/close times, average entry and exit prices, volumes, partial closes, duration and gross and net P&L"
+    }
+
+    fn parameter_schema(&self) -> Value {
+        // Generate JSON Schema from the params struct
+        let schema = schemars::schema_for!(PositionsParams);
+        serde_json::to_value(schema).unwrap_or_else(|_| {
+            // Fallback schema if serialization fails
+            json!({
+                "type": "object",
+                "properties": {
+                    "account_number": {
+                        "type": "string",
+                        "description": "Optional account number (filename without .parquet extension) or registered account name"
+                    },
+                    "symbol": {
+                        "type": "string",
+                        "description": "Optional broker symbol to keep (e.g. \"EURUSD\")"
+                    },
+                    "status": {
+                        "type": "string",
+                        "enum": ["open", "closed"],
+                        "description": "Optional status to keep (defaults to both)"
+                    },
+                    "reporting_currency": {
+                        "type": "string",
+                        "description": "Optional currency to report amounts in (e.g. \"USD\"), converted at daily FX closes"
+                    }
+                }
+            })
+        })
+    }
+
+    fn validate_parameters(&self, params: &Value) -> Result<(), AppError> {
+        // Try to deserialize to validate structure
+        let _: PositionsParams = serde_json::from_value(params.clone())
+            .map_err(|e| AppError::new(format!("Invalid parameters: {}", e)))?;
+        
+        Ok(())
+    }
+
+    fn execute(&self, params: Value) -> Result<Value, AppError> {
+        // Deserialize parameters
+        let params: PositionsParams = serde_json::from_value(params)
+            .map_err(|e| AppError::new(format!("Failed to parse parameters: {}", e)))?;
+
+        // Execute the query
+        let results = execute_query(&params)?;
+
+        // Serialize results to JSON
+        let json_results: Vec<Value> = results
+            .into_iter()
+            .map(|r| serde_json::to_value(r).unwrap_or_else(|_| json!({})))
+            .collect();
+
+        Ok(Value::Array(json_results))
+    }
+}
+
@@ -0,0 +1,28 @@
+//! Parameters for positions insight
+
+use crate::features::deals::model::PositionStatus;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+/// Parameters for the positions insight
+#[derive(Debug, Clone, Default, Serialize, Deserialize, JsonSchema)]
+pub struct PositionsParams {
+    /// Optional account number (filename without .parquet extension) or registered account name
+    /// If not provided, will rebuild the positions of all deal files
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub account_number: Option<String>,
+
+    /// Optional broker symbol to keep (e.g. "EURUSD")
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub symbol: Option<String>,
+
+    /// Optional status to keep ("open" or "closed")
+    /// If not provided, both open and closed positions are returned
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub status: Option<PositionStatus>,
+
+    /// Optional currency to report amounts in (e.g. "USD"), converted at daily FX closes
+    /// Requires the currency of each account to be set
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reporting_currency: Option<String>,
+}
@@ -0,0 +1,64 @@
+//! Query logic for positions insight
+//!
+//! Positions are rebuilt by the PositionService from the account's buy/sell deals,
+//! with times shifted from server time to UTC by the account's timezone offset.
+
+use crate::features::accounts::service::AccountService;
+use crate::features::deals::positions::PositionService;
+use crate::features::deals::service::DealService;
+use crate::features::insights::deals::positions::params::PositionsParams;
+use crate::features::insights::deals::positions::result::PositionsResult;
+use crate::features::fx::service::CurrencyConverter;
+use crate::shared::error::AppError;
+
+/// Executes the positions query
+pub fn execute_query(params: &PositionsParams) -> Result<Vec<PositionsResult>, AppError> {
+    let accounts = AccountService::resolve(params.account_number.as_deref())?;
+    let mut converter = params
+        .reporting_currency
+        .as_deref()
+        .map(CurrencyConverter::new)
+        .transpose()?;
+
+    let mut results = Vec::new();
+    for account in accounts {
+        let currency = converter
+            .as_ref()
+            .map(|converter| converter.account_currency(&account.id))
+            .transpose()?;
+        let offset_seconds = account.metadata.timezone_offset_minutes * 60;
+        let deals = DealService::read_deals_from_file(&account.id)?;
+
+        for mut position in PositionService::build_positions(&deals, offset_seconds) {
+            if params.symbol.as_deref().is_some_and(|symbol| symbol != position.symbol)
+                || params.status.is_some_and(|status| status != position.status)
+            {
+                continue;
+            }
+
+            // Amounts are converted at the close of the position (its open while still open)
+            if let (Some(converter), Some(currency)) = (converter.as_mut(), currency.as_deref()) {
+                let rate = converter.rate(currency, position.close_time.unwrap_or(position.open_time))?;
+                position.gross_profit *= rate;
+                position.commission *= rate;
+                position.swap *= rate;
+                position.fee *= rate;
+                position.net_profit *= rate;
+            }
+
+            results.push(PositionsResult {
+                account: account.id.clone(),
+                position,
+            });
+        }
+    }
+
+    results.sort_by(|a, b| {
+        a.account
+            .cmp(&b.account)
+            .then(a.position.open_time.cmp(&b.position.open_time))
+            .then(a.position.position_id.cmp(&b.position.position_id))
+    });
+
+    Ok(results)
+}
@@ -0,0 +1,13 @@
+//! Result structure for positions insight
+
+use crate::features::deals::model::Position;
+use serde::{Deserialize, Serialize};
+
+/// Result row for positions insight (one position)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PositionsResult {
+    /// Account (deal file) the position belongs to
+    pub account: String,
+    #[serde(flatten)]
+    pub position: Position,
+}
@@ -0,0 +1,118 @@
+//! Cached price history for deal insights
+//! Matches broker symbols to cached assets and loads the bars covering a set of deals
+
+use crate::features::asset::model::OHLCData;
+use crate::features::asset::service::AssetService;
+use crate::shared::utils::timeframe;
+
+/// Most separate time windows read per symbol
+const MAX_WINDOWS: usize = 256;
+
+/// Bars of the cached asset matching a broker symbol
+pub struct SymbolBars {
+    /// Cached asset symbol (e.g. "EURUSD" for the broker symbol "EURUSD.m")
+    pub asset_symbol: String,
+    pub timeframe: String,
+    pub timeframe_seconds: i64,
+    /// Time-sorted bars
+    pub bars: Vec<OHLCData>,
+}
+
+impl SymbolBars {
+    /// Index of the bar containing `time` (UTC seconds), if any
+    pub fn bar_at(&self, time: i64) -> Option<usize> {
+        let index = self.bars.partition_point(|bar| bar.time <= time).checked_sub(1)?;
+        (time < self.bars[index].time + self.timeframe_seconds).then_some(index)
+    }
+}
+
+/// Matches `symbol` to a cached asset and loads its bars inside `windows` (UTC `(start, end)`
+/// pairs, each widened to the bar containing its start)
+/// Uses `timeframe` when given, otherwise the finest cached timeframe of the asset
+/// Errors are returned as messages so callers can flag the affected deals instead of failing
+pub fn load_symbol_bars(
+    symbol: &str,
+    timeframe: Option<&str>,
+    windows: impl IntoIterator<Item = (i64, i64)>,
+) -> Result<SymbolBars, String> {
+    let asset_symbol = AssetService::resolve_cached_symbol(symbol).map_err(|e| e.message)?;
+    let timeframe = match timeframe {
+        Some(timeframe) => timeframe.to_string(),
+        None => AssetService::finest_timeframe(&asset_symbol).map_err(|e| e.message)?,
+    };
+    let timeframe_seconds = timeframe::parse_timeframe_seconds(&timeframe).map_err(|e| e.message)?;
+
+    let windows = merge_windows(
+        windows
+            .into_iter()
+            .map(|(start, end)| (start - timeframe_seconds, end))
+            .collect(),
+    );
+    let bars = AssetService::retrieve_asset_ochl_windows(&asset_symbol, &timeframe, &windows)
+        .map_err(|e| e.message)?;
+
+    Ok(SymbolBars {
+        asset_symbol,
+        timeframe,
+        timeframe_seconds,
+        bars,
+    })
+}
+
+/// Sorts the windows and merges overlapping ones
+/// Beyond `MAX_WINDOWS`, the windows separated by the smallest gaps are merged as well,
+/// which keeps the scan filter small at the cost of reading a few bars between them
+fn merge_windows(mut windows: Vec<(i64, i64)>) -> Vec<(i64, i64)> {
+    windows.sort_unstable();
+    let mut merged: Vec<(i64, i64)> = Vec::with_capacity(windows.len());
+    for (start, end) in windows {
+        match merged.last_mut() {
+            Some(last) if start <= last.1 => last.1 = last.1.max(end),
+            _ => merged.push((start, end)),
+        }
+    }
+    if merged.len() <= MAX_WINDOWS {
+        return merged;
+    }
+
+    // Gaps up to the largest one that still has to be closed are merged
+    let mut gaps: Vec<i64> = merged.windows(2).map(|pair| pair[1].0 - pair[0].1).collect();
+    gaps.sort_unstable();
+    let max_merged_gap = gaps[merged.len() - MAX_WINDOWS - 1];
+
+    let mut reduced: Vec<(i64, i64)> = Vec::with_capacity(MAX_WINDOWS);
+    for (start, end) in merged {
+        match reduced.last_mut() {
+            Some(last) if start - last.1 <= max_merged_gap => last.1 = last.1.max(end),
+            _ => reduced.push((start, end)),
+        }
+    }
+    reduced
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn merges_overlapping_windows() {
+        assert_eq!(merge_windows(vec![(50, 60), (0, 10), (5, 20), (20, 30)]), vec![(0, 30), (50, 60)]);
+        assert_eq!(merge_windows(Vec::new()), Vec::new());
+    }
+
+    #[test]
+    fn merges_the_closest_windows_beyond_the_limit() {
+        // Pairs of 5 second windows 10 seconds apart, the pairs 100 seconds apart
+        let mut windows = Vec::new();
+        let mut start = 0;
+        for i in 0..MAX_WINDOWS * 2 {
+            windows.push((start, start + 5));
+            start += if i % 2 == 0 { 15 } else { 105 };
+        }
+
+        let merged = merge_windows(windows);
+        assert_eq!(merged.len(), MAX_WINDOWS);
+        assert_eq!(merged[0], (0, 20));
+        assert_eq!(merged[1], (120, 140));
+    }
+}
@@ -48,11 +48,15 @@ impl Insight for ProfitBySymbolInsight {
                 "properties": {
                     "account_number": {
                         "type": "string",
-                        "description": "Optional account number (filename without .parquet extension)"
+                        "description": "Optional account number (filename without .parquet extension) or registered account name"
                     },
                     "min_profit": {
                         "type": "number",
                         "description": "Optional minimum profit threshold to filter results"
+                    },
+                    "reporting_currency": {
+                        "type": "string",
+                        "description": "Optional currency to report amounts in (e.g. \"USD\"), converted at daily FX closes"
                     }
                 }
             })
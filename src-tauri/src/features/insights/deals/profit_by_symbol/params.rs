@@ -6,7 +6,7 @@ use serde::{Deserialize, Serialize};
 /// Parameters for the profit_by_symbol insight
 #[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct ProfitBySymbolParams {
-    /// Optional account number (filename without .parquet extension)
+    /// Optional account number (filename without .parquet extension) or registered account name
     /// If not provided, will aggregate across all deal files
     #[serde(skip_serializing_if = "Option::is_none")]
     pub account_number: Option<String>,
@@ -14,6 +14,11 @@ pub struct ProfitBySymbolParams {
     /// Optional minimum profit threshold to filter results
     #[serde(skip_serializing_if = "Option::is_none")]
     pub min_profit: Option<f64>,
+
+    /// Optional currency to report amounts in (e.g. "USD"), converted at daily FX closes
+    /// Requires the currency of each account to be set
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reporting_currency: Option<String>,
 }
 
 impl Default for ProfitBySymbolParams {
@@ -21,6 +26,7 @@ impl Default for ProfitBySymbolParams {
         Self {
             account_number: None,
             min_profit: None,
+            reporting_currency: None,
         }
     }
 }
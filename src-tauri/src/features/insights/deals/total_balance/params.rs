@@ -6,16 +6,22 @@ use serde::{Deserialize, Serialize};
 /// Parameters for the total_balance insight
 #[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct TotalBalanceParams {
-    /// Optional account number (filename without .parquet extension)
+    /// Optional account number (filename without .parquet extension) or registered account name
     /// If not provided, will read from all deal files
     #[serde(skip_serializing_if = "Option::is_none")]
     pub account_number: Option<String>,
+
+    /// Optional currency to report amounts in (e.g. "USD"), converted at daily FX closes
+    /// Requires the currency of each account to be set
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reporting_currency: Option<String>,
 }
 
 impl Default for TotalBalanceParams {
     fn default() -> Self {
         Self {
             account_number: None,
+            reporting_currency: None,
         }
     }
 }
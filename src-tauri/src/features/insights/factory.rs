@@ -21,6 +21,14 @@ pub fn initialize_registry() -> &'static InsightRegistry {
         registry.register(crate::features::insights::deals::trade_entries_with_balance::TradeEntriesWithBalanceInsight::new());
         registry.register(crate::features::insights::deals::balance_entries::BalanceEntriesInsight::new());
         registry.register(crate::features::insights::deals::total_balance::TotalBalanceInsight::new());
+        registry.register(crate::features::insights::deals::mae_mfe::MaeMfeInsight::new());
+        registry.register(crate::features::insights::deals::positions::PositionsInsight::new());
+        registry.register(crate::features::insights::deals::execution_quality::ExecutionQualityInsight::new());
+        registry.register(crate::features::insights::assets::atr_by_hour::AtrByHourInsight::new());
+        registry.register(crate::features::insights::assets::daily_range_stats::DailyRangeStatsInsight::new());
+        registry.register(crate::features::insights::assets::return_distribution::ReturnDistributionInsight::new());
+        registry.register(crate::features::insights::assets::correlation_matrix::CorrelationMatrixInsight::new());
+        registry.register(crate::features::insights::assets::return_seasonality::ReturnSeasonalityInsight::new());
         
         registry
     })
@@ -14,4 +14,5 @@ pub use batch::execute_batch_insights;
 
 // Insight implementations
 pub mod deals;
+pub mod assets;
 
@@ -1,5 +1,6 @@
 //! Insight request and response models
 
+use crate::shared::error::AppError;
 use serde::{Deserialize, Serialize};
 
 /// Request to execute an insight
@@ -20,6 +21,9 @@ pub struct InsightResponse {
     pub data: Option<serde_json::Value>,
     /// Error message if execution failed
     pub error: Option<String>,
+    /// Machine-readable error code, when the error has one (e.g. "invalid_account")
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error_code: Option<String>,
     /// Column names for table rendering (extracted from result data)
     pub columns: Vec<String>,
 }
@@ -31,6 +35,7 @@ impl InsightResponse {
             success: true,
             data: Some(data),
             error: None,
+            error_code: None,
             columns,
         }
     }
@@ -41,9 +46,18 @@ impl InsightResponse {
             success: false,
             data: None,
             error: Some(message),
+            error_code: None,
             columns: Vec::new(),
         }
     }
+
+    /// Create an error response carrying the code of the error
+    pub fn from_error(error: AppError) -> Self {
+        Self {
+            error_code: error.code,
+            ..Self::error(error.message)
+        }
+    }
 }
 
 /// Batch request to execute multiple insights
@@ -64,6 +78,9 @@ pub struct BatchInsightItem {
     pub data: Option<serde_json::Value>,
     /// Error message if execution failed
     pub error: Option<String>,
+    /// Machine-readable error code, when the error has one (e.g. "invalid_account")
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error_code: Option<String>,
     /// Column names for table rendering (extracted from result data)
     pub columns: Vec<String>,
 }
@@ -76,6 +93,7 @@ impl BatchInsightItem {
             success: true,
             data: Some(data),
             error: None,
+            error_code: None,
             columns,
         }
     }
@@ -87,9 +105,18 @@ impl BatchInsightItem {
             success: false,
             data: None,
             error: Some(error_message),
+            error_code: None,
             columns: Vec::new(),
         }
     }
+
+    /// Create an error batch item carrying the code of the error
+    pub fn from_error(insight_id: String, error: AppError) -> Self {
+        Self {
+            error_code: error.code,
+            ..Self::error(insight_id, error.message)
+        }
+    }
 }
 
 /// Batch response containing results from multiple insights
@@ -2,14 +2,56 @@
 pub mod greet;
 pub mod asset;
 pub mod deals;
+pub mod accounts;
 pub mod insights;
+pub mod indicators;
+pub mod bars;
+pub mod backtest;
+pub mod replay;
+pub mod fx;
 
 pub use greet::greet;
 pub use asset::{
     pull_assets,
     pull_asset_by_symbol,
+    sync_asset_by_symbol,
     list_symbols,
     retrieve_asset_ochl,
+    retrieve_aligned_series,
+    import_ohlc,
+    build_bars_from_ticks,
+    retrieve_ticks_around,
+    get_asset_cache_usage,
+    delete_asset_symbol,
+    delete_asset_timeframe,
+    set_asset_cache_quota,
+    set_asset_symbol_pinned,
+    enforce_asset_cache_quota,
+    optimize_assets,
 };
-pub use deals::{validate_and_store_deals, read_deals_from_file, read_all_deals};
-pub use insights::{execute_insight, execute_batch_insights};
\ No newline at end of file
+pub use deals::{
+    validate_and_store_deals,
+    import_deals_from_paths,
+    import_mt5_statements,
+    save_import_profile,
+    list_import_profiles,
+    delete_import_profile,
+    preview_deals_import,
+    import_deals_with_profile,
+    validate_stored_deals,
+    read_deals_from_file,
+    read_all_deals,
+};
+pub use accounts::{
+    list_accounts,
+    get_account,
+    update_account,
+    delete_account,
+    set_account_timezone_offset,
+    set_account_currency,
+};
+pub use insights::{execute_insight, execute_batch_insights};
+pub use indicators::compute_indicators;
+pub use bars::retrieve_transformed_bars;
+pub use backtest::run_backtest;
+pub use replay::get_position_chart;
\ No newline at end of file
@@ -0,0 +1,26 @@
+//! Trade replay command handlers
+
+use crate::features::replay::model::PositionChart;
+use crate::features::replay::service::PositionChartService;
+use crate::shared::error::AppError;
+
+/// Tauri command: get_position_chart
+/// Retrieves the bars around a position's lifetime with a marker for every deal of the position
+/// 
+/// # Arguments
+/// * `account` - The account (deals file name, with or without .parquet)
+/// * `position_id` - The position identifier shared by the position's deals
+/// * `padding_bars` - Optional number of bars shown before the entry and after the exit (default 50)
+/// * `timeframe` - The timeframe of the bars (e.g., "15M")
+/// 
+/// Returns the bars, deal markers and SL/TP levels (when known), with times in UTC
+#[tauri::command]
+pub fn get_position_chart(
+    account: String,
+    position_id: i64,
+    padding_bars: Option<usize>,
+    timeframe: String,
+) -> Result<PositionChart, String> {
+    PositionChartService::get_position_chart(&account, position_id, padding_bars, &timeframe)
+        .map_err(AppError::into_command_error)
+}
@@ -0,0 +1,6 @@
+/// Trade replay feature module
+pub mod command;
+pub mod service;
+pub mod model;
+
+pub use command::get_position_chart;
@@ -0,0 +1,70 @@
+//! Trade replay data models
+
+use crate::features::asset::model::OHLCData;
+use serde::{Deserialize, Serialize};
+
+/// Direction of a deal
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DealSide {
+    Buy,
+    Sell,
+}
+
+/// Role of a deal within its position
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MarkerKind {
+    /// First deal opening the position
+    Entry,
+    /// Additional deal adding volume to the open position
+    ScaleIn,
+    /// Deal closing part of the volume
+    PartialClose,
+    /// Deal closing the remaining volume
+    Exit,
+    /// Deal closing the position and opening the opposite one (MT5 `DEAL_ENTRY_INOUT`)
+    Reversal,
+}
+
+/// Chart marker for one deal of a position
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DealMarker {
+    pub ticket: i64,
+    pub kind: MarkerKind,
+    pub side: DealSide,
+    /// Deal time converted to UTC (Unix seconds)
+    pub time: i64,
+    /// Open time of the bar the deal falls into (None when outside the returned bars)
+    pub bar_time: Option<i64>,
+    pub price: f64,
+    pub volume: f64,
+    pub profit: f64,
+    pub comment: String,
+}
+
+/// OHLC window around a position with its deal markers
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PositionChart {
+    pub account: String,
+    pub position_id: i64,
+    /// Symbol as recorded by the broker (e.g. "EURUSD.m")
+    pub symbol: String,
+    /// Cached asset symbol the bars were read from (e.g. "EURUSD")
+    pub asset_symbol: String,
+    pub timeframe: String,
+    /// Account timezone offset applied to the deal times (minutes from UTC)
+    pub timezone_offset_minutes: i64,
+    /// Direction of the opening deal
+    pub side: DealSide,
+    /// Time of the first deal (UTC, Unix seconds)
+    pub open_time: i64,
+    /// Time of the closing deal (None while the position is still open)
+    pub close_time: Option<i64>,
+    /// Stop-loss level, when it can be derived from the deals
+    pub stop_loss: Option<f64>,
+    /// Take-profit level, when it can be derived from the deals
+    pub take_profit: Option<f64>,
+    pub bars: Vec<OHLCData>,
+    pub markers: Vec<DealMarker>,
+}
@@ -0,0 +1,185 @@
+//! Trade replay service - OHLC window around a position with its deal markers
+
+use crate::shared::error::AppError;
+use crate::shared::utils::{timeframe, validation};
+use crate::features::asset::model::{OHLCData, TimeRange};
+use crate::features::asset::service::AssetService;
+use crate::features::accounts::service::AccountService;
+use crate::features::deals::model::{
+    Deal, DealRole, PositionStatus, DEAL_REASON_SL, DEAL_REASON_TP, DEAL_TYPE_BUY, DEAL_TYPE_SELL,
+};
+use crate::features::deals::positions::PositionService;
+use crate::features::deals::service::DealService;
+use crate::features::replay::model::{DealMarker, DealSide, MarkerKind, PositionChart};
+
+/// Bars shown before the entry and after the exit when not specified
+const DEFAULT_PADDING_BARS: usize = 50;
+const MAX_PADDING_BARS: usize = 5_000;
+
+/// Extra history loaded around the padded window to cover weekends and holidays
+const GAP_ALLOWANCE_SECONDS: i64 = 7 * 86_400;
+
+/// Service for position charts
+pub struct PositionChartService;
+
+impl PositionChartService {
+    /// Builds the chart of a position: bars around its lifetime plus one marker per deal
+    /// Deal times are converted to UTC with the account's timezone offset and the broker
+    /// symbol is matched to a cached asset symbol
+    pub fn get_position_chart(
+        account: &str,
+        position_id: i64,
+        padding_bars: Option<usize>,
+        timeframe: &str,
+    ) -> Result<PositionChart, AppError> {
+        validation::validate_non_empty(account, "account")?;
+        let timeframe_seconds = timeframe::parse_timeframe_seconds(timeframe)?;
+        let padding = padding_bars.unwrap_or(DEFAULT_PADDING_BARS);
+        if padding > MAX_PADDING_BARS {
+            return Err(AppError::new(format!(
+                "Padding cannot exceed {} bars",
+                MAX_PADDING_BARS
+            )));
+        }
+
+        let resolved = AccountService::resolve_one(account)?;
+        let account = resolved.id.as_str();
+        let offset_minutes = resolved.metadata.timezone_offset_minutes;
+
+        let mut deals: Vec<Deal> = DealService::read_deals_from_file(account)?
+            .into_iter()
+            .filter(|deal| {
+                deal.position_id == position_id
+                    && (deal.r#type == DEAL_TYPE_BUY || deal.r#type == DEAL_TYPE_SELL)
+            })
+            .collect();
+        if deals.is_empty() {
+            return Err(AppError::new(format!(
+                "Position {} not found in account {}",
+                position_id, account
+            )));
+        }
+        deals.sort_by_key(|deal| (deal.time_msc, deal.time, deal.ticket));
+
+        let to_utc = |time: i64| time - offset_minutes * 60;
+        let (mut markers, closed) = Self::build_markers(&deals, to_utc);
+        let (stop_loss, take_profit) = Self::protective_levels(&deals);
+
+        let symbol = deals[0].symbol.clone();
+        let asset_symbol = AssetService::resolve_cached_symbol(&symbol)?;
+
+        let open_time = markers[0].time;
+        let close_time = closed.then(|| markers[markers.len() - 1].time);
+
+        // Load generously around the position, then cut to exactly `padding` bars per side
+        let margin = padding as i64 * timeframe_seconds * 2 + GAP_ALLOWANCE_SECONDS;
+        let range = TimeRange {
+            start: Some(open_time - margin),
+            end: close_time.map(|time| time + margin),
+        };
+        let bars = AssetService::retrieve_asset_ochl(&asset_symbol, timeframe, Some(range))?;
+        if bars.is_empty() {
+            return Err(AppError::new(format!(
+                "No {} bars of {} around position {}",
+                timeframe, asset_symbol, position_id
+            )));
+        }
+
+        let bar_index = |time: i64| bars.partition_point(|bar| bar.time <= time).saturating_sub(1);
+        let first = bar_index(open_time).saturating_sub(padding);
+        let last = match close_time {
+            Some(time) => (bar_index(time) + padding).min(bars.len() - 1),
+            None => bars.len() - 1,
+        };
+        let bars: Vec<OHLCData> = bars[first..=last].to_vec();
+
+        for marker in &mut markers {
+            let index = bars.partition_point(|bar| bar.time <= marker.time);
+            marker.bar_time = index
+                .checked_sub(1)
+                .map(|i| bars[i].time)
+                .filter(|bar_time| marker.time < bar_time + timeframe_seconds);
+        }
+
+        Ok(PositionChart {
+            account: account.to_string(),
+            position_id,
+            symbol,
+            asset_symbol,
+            timeframe: timeframe.to_string(),
+            timezone_offset_minutes: offset_minutes,
+            side: markers[0].side,
+            open_time,
+            close_time,
+            stop_loss,
+            take_profit,
+            bars,
+            markers,
+        })
+    }
+
+    /// Classifies the deals of a position (sorted by time) with the PositionService
+    /// Returns the markers and whether the position ends closed
+    fn build_markers(deals: &[Deal], to_utc: impl Fn(i64) -> i64) -> (Vec<DealMarker>, bool) {
+        let deal_refs: Vec<&Deal> = deals.iter().collect();
+        let markers = deals
+            .iter()
+            .zip(PositionService::deal_roles(&deal_refs))
+            .map(|(deal, role)| DealMarker {
+                ticket: deal.ticket,
+                kind: match role {
+                    DealRole::Entry => MarkerKind::Entry,
+                    DealRole::ScaleIn => MarkerKind::ScaleIn,
+                    DealRole::PartialClose => MarkerKind::PartialClose,
+                    DealRole::Exit => MarkerKind::Exit,
+                    DealRole::Reversal => MarkerKind::Reversal,
+                },
+                side: if deal.r#type == DEAL_TYPE_BUY {
+                    DealSide::Buy
+                } else {
+                    DealSide::Sell
+                },
+                time: to_utc(deal.time),
+                bar_time: None,
+                price: deal.price,
+                volume: deal.volume,
+                profit: deal.profit,
+                comment: deal.comment.clone(),
+            })
+            .collect();
+
+        let closed = PositionService::build_positions(deals, 0)
+            .last()
+            .is_some_and(|position| position.status == PositionStatus::Closed);
+        (markers, closed)
+    }
+
+    /// Stop-loss and take-profit levels known from the deals
+    /// MT5 writes triggered levels into the closing deal's comment (e.g. "[sl 1.08512]");
+    /// otherwise the fill price of a deal closed by SL/TP is used
+    fn protective_levels(deals: &[Deal]) -> (Option<f64>, Option<f64>) {
+        let mut stop_loss = None;
+        let mut take_profit = None;
+
+        for deal in deals {
+            let comment = deal.comment.to_ascii_lowercase().replace(['[', ']'], " ");
+            let tokens: Vec<&str> = comment.split_whitespace().collect();
+            for pair in tokens.windows(2) {
+                let Ok(level) = pair[1].parse::<f64>() else { continue };
+                match pair[0] {
+                    "sl" => stop_loss = Some(level),
+                    "tp" => take_profit = Some(level),
+                    _ => {}
+                }
+            }
+
+            if deal.reason == DEAL_REASON_SL {
+                stop_loss = stop_loss.or(Some(deal.price));
+            } else if deal.reason == DEAL_REASON_TP {
+                take_profit = take_profit.or(Some(deal.price));
+            }
+        }
+
+        (stop_loss, take_profit)
+    }
+}
@@ -5,11 +5,41 @@ use features::{
     greet,
     pull_assets,
     pull_asset_by_symbol,
+    sync_asset_by_symbol,
     list_symbols,
     retrieve_asset_ochl,
+    retrieve_aligned_series,
+    import_ohlc,
+    build_bars_from_ticks,
+    retrieve_ticks_around,
+    get_asset_cache_usage,
+    delete_asset_symbol,
+    delete_asset_timeframe,
+    set_asset_cache_quota,
+    set_asset_symbol_pinned,
+    enforce_asset_cache_quota,
+    optimize_assets,
+    compute_indicators,
+    retrieve_transformed_bars,
+    run_backtest,
+    get_position_chart,
     validate_and_store_deals,
+    import_deals_from_paths,
+    import_mt5_statements,
+    save_import_profile,
+    list_import_profiles,
+    delete_import_profile,
+    preview_deals_import,
+    import_deals_with_profile,
+    validate_stored_deals,
     read_deals_from_file,
     read_all_deals,
+    list_accounts,
+    get_account,
+    update_account,
+    delete_account,
+    set_account_timezone_offset,
+    set_account_currency,
     execute_insight,
     execute_batch_insights,
 };
@@ -25,11 +55,41 @@ pub fn run() {
             greet,
             pull_assets,
             pull_asset_by_symbol,
+            sync_asset_by_symbol,
             list_symbols,
             retrieve_asset_ochl,
+            retrieve_aligned_series,
+            import_ohlc,
+            build_bars_from_ticks,
+            retrieve_ticks_around,
+            get_asset_cache_usage,
+            delete_asset_symbol,
+            delete_asset_timeframe,
+            set_asset_cache_quota,
+            set_asset_symbol_pinned,
+            enforce_asset_cache_quota,
+            optimize_assets,
+            compute_indicators,
+            retrieve_transformed_bars,
+            run_backtest,
+            get_position_chart,
             validate_and_store_deals,
+            import_deals_from_paths,
+            import_mt5_statements,
+            save_import_profile,
+            list_import_profiles,
+            delete_import_profile,
+            preview_deals_import,
+            import_deals_with_profile,
+            validate_stored_deals,
             read_deals_from_file,
             read_all_deals,
+            list_accounts,
+            get_account,
+            update_account,
+            delete_account,
+            set_account_timezone_offset,
+            set_account_currency,
             execute_insight,
             execute_batch_insights
         ])
@@ -15,6 +15,15 @@ impl AppError {
             code: None,
         }
     }
+
+    /// Error string returned by Tauri commands: `code: message` when the error has a code
+    /// (e.g. `invalid_account: ...`), so callers can branch on it, otherwise the message
+    pub fn into_command_error(self) -> String {
+        match self.code {
+            Some(code) => format!("{}: {}", code, self.message),
+            None => self.message,
+        }
+    }
 }
 
 impl std::fmt::Display for AppError {
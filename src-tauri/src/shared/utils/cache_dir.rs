@@ -39,14 +39,53 @@ pub fn get_symbol_cache_dir(symbol: &str) -> Result<PathBuf, AppError> {
     Ok(asset_dir.join(symbol))
 }
 
+/// Gets the path of the asset cache manifest (access times, pins, quota)
+/// Stored next to the asset directory so a full `pull_assets` does not wipe it
+pub fn get_asset_manifest_path() -> Result<PathBuf, AppError> {
+    let cache_dir = get_cache_dir()?;
+    Ok(cache_dir.join("asset_manifest.json"))
+}
+
 /// Gets the deals cache directory
 pub fn get_deals_cache_dir() -> Result<PathBuf, AppError> {
     let cache_dir = get_cache_dir()?;
     Ok(cache_dir.join("deals"))
 }
 
+/// Gets the path of the account registry (names, brokers, currencies, timezone offsets)
+/// Stored next to the deals directory so re-importing an account keeps its metadata
+pub fn get_account_registry_path() -> Result<PathBuf, AppError> {
+    let cache_dir = get_cache_dir()?;
+    Ok(cache_dir.join("accounts.json"))
+}
+
+/// Gets the path of the saved column-mapping profiles for deal imports
+pub fn get_import_profiles_path() -> Result<PathBuf, AppError> {
+    let cache_dir = get_cache_dir()?;
+    Ok(cache_dir.join("import_profiles.json"))
+}
+
 /// Ensures the cache directory exists
 pub fn ensure_cache_dir(path: &Path) -> Result<(), AppError> {
     std::fs::create_dir_all(path)
         .map_err(|e| AppError::new(format!("Failed to create cache directory: {}", e)))
 }
+
+/// Recursively collects the parquet files of a folder
+pub fn collect_parquet_files(dir: &Path, files: &mut Vec<PathBuf>) -> Result<(), AppError> {
+    let entries = std::fs::read_dir(dir)
+        .map_err(|e| AppError::new(format!("Failed to read directory {}: {}", dir.display(), e)))?;
+
+    for entry in entries {
+        let entry = entry
+            .map_err(|e| AppError::new(format!("Failed to read directory entry: {}", e)))?;
+        let path = entry.path();
+        if path.is_dir() {
+            collect_parquet_files(&path, files)?;
+        } else if path.extension().and_then(|s| s.to_str()) == Some("parquet") {
+            files.push(path);
+        }
+    }
+
+    Ok(())
+}
@@ -3,3 +3,5 @@
 pub mod validation;
 pub mod cache_dir;
 pub mod zip_extract;
+pub mod timeframe;
+pub mod statistics;
@@ -0,0 +1,74 @@
+//! Descriptive statistics over plain value slices
+//!
+//! Used by insights that aggregate a few thousand values after a Polars query,
+//! where collecting into a `Vec<f64>` is simpler than chaining more expressions.
+
+/// Arithmetic mean (None for an empty slice)
+pub fn mean(values: &[f64]) -> Option<f64> {
+    if values.is_empty() {
+        return None;
+    }
+    Some(values.iter().sum::<f64>() / values.len() as f64)
+}
+
+/// Sample standard deviation (None for fewer than two values)
+pub fn std_dev(values: &[f64]) -> Option<f64> {
+    if values.len() < 2 {
+        return None;
+    }
+    let mean = mean(values)?;
+    let variance = values.iter().map(|v| (v - mean).powi(2)).sum::<f64>() / (values.len() - 1) as f64;
+    Some(variance.sqrt())
+}
+
+/// Percentile (0-100) with linear interpolation between closest ranks
+/// `sorted` must be sorted ascending
+pub fn percentile(sorted: &[f64], percent: f64) -> Option<f64> {
+    if sorted.is_empty() {
+        return None;
+    }
+    let rank = (percent.clamp(0.0, 100.0) / 100.0) * (sorted.len() - 1) as f64;
+    let lower = rank.floor() as usize;
+    let upper = rank.ceil() as usize;
+    let weight = rank - lower as f64;
+    Some(sorted[lower] + (sorted[upper] - sorted[lower]) * weight)
+}
+
+/// Median (None for an empty slice); `sorted` must be sorted ascending
+pub fn median(sorted: &[f64]) -> Option<f64> {
+    percentile(sorted, 50.0)
+}
+
+/// Returns a copy of `values` sorted ascending (NaN values are dropped)
+pub fn sorted(values: &[f64]) -> Vec<f64> {
+    let mut sorted: Vec<f64> = values.iter().copied().filter(|v| !v.is_nan()).collect();
+    sorted.sort_by(|a, b| a.total_cmp(b));
+    sorted
+}
+
+/// Pearson correlation of two equally long series
+/// None when fewer than two pairs exist or either series is constant
+pub fn pearson(x: &[f64], y: &[f64]) -> Option<f64> {
+    let n = x.len().min(y.len());
+    if n < 2 {
+        return None;
+    }
+    let mean_x = mean(&x[..n])?;
+    let mean_y = mean(&y[..n])?;
+
+    let mut covariance = 0.0;
+    let mut variance_x = 0.0;
+    let mut variance_y = 0.0;
+    for i in 0..n {
+        let dx = x[i] - mean_x;
+        let dy = y[i] - mean_y;
+        covariance += dx * dy;
+        variance_x += dx * dx;
+        variance_y += dy * dy;
+    }
+
+    if variance_x == 0.0 || variance_y == 0.0 {
+        return None;
+    }
+    Some(covariance / (variance_x.sqrt() * variance_y.sqrt()))
+}
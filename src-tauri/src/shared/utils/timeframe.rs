@@ -0,0 +1,67 @@
+//! Timeframe parsing utilities
+//!
+//! Timeframes use the same notation as the asset cache file names:
+//! a positive count followed by a unit suffix, e.g. `30S`, `1M`, `15M`, `4H`, `1D`, `1W`.
+//! `M` means minutes (the cache stores minute bars as `1M.parquet`).
+
+use crate::shared::error::AppError;
+
+/// Seconds in one week
+const WEEK_SECONDS: i64 = 7 * 86_400;
+
+/// The Unix epoch is a Thursday; weekly buckets are shifted so they start on Monday
+const WEEK_ALIGNMENT_OFFSET_SECONDS: i64 = 4 * 86_400;
+
+/// Parses a timeframe string (e.g. "15M", "4H", "10S") into its length in seconds
+pub fn parse_timeframe_seconds(timeframe: &str) -> Result<i64, AppError> {
+    let timeframe = timeframe.trim();
+    let unit = timeframe
+        .chars()
+        .last()
+        .ok_or_else(|| AppError::new("Timeframe cannot be empty"))?;
+    let count_part = &timeframe[..timeframe.len() - unit.len_utf8()];
+
+    let count: i64 = count_part.parse().map_err(|_| {
+        AppError::new(format!(
+            "Invalid timeframe '{}'. Expected a count followed by S, M, H, D or W (e.g. \"15M\")",
+            timeframe
+        ))
+    })?;
+    if count <= 0 {
+        return Err(AppError::new(format!(
+            "Invalid timeframe '{}'. Count must be positive",
+            timeframe
+        )));
+    }
+
+    let unit_seconds = match unit.to_ascii_uppercase() {
+        'S' => 1,
+        'M' => 60,
+        'H' => 3_600,
+        'D' => 86_400,
+        'W' => WEEK_SECONDS,
+        _ => {
+            return Err(AppError::new(format!(
+                "Invalid timeframe unit in '{}'. Expected S, M, H, D or W",
+                timeframe
+            )))
+        }
+    };
+
+    count.checked_mul(unit_seconds).ok_or_else(|| {
+        AppError::new(format!(
+            "Invalid timeframe '{}'. Count is too large",
+            timeframe
+        ))
+    })
+}
+
+/// Returns the offset (in seconds) that bucket boundaries are aligned to
+/// Weekly timeframes start on Monday 00:00 UTC, everything else is aligned to the epoch
+pub fn bucket_alignment_offset(timeframe_seconds: i64) -> i64 {
+    if timeframe_seconds % WEEK_SECONDS == 0 {
+        WEEK_ALIGNMENT_OFFSET_SECONDS
+    } else {
+        0
+    }
+}
@@ -8,6 +8,8 @@ export interface FileImportResult {
   filename: string;
   success: boolean;
   message: string;
+  // Machine-readable code of the error (e.g. "invalid_account")
+  code?: string | null;
 }
 
 export interface Deal {
@@ -1,18 +1,52 @@
 import { Button } from "@/shared/ui/Button";
+import type { Account } from "../../types";
 import styles from "./Component.module.css";
 
 interface HeaderProps {
+  accounts: Account[];
+  // Selected account id, null for all imported accounts
+  account: string | null;
+  onAccountChange: (account: string | null) => void;
   onImportClick: () => void;
 }
 
-export function Header({ onImportClick }: HeaderProps) {
+// Display name of an account in the selector
+const accountLabel = (account: Account): string => {
+  const label = account.name ? `${account.name} (${account.id})` : account.id;
+  return account.is_backtest ? `${label} - backtest` : label;
+};
+
+export function Header({
+  accounts,
+  account,
+  onAccountChange,
+  onImportClick,
+}: HeaderProps) {
+  // Only accounts with deals can be shown; backtests are listed after the imported accounts
+  const options = accounts
+    .filter((option) => option.has_deals)
+    .sort((a, b) => Number(a.is_backtest) - Number(b.is_backtest));
+
   return (
     <header className={styles.header}>
       <h1 className={styles.title}>Performance</h1>
-      <Button variant="primary" shape="medium" onClick={onImportClick}>
-        Import
-      </Button>
+      <div className={styles.actions}>
+        <select
+          className={styles.select}
+          value={account ?? ""}
+          onChange={(e) => onAccountChange(e.target.value || null)}
+        >
+          <option value="">All accounts</option>
+          {options.map((option) => (
+            <option key={option.id} value={option.id}>
+              {accountLabel(option)}
+            </option>
+          ))}
+        </select>
+        <Button variant="primary" shape="medium" onClick={onImportClick}>
+          Import
+        </Button>
+      </div>
     </header>
   );
 }
-
@@ -3,4 +3,4 @@
  */
 
 export { useDeals } from "./use-deals";
-
+export { useAccounts } from "./use-accounts";
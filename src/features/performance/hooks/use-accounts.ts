@@ -0,0 +1,45 @@
+import { useState, useEffect, useCallback } from "react";
+import { invokeCommand } from "@/shared/services/tauri";
+import type { Account } from "../types";
+
+/**
+ * Hook for listing the accounts of the account registry
+ * @param autoFetch - If true, automatically fetches the accounts on mount
+ * @returns Object containing accounts array, loading state, error state, and fetch function
+ */
+export function useAccounts(autoFetch: boolean = true) {
+  const [accounts, setAccounts] = useState<Account[]>([]);
+  const [isLoading, setIsLoading] = useState(false);
+  const [error, setError] = useState<string | null>(null);
+
+  const fetchAccounts = useCallback(async (): Promise<void> => {
+    setIsLoading(true);
+    setError(null);
+
+    try {
+      const result = await invokeCommand<Account[]>("list_accounts");
+      setAccounts(result);
+    } catch (err) {
+      const errorMessage =
+        err instanceof Error ? err.message : "Failed to list accounts";
+      setError(errorMessage);
+      setAccounts([]);
+    } finally {
+      setIsLoading(false);
+    }
+  }, []);
+
+  // Auto-fetch on mount if autoFetch is enabled
+  useEffect(() => {
+    if (autoFetch) {
+      fetchAccounts();
+    }
+  }, [autoFetch, fetchAccounts]);
+
+  return {
+    accounts,
+    isLoading,
+    error,
+    fetchAccounts,
+  };
+}
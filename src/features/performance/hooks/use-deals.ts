@@ -4,8 +4,8 @@ import type { Deal } from "@/features/deals/types";
 
 /**
  * Hook for retrieving deals/trades from a Parquet file by account number
- * @param accountNumber - The account number (filename without .parquet extension), defaults to "5043757397"
- * @param autoFetch - If true, automatically fetches data on mount
+ * @param accountNumber - The account number (filename without .parquet extension), e.g. an id from list_accounts; nothing is fetched while it is null
+ * @param autoFetch - If true, automatically fetches data on mount and whenever the account changes
  * @returns Object containing deals array, loading state, error state, and fetch function
  */
 interface UseDealsReturn {
@@ -13,11 +13,11 @@ interface UseDealsReturn {
   isLoading: boolean;
   error: string | null;
   fetchDeals: (overrideAccountNumber?: string) => Promise<void>;
-  accountNumber: string;
+  accountNumber: string | null;
 }
 
 export function useDeals(
-  accountNumber: string = "5043757397",
+  accountNumber: string | null,
   autoFetch: boolean = true
 ): UseDealsReturn {
   const [deals, setDeals] = useState<Deal[]>([]);
@@ -27,9 +27,9 @@ export function useDeals(
 
   const fetchDeals = useCallback(
     async (overrideAccountNumber?: string): Promise<void> => {
-      const targetAccount = overrideAccountNumber ?? currentAccountNumber;
+      const targetAccount = overrideAccountNumber ?? accountNumber;
 
-      if (!targetAccount.trim()) {
+      if (!targetAccount?.trim()) {
         setError("Account number is required");
         return;
       }
@@ -60,16 +60,18 @@ export function useDeals(
         setIsLoading(false);
       }
     },
-    [currentAccountNumber]
+    [accountNumber]
   );
 
-  // Auto-fetch on mount if autoFetch is enabled
+  // Auto-fetch on mount and on account change if autoFetch is enabled
   useEffect(() => {
     if (autoFetch && accountNumber) {
-      fetchDeals();
+      fetchDeals(accountNumber);
+    } else if (!accountNumber) {
+      setDeals([]);
+      setCurrentAccountNumber(null);
     }
-    // eslint-disable-next-line react-hooks/exhaustive-deps
-  }, [autoFetch, accountNumber]);
+  }, [autoFetch, accountNumber, fetchDeals]);
 
   return {
     deals,
@@ -79,4 +81,3 @@ export function useDeals(
     accountNumber: currentAccountNumber,
   };
 }
-
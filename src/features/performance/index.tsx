@@ -1,55 +1,64 @@
 import { Container } from "@/shared/ui/Container";
 import { DealImportModal } from "../deals";
 import { Header, TradesList, InsightsGrid } from "./components";
+import { useAccounts } from "./hooks";
 import { useInsight } from "@/shared/insights";
 import { useBatchInsights } from "@/shared/insights";
+import type { BatchInsightConfig } from "@/shared/insights";
 import type { TradeEntriesParams } from "@/features/insights/deals/trade-entries/types";
 import type { Deal } from "@/features/deals/types";
-import { useState } from "react";
+import { useEffect, useMemo, useState } from "react";
 import { useKeyboardShortcut } from "@/shared/hooks";
 
+// Insights shown in the grid
+const BATCH_INSIGHT_IDS = [
+  "deals.total_balance",
+  "deals.trade_entries",
+  "deals.balance_entries",
+  "deals.all_entries",
+  "deals.trade_entries_with_balance",
+  "deals.profit_by_symbol",
+];
+
 export default function PerformanceScreen() {
   const [dealImportModalOpen, setDealImportModalOpen] = useState(false);
-  const { data: deals, isLoading, error } = useInsight<TradeEntriesParams, Deal>(
+  // Selected account id; null reads every imported account (backtests excluded)
+  const [account, setAccount] = useState<string | null>(null);
+  const { accounts, fetchAccounts } = useAccounts();
+
+  const parameters = useMemo<TradeEntriesParams>(
+    () => (account ? { account_number: account } : {}),
+    [account]
+  );
+  const { data: deals, isLoading, error, execute } = useInsight<TradeEntriesParams, Deal>(
     "deals.trade_entries",
-    { account_number: "5043757397" },
-    { autoFetch: true }
+    parameters
   );
 
   // Batch insights execution - all existing insights
+  const batchConfigs = useMemo<BatchInsightConfig[]>(
+    () =>
+      BATCH_INSIGHT_IDS.map((insight_id) => ({
+        insight_id,
+        parameters: account ? { account_number: account } : {},
+      })),
+    [account]
+  );
   const {
     data: batchResults,
     isLoading: isBatchLoading,
     error: batchError,
-  } = useBatchInsights(
-    [
-      {
-        insight_id: "deals.total_balance",
-        parameters: { account_number: "5043757397" },
-      },
-      {
-        insight_id: "deals.trade_entries",
-        parameters: { account_number: "5043757397" },
-      },
-      {
-        insight_id: "deals.balance_entries",
-        parameters: { account_number: "5043757397" },
-      },
-      {
-        insight_id: "deals.all_entries",
-        parameters: { account_number: "5043757397" },
-      },
-      {
-        insight_id: "deals.trade_entries_with_balance",
-        parameters: { account_number: "5043757397" },
-      },
-      {
-        insight_id: "deals.profit_by_symbol",
-        parameters: { account_number: "5043757397" },
-      },
-    ],
-    { autoFetch: true }
-  );
+    execute: executeBatch,
+  } = useBatchInsights(batchConfigs);
+
+  // Fetch on mount and whenever the selected account changes
+  useEffect(() => {
+    execute();
+  }, [execute]);
+
+  useEffect(() => {
+    executeBatch();
+  }, [executeBatch]);
 
   // Keyboard shortcut for Ctrl+D - opens DealImportModal
   useKeyboardShortcut(
@@ -60,14 +69,27 @@ export default function PerformanceScreen() {
     { ctrlKey: true }
   );
 
+  // Imports may add accounts and deals
+  const handleImportClose = () => {
+    setDealImportModalOpen(false);
+    fetchAccounts();
+    execute();
+    executeBatch();
+  };
+
   return (
     <Container variant="large" style={{ marginInline: "auto" }}>
       <DealImportModal
         open={dealImportModalOpen}
-        onClose={() => setDealImportModalOpen(false)}
+        onClose={handleImportClose}
       />
       <Container variant="large" bordered="none" padding="0">
-        <Header onImportClick={() => setDealImportModalOpen(true)} />
+        <Header
+          accounts={accounts}
+          account={account}
+          onAccountChange={setAccount}
+          onImportClick={() => setDealImportModalOpen(true)}
+        />
         <InsightsGrid
           results={batchResults || []}
           isLoading={isBatchLoading}
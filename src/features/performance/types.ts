@@ -0,0 +1,17 @@
+/**
+ * Performance feature types
+ */
+
+// Account as returned by list_accounts (registry metadata flattened in)
+export interface Account {
+  id: string;
+  name?: string;
+  broker?: string;
+  server?: string;
+  currency?: string;
+  leverage?: number;
+  timezone_offset_minutes: number;
+  tags: string[];
+  has_deals: boolean;
+  is_backtest: boolean;
+}
@@ -16,6 +16,8 @@ export interface BatchInsightItem {
   success: boolean;
   data?: Record<string, unknown>[];
   error?: string;
+  // Machine-readable code of the error (e.g. "invalid_account")
+  error_code?: string;
   columns: string[];
 }
 